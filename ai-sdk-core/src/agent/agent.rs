@@ -1,8 +1,9 @@
 use crate::error::GenerateTextError;
-use crate::tool::Tool;
+use crate::tool::{Tool, ToolApprovalResponse, UserContext};
 use crate::GenerateTextResult;
 use crate::StreamTextResult;
 use ai_sdk_provider::language_model::Message;
+use ai_sdk_provider::AbortSignal;
 use async_trait::async_trait;
 use std::sync::Arc;
 
@@ -41,8 +42,14 @@ pub struct AgentCallParameters {
     /// Full message array (alternative to prompt)
     pub messages: Option<Vec<Message>>,
 
-    /// Optional abort signal (future enhancement)
-    pub abort_signal: Option<()>,
+    /// Signal that cancels the call, including running tools
+    pub abort_signal: Option<AbortSignal>,
+
+    /// Decisions on the tool calls a previous call left pending
+    pub approvals: Vec<ToolApprovalResponse>,
+
+    /// Value every tool can read through [`ToolContext::get`](crate::ToolContext::get)
+    pub tool_context: Option<UserContext>,
 }
 
 impl AgentCallParameters {
@@ -52,6 +59,8 @@ impl AgentCallParameters {
             prompt: Some(prompt.into()),
             messages: None,
             abort_signal: None,
+            approvals: Vec::new(),
+            tool_context: None,
         }
     }
 
@@ -61,8 +70,34 @@ impl AgentCallParameters {
             prompt: None,
             messages: Some(messages),
             abort_signal: None,
+            approvals: Vec::new(),
+            tool_context: None,
         }
     }
+
+    /// Set a signal that cancels the call
+    pub fn with_abort_signal(mut self, abort_signal: AbortSignal) -> Self {
+        self.abort_signal = Some(abort_signal);
+        self
+    }
+
+    /// Set the decisions on the tool calls a previous call left pending
+    ///
+    /// Resume with the earlier messages followed by the response messages of
+    /// the paused call; the pending calls run or are denied before the next
+    /// model call.
+    pub fn with_approvals(mut self, approvals: Vec<ToolApprovalResponse>) -> Self {
+        self.approvals = approvals;
+        self
+    }
+
+    /// Attach a value every tool can read through
+    /// [`ToolContext::get`](crate::ToolContext::get), such as the request's
+    /// user or a database pool
+    pub fn with_tool_context<T: std::any::Any + Send + Sync>(mut self, value: T) -> Self {
+        self.tool_context = Some(Arc::new(value));
+        self
+    }
 }
 
 #[cfg(test)]
@@ -5,18 +5,16 @@
 
 #[allow(clippy::module_inception)]
 mod agent;
-mod step_result;
-mod stop_condition;
 mod tool_loop_agent;
 mod tool_loop_agent_settings;
 
-pub use agent::{Agent, AgentCallParameters};
-pub use step_result::StepResult;
-pub use stop_condition::{
+pub use crate::stop_condition::{
     has_tool_call, is_stop_condition_met, step_count_is, StopCondition, StopConditionContext,
 };
+pub use crate::StepResult;
+pub use agent::{Agent, AgentCallParameters};
 pub use tool_loop_agent::ToolLoopAgent;
 pub use tool_loop_agent_settings::{
     FinishContext, OnFinishCallback, OnStepFinishCallback, PrepareCallContext, PrepareCallFn,
-    PrepareStepContext, PrepareStepFn, ToolLoopAgentSettings,
+    PrepareStepContext, PrepareStepFn, PrepareStepResult, ToolLoopAgentSettings,
 };
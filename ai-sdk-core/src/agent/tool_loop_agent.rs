@@ -1,16 +1,24 @@
 use super::agent::{Agent, AgentCallParameters};
-use super::step_result::StepResult;
-use super::stop_condition::is_stop_condition_met;
-use super::tool_loop_agent_settings::{FinishContext, PrepareCallContext, ToolLoopAgentSettings};
-use crate::error::GenerateTextError;
-use crate::tool::{Tool, ToolExecutor};
+use super::tool_loop_agent_settings::{
+    FinishContext, PrepareCallContext, PrepareStepContext, ToolLoopAgentSettings,
+};
+use crate::error::{GenerateTextError, TimeoutError, TimeoutStage};
+use crate::generate_text::{assistant_message, repair_step_tool_calls, FinishEvent};
+use crate::stop_condition::{is_stop_condition_met, StopConditionContext};
+use crate::stream_text::{StepLoop, StreamCallbacks};
+use crate::tool::{unresolved_tool_calls, Tool, ToolApprovalResponse, ToolExecutor, UserContext};
+use crate::StepResult;
 use crate::{GenerateTextResult, StreamTextResult};
 use ai_sdk_provider::language_model::{
-    AssistantContentPart, CallOptions, Content, FinishReason, FunctionTool, Message,
-    Tool as ProviderTool, Usage, UserContentPart,
+    CallOptions, FinishReason, FunctionTool, Message, Tool as ProviderTool, Usage, UserContentPart,
 };
+use ai_sdk_provider::{abortable, AbortSignal};
 use async_trait::async_trait;
 use std::sync::Arc;
+use std::time::Instant;
+
+/// Step limit when no stop conditions are configured
+const DEFAULT_MAX_STEPS: u32 = 20;
 
 /// Tool loop agent that autonomously executes tools
 pub struct ToolLoopAgent {
@@ -53,16 +61,53 @@ impl ToolLoopAgent {
             );
         }
 
-        Ok(PreparedCall { messages })
+        Ok(PreparedCall {
+            messages,
+            abort_signal: params.abort_signal,
+            approvals: params.approvals,
+            tool_context: params.tool_context,
+        })
+    }
+
+    /// Prepare the call and run the prepare_call hook if provided
+    async fn prepare(
+        &self,
+        params: AgentCallParameters,
+    ) -> Result<PreparedCall, GenerateTextError> {
+        let mut prepared = self.prepare_call(params)?;
+        if let Some(prepare_call) = &self.settings.prepare_call {
+            prepared.messages = prepare_call(PrepareCallContext {
+                messages: prepared.messages,
+                agent: self,
+            })
+            .await?;
+        }
+        Ok(prepared)
     }
 
     /// Execute the tool loop
     async fn execute_loop(
         &self,
-        mut messages: Vec<Message>,
+        prepared: PreparedCall,
     ) -> Result<GenerateTextResult, GenerateTextError> {
+        let PreparedCall {
+            mut messages,
+            abort_signal,
+            approvals,
+            tool_context,
+        } = prepared;
+
         // Create tool executor with executable tools
-        let tool_executor = ToolExecutor::new(self.settings.tools.clone());
+        let tool_executor = ToolExecutor::new(self.settings.tools.clone())
+            .with_abort_signal(abort_signal.clone())
+            .with_user_context(tool_context)
+            .with_approval_handler(self.settings.tool_approval.clone())
+            .with_approvals(approvals)
+            .with_input_validation(self.settings.validate_tool_inputs)
+            .with_tool_call_repair(self.settings.repair_tool_call.clone())
+            .with_strict_tool_calls(self.settings.strict_tool_calls)
+            .with_execution_policy(self.settings.tool_execution_policy)
+            .with_timeouts(self.settings.timeouts);
 
         // Convert executable tools to provider tool definitions
         let provider_tools: Vec<ProviderTool> = self
@@ -79,7 +124,9 @@ impl ToolLoopAgent {
             })
             .collect();
 
+        let started = Instant::now();
         let mut steps = Vec::new();
+        let mut response_messages = Vec::new();
         let mut total_usage = Usage {
             input_tokens: Some(0),
             output_tokens: Some(0),
@@ -88,28 +135,40 @@ impl ToolLoopAgent {
             cached_input_tokens: None,
         };
 
-        // Use a default stop condition of 20 steps if none provided
-        let default_max_steps = 20;
         let mut step_count = 0;
 
-        loop {
+        // Run the tool calls a previous call left waiting for approval
+        let unresolved = unresolved_tool_calls(&messages);
+        let mut pending_approvals = tool_executor.pending_approvals(&unresolved);
+        if !unresolved.is_empty() && pending_approvals.is_empty() {
+            let execution = tool_executor.execute_step_tools(unresolved, 0, &messages);
+            let tool_results = abortable(abort_signal.as_ref(), execution).await??;
+            let tool_message = Message::Tool {
+                content: tool_results,
+            };
+            response_messages.push(tool_message.clone());
+            messages.push(tool_message);
+        }
+
+        while pending_approvals.is_empty() {
             // Check stop conditions before each step
+            let context = StopConditionContext {
+                steps: &steps,
+                elapsed: started.elapsed(),
+            };
             if !steps.is_empty()
-                && is_stop_condition_met(&self.settings.stop_conditions, &steps).await
+                && is_stop_condition_met(&self.settings.stop_conditions, &context).await
             {
                 break;
             }
 
             // Default: stop after 20 steps if no stop conditions provided
-            if self.settings.stop_conditions.is_empty() && step_count >= default_max_steps {
+            if self.settings.stop_conditions.is_empty() && step_count >= DEFAULT_MAX_STEPS {
                 break;
             }
 
-            step_count += 1;
-
             // Prepare call options
-            let options = CallOptions {
-                prompt: messages.clone(),
+            let mut options = CallOptions {
                 tools: if !provider_tools.is_empty() {
                     Some(provider_tools.clone())
                 } else {
@@ -120,16 +179,44 @@ impl ToolLoopAgent {
                 } else {
                     None
                 },
-                ..Default::default()
+                abort_signal: abort_signal.clone(),
+                ..self.settings.call_settings.call_options(messages.clone())
             };
 
-            // Call model
-            let response = self
-                .settings
-                .model
-                .do_generate(options)
-                .await
-                .map_err(GenerateTextError::ModelError)?;
+            // Let the prepare_step hook override this step
+            let mut model = self.settings.model.clone();
+            if let Some(prepare_step) = &self.settings.prepare_step {
+                let step_override = prepare_step(PrepareStepContext {
+                    model: self.settings.model.as_ref(),
+                    steps: &steps,
+                    step_number: step_count as usize,
+                    messages: &messages,
+                })
+                .await?;
+                model = step_override.apply(&model, &mut options);
+            }
+
+            step_count += 1;
+
+            // Call model with retry
+            let timeouts = &self.settings.timeouts;
+            let response = abortable(
+                abort_signal.as_ref(),
+                self.settings.retry_policy.retry(|| {
+                    let model = model.clone();
+                    let options = options.clone();
+                    async move {
+                        timeouts
+                            .limit(TimeoutStage::ModelCall, model.do_generate(options))
+                            .await?
+                    }
+                }),
+            )
+            .await?
+            .map_err(|e| match e.downcast::<TimeoutError>() {
+                Ok(timeout) => GenerateTextError::Timeout(*timeout),
+                Err(e) => GenerateTextError::ModelError(e),
+            })?;
 
             // Update usage
             if let (Some(total_input), Some(input)) =
@@ -149,90 +236,71 @@ impl ToolLoopAgent {
             }
 
             // Create step result
-            let step_result = StepResult {
-                content: response.content.clone(),
-                tool_calls: StepResult::extract_tool_calls(&response.content),
-                tool_results: None,
-                text: StepResult::extract_text(&response.content),
-                reasoning_text: StepResult::extract_reasoning(&response.content),
-                finish_reason: response.finish_reason,
-                usage: response.usage.clone(),
-                warnings: response.warnings.clone(),
-                request: response.request,
-                response: response.response,
-                provider_metadata: response.provider_metadata,
-            };
-
-            steps.push(step_result.clone());
+            let mut step = StepResult::new(step_count - 1, response);
+            abortable(
+                abort_signal.as_ref(),
+                repair_step_tool_calls(&tool_executor, &mut step, &messages),
+            )
+            .await?;
+            let tool_calls = step.tool_calls.clone();
+            let finish_reason = step.finish_reason;
+            let assistant = assistant_message(step.content.clone());
+            steps.push(step);
+            response_messages.push(assistant.clone());
 
-            // Call on_step_finish callback
-            if let Some(callback) = &self.settings.on_step_finish {
-                callback(step_result.clone()).await;
+            // Check if we should continue (no tool calls or not finishing with tool calls)
+            if tool_calls.is_empty() || finish_reason != FinishReason::ToolCalls {
+                if let Some(callback) = &self.settings.on_step_finish {
+                    callback(steps[steps.len() - 1].clone()).await;
+                }
+                break;
             }
 
-            // Check if we should continue (no tool calls or not finishing with tool calls)
-            if step_result.tool_calls.is_none() || response.finish_reason != FinishReason::ToolCalls
-            {
+            // Stop for calls that need a human decision
+            pending_approvals = tool_executor.pending_approvals(&tool_calls);
+            if !pending_approvals.is_empty() {
+                if let Some(callback) = &self.settings.on_step_finish {
+                    callback(steps[steps.len() - 1].clone()).await;
+                }
                 break;
             }
 
-            // Execute tools
-            let tool_calls = step_result.tool_calls.as_ref().unwrap();
-            let tool_results = tool_executor.execute_tools(tool_calls.clone()).await;
-
-            // Append assistant message with tool calls
-            messages.push(Message::Assistant {
-                content: response
-                    .content
-                    .into_iter()
-                    .filter_map(|c| match c {
-                        Content::Text(tp) => Some(AssistantContentPart::Text(tp)),
-                        Content::ToolCall(tc) => Some(AssistantContentPart::ToolCall(tc)),
-                        Content::Reasoning(rp) => Some(AssistantContentPart::Reasoning(rp)),
-                        _ => None,
-                    })
-                    .collect(),
-            });
+            // Execute tools; they see the history with the assistant message
+            messages.push(assistant);
+            let execution = tool_executor.execute_step_tools(tool_calls, step_count - 1, &messages);
+            let tool_results = abortable(abort_signal.as_ref(), execution).await??;
+            let step = steps.last_mut().expect("step was just pushed");
+            step.tool_results.extend(tool_results.iter().cloned());
 
-            // Append tool results
-            messages.push(Message::Tool {
+            // Call on_step_finish callback
+            if let Some(callback) = &self.settings.on_step_finish {
+                callback(steps[steps.len() - 1].clone()).await;
+            }
+
+            // Append the tool results
+            let tool_message = Message::Tool {
                 content: tool_results,
-            });
+            };
+            response_messages.push(tool_message.clone());
+            messages.push(tool_message);
         }
 
         // Call on_finish callback
-        if let Some(callback) = &self.settings.on_finish {
-            let last_step = steps.last().cloned().unwrap_or(StepResult {
-                content: vec![],
-                tool_calls: None,
-                tool_results: None,
-                text: String::new(),
-                reasoning_text: None,
-                finish_reason: FinishReason::Stop,
-                usage: Usage {
-                    input_tokens: Some(0),
-                    output_tokens: Some(0),
-                    total_tokens: Some(0),
-                    reasoning_tokens: None,
-                    cached_input_tokens: None,
-                },
-                warnings: vec![],
-                request: None,
-                response: None,
-                provider_metadata: None,
-            });
-
+        if let (Some(callback), Some(last_step)) = (&self.settings.on_finish, steps.last()) {
             callback(FinishContext {
                 steps: steps.clone(),
                 total_usage: total_usage.clone(),
-                last_step,
+                last_step: last_step.clone(),
             })
             .await;
         }
 
-        // Convert agent steps to generate_text steps
-        let generate_text_steps = convert_agent_steps_to_generate_text_steps(steps);
-        Ok(GenerateTextResult::new(generate_text_steps, total_usage))
+        Ok(GenerateTextResult::new(
+            steps,
+            total_usage,
+            response_messages,
+            pending_approvals,
+        ))
     }
 }
 
@@ -250,58 +318,89 @@ impl Agent for ToolLoopAgent {
         &self,
         params: AgentCallParameters,
     ) -> Result<GenerateTextResult, GenerateTextError> {
-        // Prepare call
-        let mut prepared = self.prepare_call(params)?;
-
-        // Call prepare_call hook if provided
-        if let Some(prepare_call) = &self.settings.prepare_call {
-            prepared.messages = prepare_call(PrepareCallContext {
-                messages: prepared.messages,
-                agent: self,
-            })
-            .await?;
-        }
+        let prepared = self.prepare(params).await?;
 
         // Execute the loop
-        self.execute_loop(prepared.messages).await
+        let run = self.execute_loop(prepared);
+        self.settings
+            .timeouts
+            .limit(TimeoutStage::Total, run)
+            .await?
     }
 
     async fn stream(
         &self,
-        _params: AgentCallParameters,
+        params: AgentCallParameters,
     ) -> Result<StreamTextResult, GenerateTextError> {
-        // Streaming not yet implemented for agents
-        Err(GenerateTextError::InvalidParameters(
-            "Streaming not yet supported for agents".into(),
-        ))
+        let prepared = self.prepare(params).await?;
+        let settings = &self.settings;
+
+        let tool_executor = ToolExecutor::new(settings.tools.clone())
+            .with_abort_signal(prepared.abort_signal.clone())
+            .with_user_context(prepared.tool_context)
+            .with_approval_handler(settings.tool_approval.clone())
+            .with_approvals(prepared.approvals)
+            .with_input_validation(settings.validate_tool_inputs)
+            .with_tool_call_repair(settings.repair_tool_call.clone())
+            .with_strict_tool_calls(settings.strict_tool_calls)
+            .with_execution_policy(settings.tool_execution_policy)
+            .with_timeouts(settings.timeouts);
+        let call_options = CallOptions {
+            tool_choice: (!settings.tools.is_empty()).then(|| settings.tool_choice.clone()),
+            abort_signal: prepared.abort_signal,
+            ..settings.call_settings.call_options(prepared.messages)
+        };
+        let step_loop = StepLoop {
+            model: settings.model.clone(),
+            tool_executor,
+            max_steps: if settings.stop_conditions.is_empty() {
+                DEFAULT_MAX_STEPS
+            } else {
+                u32::MAX
+            },
+            stop_conditions: settings.stop_conditions.clone(),
+            call_options,
+            retry_policy: settings.retry_policy.clone(),
+            timeouts: settings.timeouts,
+            prepare_step: settings.prepare_step.clone(),
+            on_step_finish: settings.on_step_finish.clone(),
+        };
+
+        let on_finish = settings.on_finish.clone().map(|callback| {
+            let on_finish: crate::OnFinishCallback = Arc::new(move |event: FinishEvent| {
+                let callback = callback.clone();
+                Box::pin(async move {
+                    if let Some(last_step) = event.steps.last().cloned() {
+                        callback(FinishContext {
+                            steps: event.steps,
+                            total_usage: event.total_usage,
+                            last_step,
+                        })
+                        .await;
+                    }
+                })
+            });
+            on_finish
+        });
+        Ok(step_loop.spawn(StreamCallbacks {
+            on_finish,
+            ..Default::default()
+        }))
     }
 }
 
 struct PreparedCall {
     messages: Vec<Message>,
-}
-
-// Helper to convert agent StepResults to generate_text StepResults
-fn convert_agent_steps_to_generate_text_steps(
-    steps: Vec<StepResult>,
-) -> Vec<crate::generate_text::StepResult> {
-    steps
-        .into_iter()
-        .enumerate()
-        .map(|(index, step)| crate::generate_text::StepResult {
-            step_index: index as u32,
-            response_content: step.content,
-            tool_calls: step.tool_calls.unwrap_or_default(),
-            finish_reason: step.finish_reason,
-            usage: step.usage,
-        })
-        .collect()
+    abort_signal: Option<AbortSignal>,
+    approvals: Vec<ToolApprovalResponse>,
+    tool_context: Option<UserContext>,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use ai_sdk_provider::language_model::TextPart;
+    use crate::agent::PrepareStepResult;
+    use ai_sdk_provider::language_model::{Content, TextPart, ToolChoice};
     use std::sync::Arc;
 
     // Mock model for testing
@@ -388,4 +487,374 @@ mod tests {
         assert_eq!(prepared.messages.len(), 2);
         assert!(matches!(prepared.messages[0], Message::System { .. }));
     }
+
+    #[tokio::test]
+    async fn test_generate_with_aborted_signal_skips_model_call() {
+        let model = crate::test_utils::MockLanguageModel::new();
+        let agent =
+            ToolLoopAgent::new(ToolLoopAgentSettings::builder(Arc::new(model.clone())).build());
+        let controller = ai_sdk_provider::AbortController::new();
+        controller.abort();
+
+        let result = agent
+            .generate(
+                AgentCallParameters::from_prompt("Hello").with_abort_signal(controller.signal()),
+            )
+            .await;
+
+        assert!(matches!(result, Err(GenerateTextError::Aborted(_))));
+        assert!(model.calls().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_generate_returns_response_messages() {
+        use crate::test_utils::{text_response, tool_call_response, EchoTool, MockLanguageModel};
+
+        let model = MockLanguageModel::new()
+            .with_generate(Ok(tool_call_response(
+                "echo",
+                serde_json::json!({ "text": "hi" }),
+            )))
+            .with_generate(Ok(text_response("Done")));
+        let settings = ToolLoopAgentSettings::builder(Arc::new(model))
+            .tools(vec![Arc::new(EchoTool)])
+            .build();
+        let agent = ToolLoopAgent::new(settings);
+
+        let result = agent
+            .generate(AgentCallParameters::from_prompt("Hello"))
+            .await
+            .unwrap();
+
+        let messages = result.response_messages();
+        assert_eq!(messages.len(), 3);
+        assert!(matches!(messages[0], Message::Assistant { .. }));
+        assert!(matches!(messages[1], Message::Tool { .. }));
+        assert!(matches!(messages[2], Message::Assistant { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_stream_runs_tool_loop_with_hooks() {
+        use crate::test_utils::{text_stream_parts, tool_call_stream_parts, EchoTool};
+        use crate::TextStreamPart;
+        use futures::StreamExt;
+
+        let model = crate::test_utils::MockLanguageModel::new()
+            .with_stream(tool_call_stream_parts(
+                "echo",
+                serde_json::json!({ "text": "hi" }),
+            ))
+            .with_stream(text_stream_parts(&["Hello", " world"]));
+        let events = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let record = |label: &'static str| {
+            let events = events.clone();
+            move |detail: String| events.lock().unwrap().push(format!("{label} {detail}"))
+        };
+
+        let prepare_step: crate::agent::PrepareStepFn = {
+            let record = record("prepare");
+            Arc::new(move |ctx| {
+                record(format!("{} {}", ctx.step_number, ctx.messages.len()));
+                Box::pin(async { Ok(PrepareStepResult::default()) })
+            })
+        };
+        let on_step_finish: crate::agent::OnStepFinishCallback = {
+            let record = record("step");
+            Arc::new(move |step| {
+                record(format!("{} {}", step.step_index, step.tool_results.len()));
+                Box::pin(async {})
+            })
+        };
+        let on_finish: crate::agent::OnFinishCallback = {
+            let record = record("finish");
+            Arc::new(move |ctx| {
+                record(format!("{} {}", ctx.steps.len(), ctx.last_step.text()));
+                Box::pin(async {})
+            })
+        };
+        let settings = ToolLoopAgentSettings::builder(Arc::new(model.clone()))
+            .tools(vec![Arc::new(EchoTool)])
+            .prepare_step(prepare_step)
+            .on_step_finish(on_step_finish)
+            .on_finish(on_finish)
+            .build();
+        let agent = ToolLoopAgent::new(settings);
+
+        let result = agent
+            .stream(AgentCallParameters::from_prompt("Hello"))
+            .await
+            .unwrap();
+        let parts: Vec<_> = result
+            .full_stream()
+            .map(|part| part.unwrap())
+            .collect()
+            .await;
+
+        assert!(matches!(
+            parts[0],
+            TextStreamPart::StepStart { step_index: 0, .. }
+        ));
+        assert!(parts
+            .iter()
+            .any(|part| matches!(part, TextStreamPart::ToolResult(r) if r.tool_name == "echo")));
+        assert!(parts
+            .iter()
+            .any(|part| matches!(part, TextStreamPart::StepStart { step_index: 1, .. })));
+        assert!(matches!(parts.last(), Some(TextStreamPart::Finish { .. })));
+        assert_eq!(result.text().await.unwrap(), "Hello world");
+        assert_eq!(model.calls()[0].tool_choice, Some(ToolChoice::Auto));
+        assert_eq!(model.calls()[1].prompt.len(), 3);
+        assert_eq!(
+            *events.lock().unwrap(),
+            [
+                "prepare 0 1",
+                "step 0 1",
+                "prepare 1 3",
+                "step 1 0",
+                "finish 2 Hello world"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_prepare_step_overrides_step() {
+        use crate::test_utils::{text_response, tool_call_response, EchoTool, MockLanguageModel};
+
+        let model = MockLanguageModel::new()
+            .with_generate(Ok(tool_call_response("echo", serde_json::json!({}))));
+        let cheap_model = MockLanguageModel::new().with_generate(Ok(text_response("Done")));
+        let prepare_step: crate::agent::PrepareStepFn = {
+            let cheap_model = cheap_model.clone();
+            Arc::new(move |ctx| {
+                let result = if ctx.step_number == 0 {
+                    PrepareStepResult::default().with_tool_choice(ToolChoice::Tool {
+                        tool_name: "echo".into(),
+                    })
+                } else {
+                    // Answer with the cheaper model from the tool exchange only
+                    PrepareStepResult::default()
+                        .with_model(Arc::new(cheap_model.clone()))
+                        .with_active_tools(Vec::<String>::new())
+                        .with_messages(ctx.messages[1..].to_vec())
+                        .with_call_settings(crate::CallSettings {
+                            temperature: Some(0.1),
+                            ..Default::default()
+                        })
+                };
+                Box::pin(async move { Ok(result) })
+            })
+        };
+        let settings = ToolLoopAgentSettings::builder(Arc::new(model.clone()))
+            .tools(vec![Arc::new(EchoTool)])
+            .prepare_step(prepare_step)
+            .build();
+        let agent = ToolLoopAgent::new(settings);
+
+        let result = agent
+            .generate(AgentCallParameters::from_prompt("Hello"))
+            .await
+            .unwrap();
+
+        assert_eq!(result.text(), "Done");
+        let calls = model.calls();
+        assert_eq!(calls.len(), 1);
+        assert_eq!(
+            calls[0].tool_choice,
+            Some(ToolChoice::Tool {
+                tool_name: "echo".into()
+            })
+        );
+        let cheap_calls = cheap_model.calls();
+        assert_eq!(cheap_calls.len(), 1);
+        assert!(cheap_calls[0].tools.is_none());
+        assert!(cheap_calls[0].tool_choice.is_none());
+        assert_eq!(cheap_calls[0].temperature, Some(0.1));
+        assert_eq!(cheap_calls[0].prompt.len(), 2);
+        // The override does not change the conversation kept by the agent
+        assert_eq!(result.response_messages().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_stream_honours_stop_conditions() {
+        use crate::test_utils::{text_stream_parts, tool_call_stream_parts, EchoTool};
+
+        let model = crate::test_utils::MockLanguageModel::new()
+            .with_stream(tool_call_stream_parts("echo", serde_json::json!({})))
+            .with_stream(text_stream_parts(&["Unused"]));
+        let settings = ToolLoopAgentSettings::builder(Arc::new(model.clone()))
+            .tools(vec![Arc::new(EchoTool)])
+            .stop_when(crate::stop_condition::has_tool_call("echo"))
+            .build();
+        let agent = ToolLoopAgent::new(settings);
+
+        let result = agent
+            .stream(AgentCallParameters::from_prompt("Hello"))
+            .await
+            .unwrap();
+
+        assert_eq!(result.steps().await.unwrap().len(), 1);
+        assert_eq!(model.calls().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_model_call_timeout() {
+        let model = crate::test_utils::MockLanguageModel::new().hanging();
+        let settings = ToolLoopAgentSettings::builder(Arc::new(model))
+            .timeouts(crate::Timeouts::new().with_model_call(std::time::Duration::from_millis(20)))
+            .build();
+        let agent = ToolLoopAgent::new(settings);
+
+        let result = agent
+            .generate(AgentCallParameters::from_prompt("Hello"))
+            .await;
+
+        assert!(matches!(
+            result,
+            Err(GenerateTextError::Timeout(error)) if error.stage == TimeoutStage::ModelCall
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_call_settings_apply_to_every_step() {
+        use crate::test_utils::{text_response, tool_call_response, EchoTool, MockLanguageModel};
+        use ai_sdk_provider::JsonValue;
+
+        let model = MockLanguageModel::new()
+            .with_generate(Ok(tool_call_response(
+                "echo",
+                serde_json::json!({ "text": "hi" }),
+            )))
+            .with_generate(Ok(text_response("Done")));
+        let settings = ToolLoopAgentSettings::builder(Arc::new(model.clone()))
+            .tools(vec![Arc::new(EchoTool)])
+            .temperature(0.3)
+            .max_tokens(256)
+            .seed(42)
+            .stop_sequences(vec!["STOP".into()])
+            .header("x-request-source", "test")
+            .provider_option("openai", "reasoningEffort", JsonValue::String("low".into()))
+            .build();
+        let agent = ToolLoopAgent::new(settings);
+
+        agent
+            .generate(AgentCallParameters::from_prompt("Hello"))
+            .await
+            .unwrap();
+
+        let calls = model.calls();
+        assert_eq!(calls.len(), 2);
+        for call in &calls {
+            assert_eq!(call.temperature, Some(0.3));
+            assert_eq!(call.max_output_tokens, Some(256));
+            assert_eq!(call.seed, Some(42));
+            assert_eq!(call.stop_sequences, Some(vec!["STOP".to_string()]));
+            assert_eq!(call.tool_choice, Some(ToolChoice::Auto));
+            assert_eq!(call.headers.as_ref().unwrap()["x-request-source"], "test");
+            assert_eq!(
+                call.provider_options.as_ref().unwrap()["openai"]["reasoningEffort"],
+                JsonValue::String("low".into())
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retries_retryable_model_errors() {
+        use crate::test_utils::{text_response, MockLanguageModel};
+        use ai_sdk_provider::ApiCallError;
+
+        let model = MockLanguageModel::new()
+            .with_generate(Err(
+                ApiCallError::new("server error", "url").with_status_code(500)
+            ))
+            .with_generate(Ok(text_response("Hello")));
+        let settings = ToolLoopAgentSettings::builder(Arc::new(model.clone()))
+            .retry_policy(
+                crate::RetryPolicy::default()
+                    .with_initial_delay(std::time::Duration::from_millis(1)),
+            )
+            .build();
+        let agent = ToolLoopAgent::new(settings);
+
+        let result = agent
+            .generate(AgentCallParameters::from_prompt("Hello"))
+            .await
+            .unwrap();
+
+        assert_eq!(result.text(), "Hello");
+        assert_eq!(model.calls().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_generate_pauses_for_approval_and_resumes() {
+        use crate::test_utils::{text_response, tool_call_response, DeleteTool, MockLanguageModel};
+
+        let model = MockLanguageModel::new()
+            .with_generate(Ok(tool_call_response(
+                "delete",
+                serde_json::json!({ "id": 7 }),
+            )))
+            .with_generate(Ok(text_response("Deleted")));
+        let agent = ToolLoopAgent::new(
+            ToolLoopAgentSettings::builder(Arc::new(model.clone()))
+                .tools(vec![Arc::new(DeleteTool)])
+                .build(),
+        );
+
+        let paused = agent
+            .generate(AgentCallParameters::from_prompt("Delete resource 7"))
+            .await
+            .unwrap();
+        assert_eq!(paused.pending_approvals().len(), 1);
+        assert_eq!(model.calls().len(), 1);
+
+        let mut history = vec![Message::User {
+            content: vec![UserContentPart::Text {
+                text: "Delete resource 7".into(),
+            }],
+        }];
+        history.extend(paused.response_messages().iter().cloned());
+        let resumed = agent
+            .generate(
+                AgentCallParameters::from_messages(history)
+                    .with_approvals(vec![ToolApprovalResponse::approve("call_1")]),
+            )
+            .await
+            .unwrap();
+
+        assert!(resumed.pending_approvals().is_empty());
+        assert_eq!(resumed.text(), "Deleted");
+        assert_eq!(model.calls().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_stream_passes_tool_context() {
+        use crate::test_utils::{
+            text_stream_parts, tool_call_stream_parts, ContextTool, MockLanguageModel, RequestId,
+        };
+
+        let model = MockLanguageModel::new()
+            .with_stream(tool_call_stream_parts("context", serde_json::json!({})))
+            .with_stream(text_stream_parts(&["Done"]));
+        let agent = ToolLoopAgent::new(
+            ToolLoopAgentSettings::builder(Arc::new(model))
+                .instructions("Be brief")
+                .tools(vec![Arc::new(ContextTool)])
+                .build(),
+        );
+
+        let result = agent
+            .stream(AgentCallParameters::from_prompt("Hi").with_tool_context(RequestId("req-7")))
+            .await
+            .unwrap();
+        let steps = result.steps().await.unwrap();
+
+        // System, user and assistant messages
+        assert_eq!(
+            steps[0].tool_results[0].output,
+            ai_sdk_provider::language_model::ToolResultOutput::Text {
+                value: "req-7/0/3".into(),
+                provider_metadata: None,
+            }
+        );
+    }
 }
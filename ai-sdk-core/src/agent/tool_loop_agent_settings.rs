@@ -1,9 +1,16 @@
-use super::step_result::StepResult;
-use super::stop_condition::StopCondition;
 use super::tool_loop_agent::ToolLoopAgent;
-use crate::tool::Tool;
-use ai_sdk_provider::language_model::{LanguageModel, Message, ToolChoice, Usage};
+use crate::call_settings::CallSettings;
+use crate::retry::RetryPolicy;
+use crate::stop_condition::StopCondition;
+use crate::timeout::Timeouts;
+use crate::tool::{Tool, ToolApprovalFn, ToolCallRepairFn, ToolExecutionPolicy};
+use crate::StepResult;
+use ai_sdk_provider::language_model::{
+    CallOptions, LanguageModel, Message, Tool as ProviderTool, ToolChoice, Usage,
+};
+use ai_sdk_provider::{JsonValue, SharedProviderOptions};
 use futures::future::BoxFuture;
+use std::collections::HashMap;
 use std::sync::Arc;
 
 /// Settings for configuring a ToolLoopAgent
@@ -37,6 +44,34 @@ pub struct ToolLoopAgentSettings {
 
     /// Hook to modify settings per step
     pub prepare_step: Option<PrepareStepFn>,
+
+    /// Time limits for the run, each model call and each tool execution
+    pub timeouts: Timeouts,
+
+    /// Model settings sent with every step; the tool choice comes from
+    /// `tool_choice`
+    pub call_settings: CallSettings,
+
+    /// Retry policy for each model call
+    pub retry_policy: RetryPolicy,
+
+    /// Handler asked to approve calls of tools that need approval; without
+    /// one the run stops at such calls
+    pub tool_approval: Option<ToolApprovalFn>,
+
+    /// Whether tool call arguments are validated against each tool's input
+    /// schema
+    pub validate_tool_inputs: bool,
+
+    /// Hook that fixes calls of unknown tools or with invalid arguments
+    pub repair_tool_call: Option<ToolCallRepairFn>,
+
+    /// Whether tool calls that stay invalid after repair fail the run
+    /// instead of getting an error result
+    pub strict_tool_calls: bool,
+
+    /// How the tool calls of a step are scheduled and isolated
+    pub tool_execution_policy: ToolExecutionPolicy,
 }
 
 // Callback type definitions
@@ -57,8 +92,14 @@ pub type PrepareCallFn = Arc<
 >;
 
 /// Function to prepare each step
+///
+/// The returned [`PrepareStepResult`] changes the next model call only;
+/// return `PrepareStepResult::default()` to keep the agent's settings.
 pub type PrepareStepFn = Arc<
-    dyn Fn(PrepareStepContext) -> BoxFuture<'static, Result<(), crate::error::GenerateTextError>>
+    dyn Fn(
+            PrepareStepContext,
+        )
+            -> BoxFuture<'static, Result<PrepareStepResult, crate::error::GenerateTextError>>
         + Send
         + Sync,
 >;
@@ -93,6 +134,127 @@ pub struct PrepareStepContext<'a> {
     pub messages: &'a [Message],
 }
 
+/// Overrides for a single step, returned by a prepare_step hook
+///
+/// Unset fields keep the agent's settings. Overrides never carry over to
+/// later steps.
+///
+/// # Example
+///
+/// ```
+/// use ai_sdk_core::agent::{PrepareStepFn, PrepareStepResult};
+/// use ai_sdk_core::ToolChoice;
+/// use std::sync::Arc;
+///
+/// // Search first, then answer without tools
+/// let prepare_step: PrepareStepFn = Arc::new(|ctx| {
+///     let result = if ctx.step_number == 0 {
+///         PrepareStepResult::default().with_tool_choice(ToolChoice::Tool {
+///             tool_name: "search".into(),
+///         })
+///     } else {
+///         PrepareStepResult::default().with_active_tools(Vec::<String>::new())
+///     };
+///     Box::pin(async move { Ok(result) })
+/// });
+/// ```
+#[derive(Clone, Default)]
+pub struct PrepareStepResult {
+    /// Model to call instead of the agent's model
+    pub model: Option<Arc<dyn LanguageModel>>,
+    /// Names of the tools offered to the model; others are hidden
+    pub active_tools: Option<Vec<String>>,
+    /// Tool choice for this step
+    pub tool_choice: Option<ToolChoice>,
+    /// Prompt for this step, replacing the conversation so far
+    pub messages: Option<Vec<Message>>,
+    /// Call settings for this step, replacing the agent's settings
+    pub call_settings: Option<CallSettings>,
+}
+
+impl PrepareStepResult {
+    /// Calls `model` in this step
+    pub fn with_model(mut self, model: Arc<dyn LanguageModel>) -> Self {
+        self.model = Some(model);
+        self
+    }
+
+    /// Offers only the named tools in this step
+    pub fn with_active_tools<I, S>(mut self, tools: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.active_tools = Some(tools.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// Forces a tool choice in this step
+    pub fn with_tool_choice(mut self, tool_choice: ToolChoice) -> Self {
+        self.tool_choice = Some(tool_choice);
+        self
+    }
+
+    /// Sends `messages` as the prompt of this step
+    pub fn with_messages(mut self, messages: Vec<Message>) -> Self {
+        self.messages = Some(messages);
+        self
+    }
+
+    /// Uses `settings` for this step
+    pub fn with_call_settings(mut self, settings: CallSettings) -> Self {
+        self.call_settings = Some(settings);
+        self
+    }
+
+    /// Applies the overrides to the options of a step and returns the model
+    /// to call
+    pub(crate) fn apply(
+        self,
+        model: &Arc<dyn LanguageModel>,
+        options: &mut CallOptions,
+    ) -> Arc<dyn LanguageModel> {
+        if let Some(settings) = self.call_settings {
+            let tool_choice = settings.tool_choice.clone().or(options.tool_choice.take());
+            *options = CallOptions {
+                tools: options.tools.take(),
+                tool_choice,
+                abort_signal: options.abort_signal.take(),
+                ..settings.call_options(std::mem::take(&mut options.prompt))
+            };
+        }
+        if let Some(messages) = self.messages {
+            options.prompt = messages;
+        }
+        if let Some(tool_choice) = self.tool_choice {
+            options.tool_choice = Some(tool_choice);
+        }
+        if let (Some(active), Some(tools)) = (self.active_tools, options.tools.as_mut()) {
+            tools.retain(|tool| match tool {
+                ProviderTool::Function(function) => active.contains(&function.name),
+                ProviderTool::ProviderDefined(defined) => active.contains(&defined.name),
+            });
+            if tools.is_empty() {
+                options.tools = None;
+                options.tool_choice = None;
+            }
+        }
+        self.model.unwrap_or_else(|| model.clone())
+    }
+}
+
+impl std::fmt::Debug for PrepareStepResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PrepareStepResult")
+            .field("model", &self.model.as_ref().map(|model| model.model_id()))
+            .field("active_tools", &self.active_tools)
+            .field("tool_choice", &self.tool_choice)
+            .field("messages", &self.messages)
+            .field("call_settings", &self.call_settings)
+            .finish()
+    }
+}
+
 impl ToolLoopAgentSettings {
     /// Create a new builder for ToolLoopAgentSettings
     pub fn builder(model: Arc<dyn LanguageModel>) -> ToolLoopAgentSettingsBuilder {
@@ -107,6 +269,14 @@ impl ToolLoopAgentSettings {
             on_finish: None,
             prepare_call: None,
             prepare_step: None,
+            timeouts: Timeouts::default(),
+            call_settings: CallSettings::default(),
+            retry_policy: RetryPolicy::default(),
+            tool_approval: None,
+            validate_tool_inputs: false,
+            repair_tool_call: None,
+            strict_tool_calls: false,
+            tool_execution_policy: ToolExecutionPolicy::default(),
         }
     }
 }
@@ -123,6 +293,14 @@ pub struct ToolLoopAgentSettingsBuilder {
     on_finish: Option<OnFinishCallback>,
     prepare_call: Option<PrepareCallFn>,
     prepare_step: Option<PrepareStepFn>,
+    timeouts: Timeouts,
+    call_settings: CallSettings,
+    retry_policy: RetryPolicy,
+    tool_approval: Option<ToolApprovalFn>,
+    validate_tool_inputs: bool,
+    repair_tool_call: Option<ToolCallRepairFn>,
+    strict_tool_calls: bool,
+    tool_execution_policy: ToolExecutionPolicy,
 }
 
 impl ToolLoopAgentSettingsBuilder {
@@ -156,6 +334,12 @@ impl ToolLoopAgentSettingsBuilder {
         self
     }
 
+    /// Add a stop condition
+    pub fn stop_when(mut self, condition: StopCondition) -> Self {
+        self.stop_conditions.push(condition);
+        self
+    }
+
     /// Set on_step_finish callback
     pub fn on_step_finish(mut self, callback: OnStepFinishCallback) -> Self {
         self.on_step_finish = Some(callback);
@@ -180,6 +364,150 @@ impl ToolLoopAgentSettingsBuilder {
         self
     }
 
+    /// Set time limits for the run, each model call and each tool execution
+    pub fn timeouts(mut self, timeouts: Timeouts) -> Self {
+        self.timeouts = timeouts;
+        self
+    }
+
+    /// Set the handler asked to approve calls of tools that need approval
+    ///
+    /// Without a handler, runs stop at such calls and return them as
+    /// pending approvals; see [`AgentCallParameters::with_approvals`] to
+    /// resume.
+    ///
+    /// [`AgentCallParameters::with_approvals`]: super::AgentCallParameters::with_approvals
+    pub fn tool_approval(mut self, handler: ToolApprovalFn) -> Self {
+        self.tool_approval = Some(handler);
+        self
+    }
+
+    /// Validate tool call arguments against each tool's input schema
+    ///
+    /// Arguments that do not match get an error result, or go through the
+    /// [`repair_tool_call`](Self::repair_tool_call) hook first.
+    pub fn validate_tool_inputs(mut self, validate: bool) -> Self {
+        self.validate_tool_inputs = validate;
+        self
+    }
+
+    /// Set the hook that fixes calls of unknown tools or with invalid
+    /// arguments before they fall back to an error result
+    pub fn repair_tool_call(mut self, repair: ToolCallRepairFn) -> Self {
+        self.repair_tool_call = Some(repair);
+        self
+    }
+
+    /// Fail with [`ToolError::NoSuchTool`] or
+    /// [`ToolError::InvalidToolArguments`] when a tool call stays invalid
+    /// after repair, instead of sending the model an error result
+    ///
+    /// [`ToolError::NoSuchTool`]: crate::ToolError::NoSuchTool
+    /// [`ToolError::InvalidToolArguments`]: crate::ToolError::InvalidToolArguments
+    pub fn strict_tool_calls(mut self, strict: bool) -> Self {
+        self.strict_tool_calls = strict;
+        self
+    }
+
+    /// Set how the tool calls of a step are scheduled and isolated
+    ///
+    /// See [`ToolExecutionPolicy`] for the defaults.
+    pub fn tool_execution_policy(mut self, policy: ToolExecutionPolicy) -> Self {
+        self.tool_execution_policy = policy;
+        self
+    }
+
+    /// Set temperature (0.0 to 2.0)
+    pub fn temperature(mut self, temperature: f32) -> Self {
+        self.call_settings.temperature = Some(temperature);
+        self
+    }
+
+    /// Set maximum tokens to generate in each step
+    pub fn max_tokens(mut self, max_tokens: u32) -> Self {
+        self.call_settings.max_tokens = Some(max_tokens);
+        self
+    }
+
+    /// Set nucleus sampling probability (0.0 to 1.0)
+    pub fn top_p(mut self, top_p: f32) -> Self {
+        self.call_settings.top_p = Some(top_p);
+        self
+    }
+
+    /// Set top-k sampling
+    pub fn top_k(mut self, top_k: u32) -> Self {
+        self.call_settings.top_k = Some(top_k);
+        self
+    }
+
+    /// Set presence penalty (-2.0 to 2.0)
+    pub fn presence_penalty(mut self, presence_penalty: f32) -> Self {
+        self.call_settings.presence_penalty = Some(presence_penalty);
+        self
+    }
+
+    /// Set frequency penalty (-2.0 to 2.0)
+    pub fn frequency_penalty(mut self, frequency_penalty: f32) -> Self {
+        self.call_settings.frequency_penalty = Some(frequency_penalty);
+        self
+    }
+
+    /// Set sequences that stop generation
+    pub fn stop_sequences(mut self, stop_sequences: Vec<String>) -> Self {
+        self.call_settings.stop_sequences = Some(stop_sequences);
+        self
+    }
+
+    /// Set random seed for deterministic sampling
+    pub fn seed(mut self, seed: i64) -> Self {
+        self.call_settings.seed = Some(seed);
+        self
+    }
+
+    /// Set additional HTTP headers sent with each request
+    pub fn headers(mut self, headers: HashMap<String, String>) -> Self {
+        self.call_settings.headers = Some(headers);
+        self
+    }
+
+    /// Add an HTTP header sent with each request
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.call_settings = self.call_settings.with_header(name, value);
+        self
+    }
+
+    /// Set provider-specific options, keyed by provider name
+    pub fn provider_options(mut self, provider_options: SharedProviderOptions) -> Self {
+        self.call_settings.provider_options = Some(provider_options);
+        self
+    }
+
+    /// Set one provider-specific option, e.g. `("openai", "reasoningEffort", "low")`
+    pub fn provider_option(
+        mut self,
+        provider: impl Into<String>,
+        key: impl Into<String>,
+        value: JsonValue,
+    ) -> Self {
+        self.call_settings = self
+            .call_settings
+            .with_provider_option(provider, key, value);
+        self
+    }
+
+    /// Replace all model call settings at once
+    pub fn call_settings(mut self, settings: CallSettings) -> Self {
+        self.call_settings = settings;
+        self
+    }
+
+    /// Set the retry policy for each model call
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
     /// Build the settings
     pub fn build(self) -> ToolLoopAgentSettings {
         ToolLoopAgentSettings {
@@ -193,6 +521,14 @@ impl ToolLoopAgentSettingsBuilder {
             on_finish: self.on_finish,
             prepare_call: self.prepare_call,
             prepare_step: self.prepare_step,
+            timeouts: self.timeouts,
+            call_settings: self.call_settings,
+            retry_policy: self.retry_policy,
+            tool_approval: self.tool_approval,
+            validate_tool_inputs: self.validate_tool_inputs,
+            repair_tool_call: self.repair_tool_call,
+            strict_tool_calls: self.strict_tool_calls,
+            tool_execution_policy: self.tool_execution_policy,
         }
     }
 }
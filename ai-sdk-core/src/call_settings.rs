@@ -0,0 +1,137 @@
+use ai_sdk_provider::language_model::{CallOptions, Message, ResponseFormat, ToolChoice};
+use ai_sdk_provider::{JsonValue, SharedProviderOptions};
+use std::collections::HashMap;
+
+/// Model settings sent with every call of a generation
+///
+/// The `generate_text`, `stream_text`, `generate_object` and `stream_object`
+/// builders fill this through their setters and apply it to each step, so a
+/// seed or provider option set once holds for the whole tool loop.
+///
+/// # Example
+///
+/// ```
+/// use ai_sdk_core::{CallSettings, JsonValue};
+///
+/// let settings = CallSettings {
+///     seed: Some(42),
+///     temperature: Some(0.2),
+///     ..Default::default()
+/// }
+/// .with_provider_option("openai", "reasoningEffort", JsonValue::String("low".into()));
+/// assert!(settings.provider_options.is_some());
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CallSettings {
+    /// Maximum number of tokens to generate
+    pub max_tokens: Option<u32>,
+    /// Sampling temperature
+    pub temperature: Option<f32>,
+    /// Nucleus sampling parameter
+    pub top_p: Option<f32>,
+    /// Top-k sampling parameter
+    pub top_k: Option<u32>,
+    /// Presence penalty
+    pub presence_penalty: Option<f32>,
+    /// Frequency penalty
+    pub frequency_penalty: Option<f32>,
+    /// Sequences that stop generation
+    pub stop_sequences: Option<Vec<String>>,
+    /// Random seed for deterministic sampling
+    pub seed: Option<i64>,
+    /// Tool selection strategy, `Auto` when tools are given and this is unset
+    pub tool_choice: Option<ToolChoice>,
+    /// Format of the model's response
+    pub response_format: Option<ResponseFormat>,
+    /// Additional HTTP headers for each request
+    pub headers: Option<HashMap<String, String>>,
+    /// Provider-specific options, keyed by provider name
+    pub provider_options: Option<SharedProviderOptions>,
+}
+
+impl CallSettings {
+    /// Adds an HTTP header sent with each request
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers
+            .get_or_insert_with(HashMap::new)
+            .insert(name.into(), value.into());
+        self
+    }
+
+    /// Sets one provider-specific option, e.g. `("openai", "reasoningEffort", "low")`
+    pub fn with_provider_option(
+        mut self,
+        provider: impl Into<String>,
+        key: impl Into<String>,
+        value: JsonValue,
+    ) -> Self {
+        self.provider_options
+            .get_or_insert_with(HashMap::new)
+            .entry(provider.into())
+            .or_default()
+            .insert(key.into(), value);
+        self
+    }
+
+    /// Builds the options for one model call with `prompt`
+    pub(crate) fn call_options(&self, prompt: Vec<Message>) -> CallOptions {
+        CallOptions {
+            prompt,
+            max_output_tokens: self.max_tokens,
+            temperature: self.temperature,
+            stop_sequences: self.stop_sequences.clone(),
+            top_p: self.top_p,
+            top_k: self.top_k,
+            presence_penalty: self.presence_penalty,
+            frequency_penalty: self.frequency_penalty,
+            response_format: self.response_format.clone(),
+            seed: self.seed,
+            tool_choice: self.tool_choice.clone(),
+            headers: self.headers.clone(),
+            provider_options: self.provider_options.clone(),
+            ..Default::default()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_call_options_carry_every_setting() {
+        let settings = CallSettings {
+            max_tokens: Some(100),
+            temperature: Some(0.5),
+            top_p: Some(0.9),
+            top_k: Some(40),
+            presence_penalty: Some(0.1),
+            frequency_penalty: Some(0.2),
+            stop_sequences: Some(vec!["END".into()]),
+            seed: Some(7),
+            tool_choice: Some(ToolChoice::Required),
+            response_format: Some(ResponseFormat::Text),
+            ..Default::default()
+        }
+        .with_header("x-trace", "abc")
+        .with_provider_option("openai", "reasoningEffort", JsonValue::String("low".into()));
+
+        let options = settings.call_options(vec![]);
+
+        assert_eq!(options.max_output_tokens, Some(100));
+        assert_eq!(options.temperature, Some(0.5));
+        assert_eq!(options.top_p, Some(0.9));
+        assert_eq!(options.top_k, Some(40));
+        assert_eq!(options.presence_penalty, Some(0.1));
+        assert_eq!(options.frequency_penalty, Some(0.2));
+        assert_eq!(options.stop_sequences, Some(vec!["END".to_string()]));
+        assert_eq!(options.seed, Some(7));
+        assert_eq!(options.tool_choice, Some(ToolChoice::Required));
+        assert_eq!(options.response_format, Some(ResponseFormat::Text));
+        assert_eq!(options.headers.unwrap()["x-trace"], "abc");
+        assert_eq!(
+            options.provider_options.unwrap()["openai"]["reasoningEffort"],
+            JsonValue::String("low".into())
+        );
+    }
+}
@@ -1,3 +1,7 @@
+use ai_sdk_provider::{AbortError, ApiCallError};
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
 use thiserror::Error;
 
 /// Error that can occur during text generation
@@ -17,7 +21,7 @@ pub enum GenerateTextError {
 
     /// Tool execution error
     #[error("Tool execution error: {0}")]
-    ToolError(#[from] ToolError),
+    ToolError(ToolError),
 
     /// Maximum steps reached without completion
     #[error("Maximum steps reached without completion")]
@@ -26,10 +30,18 @@ pub enum GenerateTextError {
     /// Invalid parameters provided
     #[error("Invalid parameters: {0}")]
     InvalidParameters(String),
+
+    /// Generation was cancelled through the abort signal
+    #[error(transparent)]
+    Aborted(#[from] AbortError),
+
+    /// A stage of the generation ran over its configured timeout
+    #[error(transparent)]
+    Timeout(#[from] TimeoutError),
 }
 
 /// Error that can occur during streaming text generation
-#[derive(Error, Debug)]
+#[derive(Error, Debug, Clone)]
 pub enum StreamTextError {
     /// Missing model - must call .model() before execute()
     #[error("Missing model - must call .model() before execute()")]
@@ -39,17 +51,162 @@ pub enum StreamTextError {
     #[error("Missing prompt - must call .prompt() before execute()")]
     MissingPrompt,
 
-    /// Model error
+    /// Model error, shared so the error stays cloneable
+    ///
+    /// Use [`api_call_error`](Self::api_call_error) to inspect a failed API
+    /// call.
     #[error("Model error: {0}")]
-    ModelError(String),
+    ModelError(#[source] Arc<dyn std::error::Error + Send + Sync>),
 
     /// Stream error
     #[error("Stream error: {0}")]
     StreamError(String),
 
+    /// The model stream failed after text was delivered
+    #[error(transparent)]
+    Interrupted(#[from] StreamInterruptedError),
+
     /// Tool execution error
     #[error("Tool execution error: {0}")]
-    ToolError(#[from] ToolError),
+    ToolError(ToolError),
+
+    /// Generation was cancelled through the abort signal
+    #[error(transparent)]
+    Aborted(#[from] AbortError),
+
+    /// A stage of the generation ran over its configured timeout
+    #[error(transparent)]
+    Timeout(#[from] TimeoutError),
+}
+
+impl From<ToolError> for GenerateTextError {
+    fn from(error: ToolError) -> Self {
+        match error {
+            ToolError::Timeout(e) => Self::Timeout(e),
+            other => Self::ToolError(other),
+        }
+    }
+}
+
+impl From<ToolError> for StreamTextError {
+    fn from(error: ToolError) -> Self {
+        match error {
+            ToolError::Timeout(e) => Self::Timeout(e),
+            other => Self::ToolError(other),
+        }
+    }
+}
+
+impl From<GenerateTextError> for StreamTextError {
+    fn from(error: GenerateTextError) -> Self {
+        match error {
+            GenerateTextError::MissingModel => Self::MissingModel,
+            GenerateTextError::MissingPrompt => Self::MissingPrompt,
+            GenerateTextError::ModelError(e) => Self::ModelError(Arc::from(e)),
+            GenerateTextError::ToolError(e) => Self::ToolError(e),
+            GenerateTextError::Aborted(e) => Self::Aborted(e),
+            GenerateTextError::Timeout(e) => Self::Timeout(e),
+            other => Self::StreamError(other.to_string()),
+        }
+    }
+}
+
+impl StreamTextError {
+    /// Returns the failed API call behind a model error, if any
+    pub fn api_call_error(&self) -> Option<&ApiCallError> {
+        match self {
+            Self::ModelError(error) => ApiCallError::find(error.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+/// A model stream that failed after output had already been delivered
+///
+/// Failures before the first chunk are retried with the configured
+/// [`RetryPolicy`](crate::RetryPolicy); once output has been delivered the
+/// stream cannot be replayed transparently, so the failure is reported with
+/// the text received so far. Callers can resume by sending `partial_text` back
+/// as an assistant message and asking the model to continue.
+#[derive(Error, Debug, Clone)]
+#[error("Stream interrupted after {} bytes of text: {message}", .partial_text.len())]
+pub struct StreamInterruptedError {
+    /// Index of the step that was interrupted
+    pub step_index: u32,
+    /// Text delivered by the interrupted step before the failure
+    pub partial_text: String,
+    /// Error reported by the provider
+    pub message: String,
+    /// Whether the failure looks transient (e.g. a dropped connection)
+    pub is_retryable: bool,
+}
+
+impl StreamInterruptedError {
+    /// Creates an interruption error from a provider stream error
+    pub fn new(
+        step_index: u32,
+        partial_text: impl Into<String>,
+        error: &(dyn std::error::Error + 'static),
+    ) -> Self {
+        Self {
+            step_index,
+            partial_text: partial_text.into(),
+            message: error.to_string(),
+            is_retryable: crate::retry::is_retryable_error(error),
+        }
+    }
+}
+
+/// Stage of a generation that a [`Timeouts`](crate::Timeouts) limit applies to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TimeoutStage {
+    /// The whole multi-step run
+    Total,
+    /// A single model call
+    ModelCall,
+    /// A single tool execution
+    ToolExecution,
+    /// The gap between two chunks of a model stream
+    StreamIdle,
+}
+
+impl fmt::Display for TimeoutStage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            TimeoutStage::Total => "generation",
+            TimeoutStage::ModelCall => "model call",
+            TimeoutStage::ToolExecution => "tool execution",
+            TimeoutStage::StreamIdle => "stream idle time",
+        })
+    }
+}
+
+/// A stage of a generation that ran over its configured timeout
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+#[error("{stage} timed out after {timeout:?}")]
+pub struct TimeoutError {
+    /// Stage that timed out
+    pub stage: TimeoutStage,
+    /// The limit that was exceeded
+    pub timeout: Duration,
+}
+
+impl TimeoutError {
+    /// Creates a timeout error for `stage`
+    pub fn new(stage: TimeoutStage, timeout: Duration) -> Self {
+        Self { stage, timeout }
+    }
+
+    /// Returns whether repeating the timed-out work may succeed
+    ///
+    /// Slow model calls and stalled streams are usually transient; tool and
+    /// total timeouts are not retried.
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self.stage,
+            TimeoutStage::ModelCall | TimeoutStage::StreamIdle
+        )
+    }
 }
 
 /// Error that can occur during embedding
@@ -74,13 +231,14 @@ pub enum EmbedError {
 }
 
 /// Error that can occur during tool execution
-#[derive(Error, Debug)]
+#[derive(Error, Debug, Clone)]
 pub enum ToolError {
     /// Tool execution failed
     #[error("Tool execution failed: {0}")]
     ExecutionError(String),
 
     /// Tool not found
+    #[deprecated(note = "use NoSuchTool")]
     #[error("Tool not found: {0}")]
     ToolNotFound(String),
 
@@ -91,6 +249,43 @@ pub enum ToolError {
     /// Tool execution denied
     #[error("Tool execution denied")]
     ExecutionDenied,
+
+    /// The model called a tool that is not available
+    #[error("Tool '{tool_name}' not found. Available tools: {}", .available_tools.join(", "))]
+    NoSuchTool {
+        /// Name the model called
+        tool_name: String,
+        /// Names of the tools the model could have called
+        available_tools: Vec<String>,
+    },
+
+    /// The model called a tool with arguments that do not parse or do not
+    /// match its input schema
+    #[error("Invalid arguments for tool '{tool_name}': {message}")]
+    InvalidToolArguments {
+        /// Name of the tool
+        tool_name: String,
+        /// Arguments as the model sent them
+        input: String,
+        /// What is wrong with the arguments
+        message: String,
+    },
+
+    /// Tool failure with a machine-readable code and optional details
+    #[error("{message}")]
+    Coded {
+        /// Code identifying the kind of failure, such as `"rate_limited"`
+        code: String,
+        /// Human-readable description
+        message: String,
+        /// Extra data about the failure
+        details: Option<serde_json::Value>,
+    },
+
+    /// A call ran over the [tool execution
+    /// limit](crate::Timeouts::with_tool_execution) of the generation
+    #[error(transparent)]
+    Timeout(#[from] TimeoutError),
 }
 
 impl ToolError {
@@ -100,6 +295,8 @@ impl ToolError {
     }
 
     /// Create a not found error
+    #[deprecated(note = "use NoSuchTool")]
+    #[allow(deprecated)]
     pub fn not_found(name: impl Into<String>) -> Self {
         ToolError::ToolNotFound(name.into())
     }
@@ -108,4 +305,66 @@ impl ToolError {
     pub fn invalid_input(msg: impl Into<String>) -> Self {
         ToolError::InvalidInput(msg.into())
     }
+
+    /// Create an error with a machine-readable code
+    pub fn coded(code: impl Into<String>, message: impl Into<String>) -> Self {
+        ToolError::Coded {
+            code: code.into(),
+            message: message.into(),
+            details: None,
+        }
+    }
+
+    /// Attach details, keeping the code and message of the error
+    pub fn with_details(self, details: serde_json::Value) -> Self {
+        ToolError::Coded {
+            code: self.code().to_string(),
+            message: self.message(),
+            details: Some(details),
+        }
+    }
+
+    /// Machine-readable code of the error
+    #[allow(deprecated)]
+    pub fn code(&self) -> &str {
+        match self {
+            ToolError::ExecutionError(_) => "execution_error",
+            ToolError::ToolNotFound(_) => "tool_not_found",
+            ToolError::InvalidInput(_) => "invalid_input",
+            ToolError::ExecutionDenied => "execution_denied",
+            ToolError::NoSuchTool { .. } => "no_such_tool",
+            ToolError::InvalidToolArguments { .. } => "invalid_tool_arguments",
+            ToolError::Coded { code, .. } => code,
+            ToolError::Timeout(_) => "timeout",
+        }
+    }
+
+    /// The error as `{ "code", "message", "details" }`, as sent to the model
+    /// by tools with [`ErrorMode::Json`](crate::ErrorMode::Json)
+    pub fn to_json(&self) -> serde_json::Value {
+        let mut json = serde_json::json!({
+            "code": self.code(),
+            "message": self.message(),
+        });
+        if let ToolError::Coded {
+            details: Some(details),
+            ..
+        } = self
+        {
+            json["details"] = details.clone();
+        }
+        json
+    }
+
+    /// Description of the error without the variant prefix
+    #[allow(deprecated)]
+    fn message(&self) -> String {
+        match self {
+            ToolError::ExecutionError(message)
+            | ToolError::ToolNotFound(message)
+            | ToolError::InvalidInput(message)
+            | ToolError::Coded { message, .. } => message.clone(),
+            other => other.to_string(),
+        }
+    }
 }
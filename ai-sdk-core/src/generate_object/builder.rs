@@ -4,12 +4,15 @@
 //! schema-based outputs from language models.
 
 use super::output_strategy::{OutputStrategy, ValidationContext, ValidationResult};
+use crate::call_settings::CallSettings;
 use crate::retry::RetryPolicy;
 use ai_sdk_provider::language_model::{
     CallOptions, CallWarning, Content, FinishReason, LanguageModel, Message, ResponseFormat,
     ResponseMetadata, TextPart, Usage,
 };
+use ai_sdk_provider::{abortable, AbortError, AbortSignal, JsonValue, SharedProviderOptions};
 use serde_json::Value;
+use std::collections::HashMap;
 use std::sync::Arc;
 use thiserror::Error;
 
@@ -43,6 +46,10 @@ pub enum GenerateObjectError {
     /// No text content in response
     #[error("No text content in model response")]
     NoTextContent,
+
+    /// Generation was cancelled through the abort signal
+    #[error(transparent)]
+    Aborted(#[from] AbortError),
 }
 
 /// Builder for generating structured objects.
@@ -52,9 +59,9 @@ pub struct GenerateObjectBuilder<S: OutputStrategy> {
     output_strategy: Option<Arc<S>>,
     schema_name: Option<String>,
     schema_description: Option<String>,
-    temperature: Option<f32>,
-    max_tokens: Option<u32>,
+    settings: CallSettings,
     retry_policy: RetryPolicy,
+    abort_signal: Option<AbortSignal>,
 }
 
 impl<S: OutputStrategy + 'static> GenerateObjectBuilder<S> {
@@ -66,9 +73,9 @@ impl<S: OutputStrategy + 'static> GenerateObjectBuilder<S> {
             output_strategy: None,
             schema_name: None,
             schema_description: None,
-            temperature: None,
-            max_tokens: None,
+            settings: CallSettings::default(),
             retry_policy: RetryPolicy::default(),
+            abort_signal: None,
         }
     }
 
@@ -113,13 +120,86 @@ impl<S: OutputStrategy + 'static> GenerateObjectBuilder<S> {
 
     /// Set temperature (0.0 to 2.0)
     pub fn temperature(mut self, temperature: f32) -> Self {
-        self.temperature = Some(temperature);
+        self.settings.temperature = Some(temperature);
         self
     }
 
     /// Set maximum tokens to generate
     pub fn max_tokens(mut self, max_tokens: u32) -> Self {
-        self.max_tokens = Some(max_tokens);
+        self.settings.max_tokens = Some(max_tokens);
+        self
+    }
+
+    /// Set nucleus sampling probability (0.0 to 1.0)
+    pub fn top_p(mut self, top_p: f32) -> Self {
+        self.settings.top_p = Some(top_p);
+        self
+    }
+
+    /// Set top-k sampling
+    pub fn top_k(mut self, top_k: u32) -> Self {
+        self.settings.top_k = Some(top_k);
+        self
+    }
+
+    /// Set presence penalty (-2.0 to 2.0)
+    pub fn presence_penalty(mut self, presence_penalty: f32) -> Self {
+        self.settings.presence_penalty = Some(presence_penalty);
+        self
+    }
+
+    /// Set frequency penalty (-2.0 to 2.0)
+    pub fn frequency_penalty(mut self, frequency_penalty: f32) -> Self {
+        self.settings.frequency_penalty = Some(frequency_penalty);
+        self
+    }
+
+    /// Set sequences that stop generation
+    pub fn stop_sequences(mut self, stop_sequences: Vec<String>) -> Self {
+        self.settings.stop_sequences = Some(stop_sequences);
+        self
+    }
+
+    /// Set random seed for deterministic sampling
+    pub fn seed(mut self, seed: i64) -> Self {
+        self.settings.seed = Some(seed);
+        self
+    }
+
+    /// Set additional HTTP headers sent with each request
+    pub fn headers(mut self, headers: HashMap<String, String>) -> Self {
+        self.settings.headers = Some(headers);
+        self
+    }
+
+    /// Add an HTTP header sent with each request
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.settings = self.settings.with_header(name, value);
+        self
+    }
+
+    /// Set provider-specific options, keyed by provider name
+    pub fn provider_options(mut self, provider_options: SharedProviderOptions) -> Self {
+        self.settings.provider_options = Some(provider_options);
+        self
+    }
+
+    /// Set one provider-specific option, e.g. `("openai", "reasoningEffort", "low")`
+    pub fn provider_option(
+        mut self,
+        provider: impl Into<String>,
+        key: impl Into<String>,
+        value: JsonValue,
+    ) -> Self {
+        self.settings = self.settings.with_provider_option(provider, key, value);
+        self
+    }
+
+    /// Replace all model call settings at once
+    ///
+    /// The response format and tool choice are always set by object mode.
+    pub fn call_settings(mut self, settings: CallSettings) -> Self {
+        self.settings = settings;
         self
     }
 
@@ -129,6 +209,12 @@ impl<S: OutputStrategy + 'static> GenerateObjectBuilder<S> {
         self
     }
 
+    /// Set a signal that cancels the generation
+    pub fn abort_signal(mut self, abort_signal: AbortSignal) -> Self {
+        self.abort_signal = Some(abort_signal);
+        self
+    }
+
     /// Execute the object generation
     pub async fn execute(self) -> Result<GenerateObjectResult<S::Result>, GenerateObjectError> {
         let model = self.model.ok_or(GenerateObjectError::MissingModel)?;
@@ -142,29 +228,28 @@ impl<S: OutputStrategy + 'static> GenerateObjectBuilder<S> {
 
         // Prepare call options
         let options = CallOptions {
-            prompt: messages.clone(),
             response_format: Some(ResponseFormat::Json {
                 schema: schema.clone(),
                 name: self.schema_name,
                 description: self.schema_description,
             }),
-            temperature: self.temperature,
-            max_output_tokens: self.max_tokens,
             tools: None, // No tools in object mode
             tool_choice: None,
-            ..Default::default()
+            abort_signal: self.abort_signal.clone(),
+            ..self.settings.call_options(messages.clone())
         };
 
         // Call model with retry
-        let response = self
-            .retry_policy
-            .retry(|| {
+        let response = abortable(
+            self.abort_signal.as_ref(),
+            self.retry_policy.retry(|| {
                 let model = model.clone();
                 let options = options.clone();
                 async move { model.do_generate(options).await }
-            })
-            .await
-            .map_err(GenerateObjectError::ModelError)?;
+            }),
+        )
+        .await?
+        .map_err(GenerateObjectError::ModelError)?;
 
         // Extract text from response
         let text = extract_text_from_content(&response.content)
@@ -6,14 +6,19 @@
 use super::output_strategy::{
     OutputStrategy, PartialValidation, ValidationContext, ValidationResult,
 };
+use crate::call_settings::CallSettings;
+use crate::error::StreamInterruptedError;
 use crate::retry::RetryPolicy;
+use crate::timeout::Timeouts;
 use crate::util::{is_deep_equal, parse_partial_json, ParseState};
 use ai_sdk_provider::language_model::{
     CallOptions, FinishReason, LanguageModel, Message, ResponseFormat, StreamError, StreamPart,
     Usage,
 };
+use ai_sdk_provider::{abortable, AbortError, AbortSignal, JsonValue, SharedProviderOptions};
 use futures::stream::{Stream, StreamExt};
 use serde_json::Value;
+use std::collections::HashMap;
 use std::pin::Pin;
 use std::sync::Arc;
 use thiserror::Error;
@@ -45,6 +50,10 @@ pub enum StreamObjectError {
     /// Stream error
     #[error("Stream error: {0}")]
     StreamError(String),
+
+    /// Generation was cancelled through the abort signal
+    #[error(transparent)]
+    Aborted(#[from] AbortError),
 }
 
 /// Builder for streaming structured objects.
@@ -54,9 +63,9 @@ pub struct StreamObjectBuilder<S: OutputStrategy> {
     output_strategy: Option<Arc<S>>,
     schema_name: Option<String>,
     schema_description: Option<String>,
-    temperature: Option<f32>,
-    max_tokens: Option<u32>,
+    settings: CallSettings,
     retry_policy: RetryPolicy,
+    abort_signal: Option<AbortSignal>,
 }
 
 impl<S: OutputStrategy + 'static> StreamObjectBuilder<S> {
@@ -68,9 +77,9 @@ impl<S: OutputStrategy + 'static> StreamObjectBuilder<S> {
             output_strategy: None,
             schema_name: None,
             schema_description: None,
-            temperature: None,
-            max_tokens: None,
+            settings: CallSettings::default(),
             retry_policy: RetryPolicy::default(),
+            abort_signal: None,
         }
     }
 
@@ -115,13 +124,86 @@ impl<S: OutputStrategy + 'static> StreamObjectBuilder<S> {
 
     /// Set temperature (0.0 to 2.0)
     pub fn temperature(mut self, temperature: f32) -> Self {
-        self.temperature = Some(temperature);
+        self.settings.temperature = Some(temperature);
         self
     }
 
     /// Set maximum tokens to generate
     pub fn max_tokens(mut self, max_tokens: u32) -> Self {
-        self.max_tokens = Some(max_tokens);
+        self.settings.max_tokens = Some(max_tokens);
+        self
+    }
+
+    /// Set nucleus sampling probability (0.0 to 1.0)
+    pub fn top_p(mut self, top_p: f32) -> Self {
+        self.settings.top_p = Some(top_p);
+        self
+    }
+
+    /// Set top-k sampling
+    pub fn top_k(mut self, top_k: u32) -> Self {
+        self.settings.top_k = Some(top_k);
+        self
+    }
+
+    /// Set presence penalty (-2.0 to 2.0)
+    pub fn presence_penalty(mut self, presence_penalty: f32) -> Self {
+        self.settings.presence_penalty = Some(presence_penalty);
+        self
+    }
+
+    /// Set frequency penalty (-2.0 to 2.0)
+    pub fn frequency_penalty(mut self, frequency_penalty: f32) -> Self {
+        self.settings.frequency_penalty = Some(frequency_penalty);
+        self
+    }
+
+    /// Set sequences that stop generation
+    pub fn stop_sequences(mut self, stop_sequences: Vec<String>) -> Self {
+        self.settings.stop_sequences = Some(stop_sequences);
+        self
+    }
+
+    /// Set random seed for deterministic sampling
+    pub fn seed(mut self, seed: i64) -> Self {
+        self.settings.seed = Some(seed);
+        self
+    }
+
+    /// Set additional HTTP headers sent with each request
+    pub fn headers(mut self, headers: HashMap<String, String>) -> Self {
+        self.settings.headers = Some(headers);
+        self
+    }
+
+    /// Add an HTTP header sent with each request
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.settings = self.settings.with_header(name, value);
+        self
+    }
+
+    /// Set provider-specific options, keyed by provider name
+    pub fn provider_options(mut self, provider_options: SharedProviderOptions) -> Self {
+        self.settings.provider_options = Some(provider_options);
+        self
+    }
+
+    /// Set one provider-specific option, e.g. `("openai", "reasoningEffort", "low")`
+    pub fn provider_option(
+        mut self,
+        provider: impl Into<String>,
+        key: impl Into<String>,
+        value: JsonValue,
+    ) -> Self {
+        self.settings = self.settings.with_provider_option(provider, key, value);
+        self
+    }
+
+    /// Replace all model call settings at once
+    ///
+    /// The response format and tool choice are always set by object mode.
+    pub fn call_settings(mut self, settings: CallSettings) -> Self {
+        self.settings = settings;
         self
     }
 
@@ -131,6 +213,15 @@ impl<S: OutputStrategy + 'static> StreamObjectBuilder<S> {
         self
     }
 
+    /// Set a signal that cancels the generation
+    ///
+    /// Aborting mid-stream ends the partial object stream with
+    /// [`ObjectStreamPart::Aborted`].
+    pub fn abort_signal(mut self, abort_signal: AbortSignal) -> Self {
+        self.abort_signal = Some(abort_signal);
+        self
+    }
+
     /// Execute the streaming object generation
     pub async fn execute(
         self,
@@ -146,37 +237,38 @@ impl<S: OutputStrategy + 'static> StreamObjectBuilder<S> {
 
         // Prepare call options
         let options = CallOptions {
-            prompt: messages.clone(),
             response_format: Some(ResponseFormat::Json {
                 schema: schema.clone(),
                 name: self.schema_name,
                 description: self.schema_description,
             }),
-            temperature: self.temperature,
-            max_output_tokens: self.max_tokens,
             tools: None, // No tools in object mode
             tool_choice: None,
-            ..Default::default()
+            abort_signal: self.abort_signal.clone(),
+            ..self.settings.call_options(messages.clone())
         };
 
-        // Call model streaming with retry
-        let stream_response = self
-            .retry_policy
-            .retry(|| {
-                let model = model.clone();
-                let options = options.clone();
-                async move { model.do_stream(options).await }
-            })
-            .await
-            .map_err(StreamObjectError::ModelError)?;
+        // Call model streaming, retrying failures before the first chunk
+        let stream_response = abortable(
+            self.abort_signal.as_ref(),
+            self.retry_policy
+                .retry_stream(&model, &options, &Timeouts::default()),
+        )
+        .await?
+        .map_err(StreamObjectError::ModelError)?;
 
         // Create channels for final results
         let (object_tx, object_rx) = oneshot::channel();
         let (usage_tx, usage_rx) = oneshot::channel();
 
         // Transform stream
-        let partial_stream =
-            create_partial_stream(stream_response.stream, strategy, object_tx, usage_tx);
+        let partial_stream = create_partial_stream(
+            stream_response.stream,
+            strategy,
+            self.abort_signal,
+            object_tx,
+            usage_tx,
+        );
 
         Ok(StreamObjectResult {
             partial_object_stream: Box::pin(partial_stream),
@@ -220,6 +312,15 @@ pub enum ObjectStreamPart<P> {
         /// The error message
         error: String,
     },
+    /// The model stream failed after output was delivered
+    ///
+    /// `error.partial_text` holds the JSON text received before the failure.
+    Interrupted {
+        /// The interruption details
+        error: StreamInterruptedError,
+    },
+    /// The stream was cancelled through the abort signal
+    Aborted,
 }
 
 /// Result of streaming object generation.
@@ -236,6 +337,7 @@ pub struct StreamObjectResult<P, R> {
 fn create_partial_stream<S: OutputStrategy + 'static>(
     mut model_stream: Pin<Box<dyn Stream<Item = Result<StreamPart, StreamError>> + Send>>,
     strategy: Arc<S>,
+    abort_signal: Option<AbortSignal>,
     object_tx: oneshot::Sender<S::Result>,
     usage_tx: oneshot::Sender<Usage>,
 ) -> impl Stream<Item = ObjectStreamPart<S::Partial>> {
@@ -246,7 +348,16 @@ fn create_partial_stream<S: OutputStrategy + 'static>(
         let mut latest_object: Option<S::Partial> = None;
         let mut is_first_delta = true;
 
-        while let Some(chunk_result) = model_stream.next().await {
+        loop {
+            let chunk_result = match abortable(abort_signal.as_ref(), model_stream.next()).await {
+                Ok(Some(chunk_result)) => chunk_result,
+                Ok(None) => break,
+                Err(_) => {
+                    yield ObjectStreamPart::Aborted;
+                    break;
+                }
+            };
+
             match chunk_result {
                 Ok(StreamPart::TextDelta { delta, .. }) => {
                     accumulated_text.push_str(&delta);
@@ -347,9 +458,13 @@ fn create_partial_stream<S: OutputStrategy + 'static>(
                     yield ObjectStreamPart::Finish { finish_reason };
                     break;
                 }
+                Err(StreamError::Aborted(_)) => {
+                    yield ObjectStreamPart::Aborted;
+                    break;
+                }
                 Err(e) => {
-                    yield ObjectStreamPart::Error {
-                        error: e.to_string(),
+                    yield ObjectStreamPart::Interrupted {
+                        error: StreamInterruptedError::new(0, accumulated_text, &e),
                     };
                     break;
                 }
@@ -363,6 +478,80 @@ fn create_partial_stream<S: OutputStrategy + 'static>(
 
 #[cfg(test)]
 mod tests {
-    // Note: These tests would require a mock LanguageModel implementation
-    // For now, they serve as documentation of the expected API
+    use super::*;
+    use crate::generate_object::ObjectOutputStrategy;
+    use crate::test_utils::{text_stream_parts, MockLanguageModel};
+    use ai_sdk_provider::ApiCallError;
+    use serde_json::json;
+    use std::time::Duration;
+
+    fn builder(model: MockLanguageModel) -> StreamObjectBuilder<ObjectOutputStrategy<Value>> {
+        stream_object()
+            .model(model)
+            .prompt("Describe a person")
+            .output_strategy(ObjectOutputStrategy::new(json!({ "type": "object" })))
+            .retry_policy(RetryPolicy::default().with_initial_delay(Duration::from_millis(1)))
+    }
+
+    #[tokio::test]
+    async fn test_retries_failure_before_first_chunk() {
+        let model = MockLanguageModel::new()
+            .with_stream_error(ApiCallError::new("rate limited", "url").with_status_code(429))
+            .with_stream(text_stream_parts(&[r#"{"name":"#, r#""Ada"}"#]));
+
+        let result = builder(model.clone()).execute().await.unwrap();
+        let parts: Vec<_> = result.partial_object_stream.collect().await;
+
+        assert_eq!(model.calls().len(), 2);
+        assert!(matches!(
+            parts.last(),
+            Some(ObjectStreamPart::Finish { .. })
+        ));
+        assert_eq!(result.object.await.unwrap(), json!({ "name": "Ada" }));
+    }
+
+    #[tokio::test]
+    async fn test_mid_stream_disconnect_is_reported_with_partial_text() {
+        let mut parts = text_stream_parts(&[r#"{"name":"#]);
+        parts.truncate(3); // StreamStart, TextStart, one delta
+        parts.push(Err(StreamError::ApiCall(
+            ApiCallError::new("connection reset", "url").with_retryable(true),
+        )));
+        let model = MockLanguageModel::new().with_stream(parts);
+
+        let result = builder(model.clone()).execute().await.unwrap();
+        let parts: Vec<_> = result.partial_object_stream.collect().await;
+
+        assert_eq!(model.calls().len(), 1);
+        match parts.last() {
+            Some(ObjectStreamPart::Interrupted { error }) => {
+                assert_eq!(error.partial_text, r#"{"name":"#);
+                assert!(error.is_retryable);
+            }
+            other => panic!("Expected Interrupted part, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_abort_ends_partial_stream() {
+        let mut parts = text_stream_parts(&[r#"{"name":"#]);
+        parts.truncate(3); // StreamStart, TextStart, one delta
+        let model = MockLanguageModel::new().with_stream(parts).hanging();
+        let controller = ai_sdk_provider::AbortController::new();
+
+        let result = builder(model)
+            .abort_signal(controller.signal())
+            .execute()
+            .await
+            .unwrap();
+        let aborter = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            controller.abort();
+        });
+        let parts: Vec<_> = result.partial_object_stream.collect().await;
+        aborter.await.unwrap();
+
+        assert!(matches!(parts.last(), Some(ObjectStreamPart::Aborted)));
+        assert!(result.object.await.is_err());
+    }
 }
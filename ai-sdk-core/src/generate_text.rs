@@ -1,27 +1,64 @@
-use crate::error::GenerateTextError;
+use crate::call_settings::CallSettings;
+use crate::error::{GenerateTextError, TimeoutError, TimeoutStage};
 use crate::retry::RetryPolicy;
-use crate::tool::{Tool, ToolExecutor};
+use crate::step_result::StepResult;
+use crate::stop_condition::{is_stop_condition_met, StopCondition, StopConditionContext};
+use crate::timeout::Timeouts;
+use crate::tool::{
+    unresolved_tool_calls, Tool, ToolApprovalFn, ToolApprovalRequest, ToolApprovalResponse,
+    ToolCallRepairFn, ToolExecutionPolicy, ToolExecutor, UserContext,
+};
 use ai_sdk_provider::language_model::{
-    AssistantContentPart, CallOptions, Content, FinishReason, LanguageModel, Message, TextPart,
+    AssistantContentPart, CallOptions, CallWarning, Content, FilePart, FinishReason, LanguageModel,
+    Message, ReasoningPart, RequestInfo, ResponseFormat, ResponseInfo, SourcePart,
     Tool as ProviderTool, ToolCallPart, ToolChoice, ToolResultPart, Usage, UserContentPart,
 };
+use ai_sdk_provider::{
+    abortable, AbortSignal, JsonValue, SharedProviderMetadata, SharedProviderOptions,
+};
 use futures::future::BoxFuture;
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Instant;
 
 /// Callback type for preliminary tool results (streaming tools)
 pub type OnPreliminaryToolResultCallback =
     Arc<dyn Fn(ToolResultPart) -> BoxFuture<'static, ()> + Send + Sync>;
 
+/// Callback invoked after each generation step completes
+pub type OnStepFinishCallback = Arc<dyn Fn(StepResult) -> BoxFuture<'static, ()> + Send + Sync>;
+
+/// Callback invoked once a generation completes successfully
+pub type OnFinishCallback = Arc<dyn Fn(FinishEvent) -> BoxFuture<'static, ()> + Send + Sync>;
+
+/// Callback invoked when a generation fails
+///
+/// The error is borrowed; copy what the returned future needs before it
+/// starts.
+pub type OnErrorCallback<E> = Arc<dyn Fn(&E) -> BoxFuture<'static, ()> + Send + Sync>;
+
 /// Builder for text generation with optional tool calling
 pub struct GenerateTextBuilder {
     model: Option<Arc<dyn LanguageModel>>,
     prompt: Option<Vec<Message>>,
     tools: Vec<Arc<dyn Tool>>,
     max_steps: u32,
-    temperature: Option<f32>,
-    max_tokens: Option<u32>,
+    stop_conditions: Vec<StopCondition>,
+    settings: CallSettings,
     retry_policy: RetryPolicy,
     on_preliminary_tool_result: Option<OnPreliminaryToolResultCallback>,
+    on_step_finish: Option<OnStepFinishCallback>,
+    on_finish: Option<OnFinishCallback>,
+    on_error: Option<OnErrorCallback<GenerateTextError>>,
+    abort_signal: Option<AbortSignal>,
+    timeouts: Timeouts,
+    tool_approval: Option<ToolApprovalFn>,
+    approvals: Vec<ToolApprovalResponse>,
+    tool_context: Option<UserContext>,
+    validate_tool_inputs: bool,
+    repair_tool_call: Option<ToolCallRepairFn>,
+    strict_tool_calls: bool,
+    tool_execution_policy: ToolExecutionPolicy,
 }
 
 impl GenerateTextBuilder {
@@ -32,10 +69,22 @@ impl GenerateTextBuilder {
             prompt: None,
             tools: Vec::new(),
             max_steps: 1,
-            temperature: None,
-            max_tokens: None,
+            stop_conditions: Vec::new(),
+            settings: CallSettings::default(),
             retry_policy: RetryPolicy::default(),
             on_preliminary_tool_result: None,
+            on_step_finish: None,
+            on_finish: None,
+            on_error: None,
+            abort_signal: None,
+            timeouts: Timeouts::default(),
+            tool_approval: None,
+            approvals: Vec::new(),
+            tool_context: None,
+            validate_tool_inputs: false,
+            repair_tool_call: None,
+            strict_tool_calls: false,
+            tool_execution_policy: ToolExecutionPolicy::default(),
         }
     }
 
@@ -72,15 +121,107 @@ impl GenerateTextBuilder {
         self
     }
 
+    /// Add a condition that ends the tool loop before `max_steps`
+    ///
+    /// Conditions are checked after each step whose tools were executed; the
+    /// loop stops once any of them is met.
+    pub fn stop_when(mut self, condition: StopCondition) -> Self {
+        self.stop_conditions.push(condition);
+        self
+    }
+
     /// Set temperature (0.0 to 2.0)
     pub fn temperature(mut self, temperature: f32) -> Self {
-        self.temperature = Some(temperature);
+        self.settings.temperature = Some(temperature);
         self
     }
 
     /// Set maximum tokens to generate
     pub fn max_tokens(mut self, max_tokens: u32) -> Self {
-        self.max_tokens = Some(max_tokens);
+        self.settings.max_tokens = Some(max_tokens);
+        self
+    }
+
+    /// Set nucleus sampling probability (0.0 to 1.0)
+    pub fn top_p(mut self, top_p: f32) -> Self {
+        self.settings.top_p = Some(top_p);
+        self
+    }
+
+    /// Set top-k sampling
+    pub fn top_k(mut self, top_k: u32) -> Self {
+        self.settings.top_k = Some(top_k);
+        self
+    }
+
+    /// Set presence penalty (-2.0 to 2.0)
+    pub fn presence_penalty(mut self, presence_penalty: f32) -> Self {
+        self.settings.presence_penalty = Some(presence_penalty);
+        self
+    }
+
+    /// Set frequency penalty (-2.0 to 2.0)
+    pub fn frequency_penalty(mut self, frequency_penalty: f32) -> Self {
+        self.settings.frequency_penalty = Some(frequency_penalty);
+        self
+    }
+
+    /// Set sequences that stop generation
+    pub fn stop_sequences(mut self, stop_sequences: Vec<String>) -> Self {
+        self.settings.stop_sequences = Some(stop_sequences);
+        self
+    }
+
+    /// Set random seed for deterministic sampling
+    pub fn seed(mut self, seed: i64) -> Self {
+        self.settings.seed = Some(seed);
+        self
+    }
+
+    /// Set tool choice strategy (default: Auto when tools are given)
+    pub fn tool_choice(mut self, tool_choice: ToolChoice) -> Self {
+        self.settings.tool_choice = Some(tool_choice);
+        self
+    }
+
+    /// Set the response format
+    pub fn response_format(mut self, response_format: ResponseFormat) -> Self {
+        self.settings.response_format = Some(response_format);
+        self
+    }
+
+    /// Set additional HTTP headers sent with each request
+    pub fn headers(mut self, headers: HashMap<String, String>) -> Self {
+        self.settings.headers = Some(headers);
+        self
+    }
+
+    /// Add an HTTP header sent with each request
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.settings = self.settings.with_header(name, value);
+        self
+    }
+
+    /// Set provider-specific options, keyed by provider name
+    pub fn provider_options(mut self, provider_options: SharedProviderOptions) -> Self {
+        self.settings.provider_options = Some(provider_options);
+        self
+    }
+
+    /// Set one provider-specific option, e.g. `("openai", "reasoningEffort", "low")`
+    pub fn provider_option(
+        mut self,
+        provider: impl Into<String>,
+        key: impl Into<String>,
+        value: JsonValue,
+    ) -> Self {
+        self.settings = self.settings.with_provider_option(provider, key, value);
+        self
+    }
+
+    /// Replace all model call settings at once
+    pub fn call_settings(mut self, settings: CallSettings) -> Self {
+        self.settings = settings;
         self
     }
 
@@ -96,13 +237,137 @@ impl GenerateTextBuilder {
         self
     }
 
+    /// Set callback invoked after each step, once its tool calls have run
+    pub fn on_step_finish(mut self, callback: OnStepFinishCallback) -> Self {
+        self.on_step_finish = Some(callback);
+        self
+    }
+
+    /// Set callback invoked with the aggregated result once generation succeeds
+    pub fn on_finish(mut self, callback: OnFinishCallback) -> Self {
+        self.on_finish = Some(callback);
+        self
+    }
+
+    /// Set callback invoked with the error when `execute` fails
+    pub fn on_error(mut self, callback: OnErrorCallback<GenerateTextError>) -> Self {
+        self.on_error = Some(callback);
+        self
+    }
+
+    /// Set a signal that cancels the generation
+    ///
+    /// Aborting drops the in-flight model request and any running tools, and
+    /// `execute` returns [`GenerateTextError::Aborted`].
+    pub fn abort_signal(mut self, abort_signal: AbortSignal) -> Self {
+        self.abort_signal = Some(abort_signal);
+        self
+    }
+
+    /// Set time limits for the run, each model call and each tool execution
+    ///
+    /// A stage that runs over its limit fails with
+    /// [`GenerateTextError::Timeout`].
+    pub fn timeouts(mut self, timeouts: Timeouts) -> Self {
+        self.timeouts = timeouts;
+        self
+    }
+
+    /// Set the handler asked to approve calls of tools that need approval
+    ///
+    /// Without a handler, the generation stops at such calls and returns them
+    /// as [`pending_approvals`](GenerateTextResult::pending_approvals).
+    pub fn tool_approval(mut self, handler: ToolApprovalFn) -> Self {
+        self.tool_approval = Some(handler);
+        self
+    }
+
+    /// Set the decisions on the calls a previous run left pending
+    ///
+    /// To resume, pass the earlier prompt followed by its response messages
+    /// as [`messages`](Self::messages); the pending calls then run or are
+    /// denied before the next model call.
+    pub fn approvals(mut self, approvals: Vec<ToolApprovalResponse>) -> Self {
+        self.approvals = approvals;
+        self
+    }
+
+    /// Attach a value every tool can read through [`ToolContext::get`]
+    ///
+    /// [`ToolContext::get`]: crate::ToolContext::get
+    pub fn tool_context<T: std::any::Any + Send + Sync>(mut self, value: T) -> Self {
+        self.tool_context = Some(Arc::new(value));
+        self
+    }
+
+    /// Validate tool call arguments against each tool's input schema
+    ///
+    /// Arguments that do not match get an error result, or go through the
+    /// [`repair_tool_call`](Self::repair_tool_call) hook first.
+    pub fn validate_tool_inputs(mut self, validate: bool) -> Self {
+        self.validate_tool_inputs = validate;
+        self
+    }
+
+    /// Set the hook that fixes calls of unknown tools or with invalid
+    /// arguments before they fall back to an error result
+    pub fn repair_tool_call(mut self, repair: ToolCallRepairFn) -> Self {
+        self.repair_tool_call = Some(repair);
+        self
+    }
+
+    /// Fail with [`ToolError::NoSuchTool`] or
+    /// [`ToolError::InvalidToolArguments`] when a tool call stays invalid
+    /// after repair, instead of sending the model an error result
+    ///
+    /// [`ToolError::NoSuchTool`]: crate::ToolError::NoSuchTool
+    /// [`ToolError::InvalidToolArguments`]: crate::ToolError::InvalidToolArguments
+    pub fn strict_tool_calls(mut self, strict: bool) -> Self {
+        self.strict_tool_calls = strict;
+        self
+    }
+
+    /// Set how the tool calls of a step are scheduled and isolated
+    ///
+    /// See [`ToolExecutionPolicy`] for the defaults.
+    pub fn tool_execution_policy(mut self, policy: ToolExecutionPolicy) -> Self {
+        self.tool_execution_policy = policy;
+        self
+    }
+
     /// Execute the text generation
     pub async fn execute(self) -> Result<GenerateTextResult, GenerateTextError> {
+        let timeouts = self.timeouts;
+        let on_error = self.on_error.clone();
+        let result = match timeouts.limit(TimeoutStage::Total, self.run()).await {
+            Ok(result) => result,
+            Err(timeout) => Err(timeout.into()),
+        };
+        if let (Err(error), Some(callback)) = (&result, on_error) {
+            callback(error).await;
+        }
+        result
+    }
+
+    async fn run(self) -> Result<GenerateTextResult, GenerateTextError> {
         let model = self.model.ok_or(GenerateTextError::MissingModel)?;
         let mut messages = self.prompt.ok_or(GenerateTextError::MissingPrompt)?;
 
-        let tool_executor = ToolExecutor::new(self.tools);
+        let abort_signal = self.abort_signal;
+        let tool_executor = ToolExecutor::new(self.tools)
+            .with_abort_signal(abort_signal.clone())
+            .with_user_context(self.tool_context)
+            .with_approval_handler(self.tool_approval)
+            .with_approvals(self.approvals)
+            .with_input_validation(self.validate_tool_inputs)
+            .with_tool_call_repair(self.repair_tool_call)
+            .with_strict_tool_calls(self.strict_tool_calls)
+            .with_execution_policy(self.tool_execution_policy)
+            .with_timeouts(self.timeouts);
+        let timeouts = &self.timeouts;
+        let started = Instant::now();
         let mut steps = Vec::new();
+        let mut response_messages = Vec::new();
         let mut total_usage = Usage {
             input_tokens: Some(0),
             output_tokens: Some(0),
@@ -111,32 +376,55 @@ impl GenerateTextBuilder {
             cached_input_tokens: None,
         };
 
+        // Run the tool calls a previous run left waiting for approval
+        let unresolved = unresolved_tool_calls(&messages);
+        let mut pending_approvals = tool_executor.pending_approvals(&unresolved);
+        if !unresolved.is_empty() && pending_approvals.is_empty() {
+            let execution = tool_executor.execute_step_tools(unresolved, 0, &messages);
+            let tool_results = abortable(abort_signal.as_ref(), execution).await??;
+            let tool_message = Message::Tool {
+                content: tool_results,
+            };
+            response_messages.push(tool_message.clone());
+            messages.push(tool_message);
+        }
+
         for step_index in 0..self.max_steps {
+            if !pending_approvals.is_empty() {
+                break;
+            }
+
             // Prepare call options
             let mut options = CallOptions {
-                prompt: messages.clone(),
-                temperature: self.temperature,
-                max_output_tokens: self.max_tokens,
-                ..Default::default()
+                abort_signal: abort_signal.clone(),
+                ..self.settings.call_options(messages.clone())
             };
 
             // Add tools if available
             if !tool_executor.tools().is_empty() {
                 let tool_defs = tool_executor.tool_definitions();
                 options.tools = Some(tool_defs.into_iter().map(ProviderTool::Function).collect());
-                options.tool_choice = Some(ToolChoice::Auto);
+                options.tool_choice.get_or_insert(ToolChoice::Auto);
             }
 
             // Call model with retry
-            let response = self
-                .retry_policy
-                .retry(|| {
+            let response = abortable(
+                abort_signal.as_ref(),
+                self.retry_policy.retry(|| {
                     let model = model.clone();
                     let options = options.clone();
-                    async move { model.do_generate(options).await }
-                })
-                .await
-                .map_err(GenerateTextError::ModelError)?;
+                    async move {
+                        timeouts
+                            .limit(TimeoutStage::ModelCall, model.do_generate(options))
+                            .await?
+                    }
+                }),
+            )
+            .await?
+            .map_err(|e| match e.downcast::<TimeoutError>() {
+                Ok(timeout) => GenerateTextError::Timeout(*timeout),
+                Err(e) => GenerateTextError::ModelError(e),
+            })?;
 
             // Track usage
             if let (Some(total_input), Some(input)) =
@@ -155,78 +443,101 @@ impl GenerateTextBuilder {
                 total_usage.total_tokens = Some(total + step_total);
             }
 
-            // Extract tool calls
-            let tool_calls = extract_tool_calls(&response.content);
-
             // Store step result
-            let step_result = StepResult {
-                step_index,
-                response_content: response.content.clone(),
-                tool_calls: tool_calls.clone(),
-                finish_reason: response.finish_reason,
-                usage: response.usage.clone(),
-            };
-            steps.push(step_result);
+            let mut step = StepResult::new(step_index, response);
+            abortable(
+                abort_signal.as_ref(),
+                repair_step_tool_calls(&tool_executor, &mut step, &messages),
+            )
+            .await?;
+            let tool_calls = step.tool_calls.clone();
+            let finish_reason = step.finish_reason;
+            let assistant = assistant_message(step.content.clone());
+            steps.push(step);
+            response_messages.push(assistant.clone());
 
             // Check if we should continue
-            if tool_calls.is_empty() || response.finish_reason != FinishReason::ToolCalls {
+            if tool_calls.is_empty() || finish_reason != FinishReason::ToolCalls {
+                if let Some(callback) = &self.on_step_finish {
+                    callback(steps[steps.len() - 1].clone()).await;
+                }
                 break;
             }
 
-            // Execute tools
-            let tool_results = if let Some(ref callback) = self.on_preliminary_tool_result {
-                // Execute with streaming support
-                let mut results = Vec::new();
-                for tool_call in tool_calls {
-                    let callback = callback.clone();
-                    let result = tool_executor
-                        .execute_tool_with_stream(tool_call, move |preliminary| {
-                            let cb = callback.clone();
-                            let preliminary = preliminary.clone();
-                            // Spawn async callback
-                            tokio::spawn(async move {
-                                cb(preliminary).await;
-                            });
-                        })
-                        .await;
-                    results.push(result);
+            // Stop for calls that need a human decision
+            pending_approvals = tool_executor.pending_approvals(&tool_calls);
+            if !pending_approvals.is_empty() {
+                if let Some(callback) = &self.on_step_finish {
+                    callback(steps[steps.len() - 1].clone()).await;
                 }
-                results
-            } else {
-                // Standard non-streaming execution
-                tool_executor.execute_tools(tool_calls).await
-            };
+                break;
+            }
 
-            // Append assistant message with tool calls
-            messages.push(Message::Assistant {
-                content: response
-                    .content
-                    .into_iter()
-                    .map(|c| match c {
-                        Content::Text(tp) => AssistantContentPart::Text(tp),
-                        Content::ToolCall(tc) => AssistantContentPart::ToolCall(tc),
-                        Content::Reasoning(rp) => AssistantContentPart::Reasoning(rp),
-                        Content::File(fp) => AssistantContentPart::File(fp),
-                        Content::ToolResult(tr) => AssistantContentPart::ToolResult(tr),
-                        Content::Source(_) => {
-                            // Source is not an AssistantContentPart, skip it
-                            // This shouldn't happen in normal generation
-                            AssistantContentPart::Text(TextPart {
-                                text: String::new(),
-                                provider_metadata: None,
-                            })
-                        }
-                    })
-                    .collect(),
-            });
+            // Execute tools; they see the history with the assistant message
+            messages.push(assistant);
+            let history = &messages;
+            let execution = async {
+                if let Some(ref callback) = self.on_preliminary_tool_result {
+                    // Execute with streaming support
+                    let mut results = Vec::new();
+                    for tool_call in tool_calls {
+                        let callback = callback.clone();
+                        let execution = tool_executor.execute_tool_with_stream(
+                            tool_call,
+                            step_index,
+                            history,
+                            move |preliminary| {
+                                let cb = callback.clone();
+                                let preliminary = preliminary.clone();
+                                // Spawn async callback
+                                tokio::spawn(async move {
+                                    cb(preliminary).await;
+                                });
+                            },
+                        );
+                        results.push(execution.await?);
+                    }
+                    Ok::<_, GenerateTextError>(results)
+                } else {
+                    // Standard non-streaming execution
+                    Ok(tool_executor
+                        .execute_step_tools(tool_calls, step_index, history)
+                        .await?)
+                }
+            };
+            let tool_results = abortable(abort_signal.as_ref(), execution).await??;
+            let step = steps.last_mut().expect("step was just pushed");
+            step.tool_results.extend(tool_results.iter().cloned());
+            if let Some(callback) = &self.on_step_finish {
+                callback(steps[steps.len() - 1].clone()).await;
+            }
 
-            // Append tool results
-            messages.push(Message::Tool {
+            // Append the tool results
+            let tool_message = Message::Tool {
                 content: tool_results,
-            });
+            };
+            response_messages.push(tool_message.clone());
+            messages.push(tool_message);
+
+            let context = StopConditionContext {
+                steps: &steps,
+                elapsed: started.elapsed(),
+            };
+            if is_stop_condition_met(&self.stop_conditions, &context).await {
+                break;
+            }
         }
 
-        Ok(GenerateTextResult { steps, total_usage })
+        let result = GenerateTextResult {
+            steps,
+            total_usage,
+            response_messages,
+            pending_approvals,
+        };
+        if let Some(callback) = &self.on_finish {
+            callback(result.finish_event()).await;
+        }
+        Ok(result)
     }
 }
 
@@ -240,24 +551,72 @@ impl Default for GenerateTextBuilder {
 pub struct GenerateTextResult {
     steps: Vec<StepResult>,
     total_usage: Usage,
+    response_messages: Vec<Message>,
+    pending_approvals: Vec<ToolApprovalRequest>,
 }
 
 impl GenerateTextResult {
     /// Create a new GenerateTextResult
-    pub(crate) fn new(steps: Vec<StepResult>, total_usage: Usage) -> Self {
-        Self { steps, total_usage }
+    pub(crate) fn new(
+        steps: Vec<StepResult>,
+        total_usage: Usage,
+        response_messages: Vec<Message>,
+        pending_approvals: Vec<ToolApprovalRequest>,
+    ) -> Self {
+        Self {
+            steps,
+            total_usage,
+            response_messages,
+            pending_approvals,
+        }
     }
 }
 
 impl GenerateTextResult {
     /// Get the final text response
     pub fn text(&self) -> String {
+        self.steps.last().map(StepResult::text).unwrap_or_default()
+    }
+
+    /// Get the reasoning text of the last step, if any
+    pub fn reasoning_text(&self) -> Option<String> {
+        self.steps.last().and_then(StepResult::reasoning_text)
+    }
+
+    /// Get the reasoning parts of the last step
+    pub fn reasoning(&self) -> Vec<&ReasoningPart> {
         self.steps
             .last()
-            .map(|step| extract_text(&step.response_content))
+            .map(StepResult::reasoning)
             .unwrap_or_default()
     }
 
+    /// Get the tool calls made in the last step
+    pub fn tool_calls(&self) -> &[ToolCallPart] {
+        self.steps
+            .last()
+            .map(|step| step.tool_calls.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Get the tool results of the last step
+    pub fn tool_results(&self) -> &[ToolResultPart] {
+        self.steps
+            .last()
+            .map(|step| step.tool_results.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Get the sources cited across all steps
+    pub fn sources(&self) -> Vec<&SourcePart> {
+        self.steps.iter().flat_map(StepResult::sources).collect()
+    }
+
+    /// Get the files generated in the last step
+    pub fn files(&self) -> Vec<&FilePart> {
+        self.steps.last().map(StepResult::files).unwrap_or_default()
+    }
+
     /// Get all steps
     pub fn steps(&self) -> &[StepResult] {
         &self.steps
@@ -275,21 +634,73 @@ impl GenerateTextResult {
             .map(|s| &s.finish_reason)
             .unwrap_or(&FinishReason::Stop)
     }
+
+    /// Get the warnings reported by the provider across all steps
+    pub fn warnings(&self) -> Vec<&CallWarning> {
+        self.steps
+            .iter()
+            .flat_map(|step| step.warnings.iter())
+            .collect()
+    }
+
+    /// Get the request information of the last step
+    pub fn request(&self) -> Option<&RequestInfo> {
+        self.steps.last().and_then(|step| step.request.as_ref())
+    }
+
+    /// Get the response information of the last step
+    pub fn response(&self) -> Option<&ResponseInfo> {
+        self.steps.last().and_then(|step| step.response.as_ref())
+    }
+
+    /// Get the provider metadata of the last step
+    pub fn provider_metadata(&self) -> Option<&SharedProviderMetadata> {
+        self.steps
+            .last()
+            .and_then(|step| step.provider_metadata.as_ref())
+    }
+
+    /// Get the assistant and tool messages generated during the run
+    ///
+    /// Appending them to the prompt messages continues the conversation.
+    pub fn response_messages(&self) -> &[Message] {
+        &self.response_messages
+    }
+
+    /// Get the tool calls the generation stopped at to wait for approval
+    ///
+    /// Empty unless a tool needed approval and no approval handler was set.
+    pub fn pending_approvals(&self) -> &[ToolApprovalRequest] {
+        &self.pending_approvals
+    }
+
+    fn finish_event(&self) -> FinishEvent {
+        FinishEvent {
+            text: self.text(),
+            finish_reason: *self.finish_reason(),
+            steps: self.steps.clone(),
+            total_usage: self.total_usage.clone(),
+            response_messages: self.response_messages.clone(),
+            pending_approvals: self.pending_approvals.clone(),
+        }
+    }
 }
 
-/// Result of a single generation step
+/// Aggregated result of a finished generation, passed to `on_finish`
 #[derive(Debug, Clone)]
-pub struct StepResult {
-    /// Index of this step in the generation sequence
-    pub step_index: u32,
-    /// Content returned by the model in this step
-    pub response_content: Vec<Content>,
-    /// Tool calls made by the model in this step
-    pub tool_calls: Vec<ToolCallPart>,
-    /// Reason why generation finished
+pub struct FinishEvent {
+    /// Text generated in the last step
+    pub text: String,
+    /// Finish reason of the last step
     pub finish_reason: FinishReason,
-    /// Token usage for this step
-    pub usage: Usage,
+    /// All steps of the generation
+    pub steps: Vec<StepResult>,
+    /// Total token usage across all steps
+    pub total_usage: Usage,
+    /// Assistant and tool messages generated during the run
+    pub response_messages: Vec<Message>,
+    /// Tool calls the generation stopped at to wait for approval
+    pub pending_approvals: Vec<ToolApprovalRequest>,
 }
 
 /// Entry point function
@@ -298,75 +709,842 @@ pub fn generate_text() -> GenerateTextBuilder {
 }
 
 // Helper functions
-fn extract_tool_calls(content: &[Content]) -> Vec<ToolCallPart> {
-    content
-        .iter()
-        .filter_map(|c| match c {
-            Content::ToolCall(tc) => Some(tc.clone()),
-            _ => None,
-        })
-        .collect()
+
+/// Repairs the tool calls of a step that cannot run as made
+///
+/// Runs before approval is decided, so the call that is approved is the one
+/// that runs. `messages` is the conversation before the step.
+pub(crate) async fn repair_step_tool_calls(
+    tool_executor: &ToolExecutor,
+    step: &mut StepResult,
+    messages: &[Message],
+) {
+    if step.tool_calls.is_empty() {
+        return;
+    }
+    let mut history = messages.to_vec();
+    history.push(assistant_message(step.content.clone()));
+    let repaired = tool_executor
+        .repair_tool_calls(step.tool_calls.clone(), &history)
+        .await;
+    step.set_tool_calls(&repaired);
 }
 
-fn extract_text(content: &[Content]) -> String {
-    content
-        .iter()
-        .filter_map(|c| match c {
-            Content::Text(text_part) => Some(text_part.text.clone()),
-            _ => None,
-        })
-        .collect::<Vec<_>>()
-        .join("")
+/// The assistant message for a step's content; sources are not sent back
+pub(crate) fn assistant_message(content: Vec<Content>) -> Message {
+    Message::Assistant {
+        content: content
+            .into_iter()
+            .filter_map(|c| match c {
+                Content::Text(tp) => Some(AssistantContentPart::Text(tp)),
+                Content::ToolCall(tc) => Some(AssistantContentPart::ToolCall(tc)),
+                Content::Reasoning(rp) => Some(AssistantContentPart::Reasoning(rp)),
+                Content::File(fp) => Some(AssistantContentPart::File(fp)),
+                Content::ToolResult(tr) => Some(AssistantContentPart::ToolResult(tr)),
+                Content::Source(_) => None,
+            })
+            .collect(),
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::error::ToolError;
+    use crate::test_utils::{
+        text_response, tool_call_response, ContextTool, DeleteTool, EchoTool, MockLanguageModel,
+        RequestId, ScreenshotTool,
+    };
+    use crate::tool::{ErrorMode, ToolCallRepairRequest, ToolContext, ToolOutput};
+    use ai_sdk_provider::language_model::{GenerateResponse, TextPart, ToolResultOutput};
+    use ai_sdk_provider::{AbortController, ApiCallError};
+    use async_trait::async_trait;
+    use serde_json::{json, Value};
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::time::Duration;
 
-    #[test]
-    fn test_extract_tool_calls() {
-        let content = vec![
-            Content::Text(TextPart {
-                text: "Hello".to_string(),
-                provider_metadata: None,
-            }),
-            Content::ToolCall(ToolCallPart {
-                tool_call_id: "call_123".to_string(),
-                tool_name: "weather".to_string(),
-                input: r#"{"location":"Tokyo"}"#.to_string(),
+    /// Tool that never finishes, recording whether it was given a signal
+    #[derive(Default)]
+    struct StuckTool {
+        saw_abort_signal: AtomicBool,
+    }
+
+    #[async_trait]
+    impl Tool for StuckTool {
+        fn name(&self) -> &str {
+            "stuck"
+        }
+
+        fn description(&self) -> &str {
+            "Never finishes"
+        }
+
+        fn input_schema(&self) -> Value {
+            json!({ "type": "object" })
+        }
+
+        async fn execute(
+            &self,
+            _input: Value,
+            context: &ToolContext,
+        ) -> Result<ToolOutput, ToolError> {
+            self.saw_abort_signal
+                .store(context.abort_signal.is_some(), Ordering::SeqCst);
+            std::future::pending().await
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retries_retryable_model_errors() {
+        let model = MockLanguageModel::new()
+            .with_generate(Err(
+                ApiCallError::new("overloaded", "url").with_status_code(529)
+            ))
+            .with_generate(Ok(text_response("Hello")));
+
+        let result = generate_text()
+            .model(model.clone())
+            .prompt("Hi")
+            .retry_policy(RetryPolicy::default().with_initial_delay(Duration::from_millis(1)))
+            .execute()
+            .await
+            .unwrap();
+
+        assert_eq!(result.text(), "Hello");
+        assert_eq!(model.calls().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_does_not_retry_invalid_requests() {
+        let model = MockLanguageModel::new()
+            .with_generate(Err(
+                ApiCallError::new("invalid", "url").with_status_code(400)
+            ))
+            .with_generate(Ok(text_response("unreachable")));
+
+        let result = generate_text()
+            .model(model.clone())
+            .prompt("Hi")
+            .execute()
+            .await;
+
+        assert!(matches!(result, Err(GenerateTextError::ModelError(_))));
+        assert_eq!(model.calls().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_call_settings_apply_to_every_step() {
+        let model = MockLanguageModel::new()
+            .with_generate(Ok(tool_call_response("echo", json!({ "text": "hi" }))))
+            .with_generate(Ok(text_response("Done")));
+
+        generate_text()
+            .model(model.clone())
+            .prompt("Hi")
+            .tools(vec![Arc::new(EchoTool)])
+            .max_steps(2)
+            .temperature(0.3)
+            .top_p(0.8)
+            .top_k(20)
+            .presence_penalty(0.1)
+            .frequency_penalty(0.2)
+            .stop_sequences(vec!["STOP".into()])
+            .seed(42)
+            .tool_choice(ToolChoice::Required)
+            .response_format(ResponseFormat::Text)
+            .header("x-request-source", "test")
+            .provider_option("openai", "reasoningEffort", JsonValue::String("low".into()))
+            .execute()
+            .await
+            .unwrap();
+
+        let calls = model.calls();
+        assert_eq!(calls.len(), 2);
+        for call in &calls {
+            assert_eq!(call.temperature, Some(0.3));
+            assert_eq!(call.top_p, Some(0.8));
+            assert_eq!(call.top_k, Some(20));
+            assert_eq!(call.presence_penalty, Some(0.1));
+            assert_eq!(call.frequency_penalty, Some(0.2));
+            assert_eq!(call.stop_sequences, Some(vec!["STOP".to_string()]));
+            assert_eq!(call.seed, Some(42));
+            assert_eq!(call.tool_choice, Some(ToolChoice::Required));
+            assert_eq!(call.response_format, Some(ResponseFormat::Text));
+            assert_eq!(call.headers.as_ref().unwrap()["x-request-source"], "test");
+            assert_eq!(
+                call.provider_options.as_ref().unwrap()["openai"]["reasoningEffort"],
+                JsonValue::String("low".into())
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tool_choice_defaults_to_auto_with_tools() {
+        let model = MockLanguageModel::new().with_generate(Ok(text_response("Hello")));
+
+        generate_text()
+            .model(model.clone())
+            .prompt("Hi")
+            .tools(vec![Arc::new(EchoTool)])
+            .execute()
+            .await
+            .unwrap();
+
+        assert_eq!(model.calls()[0].tool_choice, Some(ToolChoice::Auto));
+    }
+
+    #[tokio::test]
+    async fn test_result_accessors() {
+        let model = MockLanguageModel::new()
+            .with_generate(Ok(GenerateResponse {
+                warnings: vec![CallWarning {
+                    message: "topK is not supported".into(),
+                }],
+                ..tool_call_response("echo", json!({ "text": "hi" }))
+            }))
+            .with_generate(Ok(GenerateResponse {
+                content: vec![
+                    Content::Reasoning(ReasoningPart {
+                        reasoning: "Echoed".into(),
+                        provider_metadata: None,
+                    }),
+                    Content::Text(TextPart {
+                        text: "Done".into(),
+                        provider_metadata: None,
+                    }),
+                ],
+                response: Some(ResponseInfo {
+                    headers: None,
+                    body: None,
+                    id: Some("resp_2".into()),
+                    timestamp: None,
+                    model_id: Some("mock-model".into()),
+                }),
+                ..text_response("")
+            }));
+
+        let result = generate_text()
+            .model(model)
+            .prompt("Hi")
+            .tools(vec![Arc::new(EchoTool)])
+            .max_steps(2)
+            .execute()
+            .await
+            .unwrap();
+
+        let steps = result.steps();
+        assert_eq!(steps[0].tool_calls.len(), 1);
+        assert_eq!(steps[0].tool_results.len(), 1);
+        assert_eq!(steps[0].tool_results[0].tool_call_id, "call_1");
+        assert_eq!(result.text(), "Done");
+        assert_eq!(result.reasoning_text().as_deref(), Some("Echoed"));
+        assert!(result.tool_calls().is_empty());
+        assert!(result.tool_results().is_empty());
+        assert!(result.sources().is_empty());
+        assert_eq!(result.warnings().len(), 1);
+        assert_eq!(result.response().unwrap().id.as_deref(), Some("resp_2"));
+        assert!(result.request().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_stop_when_ends_loop_before_max_steps() {
+        let model = MockLanguageModel::new()
+            .with_generate(Ok(tool_call_response("echo", json!({}))))
+            .with_generate(Ok(tool_call_response("echo", json!({}))))
+            .with_generate(Ok(text_response("Unused")));
+
+        let result = generate_text()
+            .model(model.clone())
+            .prompt("Hi")
+            .tools(vec![Arc::new(EchoTool)])
+            .max_steps(5)
+            .stop_when(crate::stop_condition::any(vec![
+                crate::stop_condition::step_count_is(2),
+                crate::stop_condition::has_tool_call("other"),
+            ]))
+            .execute()
+            .await
+            .unwrap();
+
+        assert_eq!(result.steps().len(), 2);
+        assert_eq!(model.calls().len(), 2);
+        assert_eq!(result.response_messages().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_response_messages_continue_conversation() {
+        let model = MockLanguageModel::new()
+            .with_generate(Ok(tool_call_response("echo", json!({ "text": "hi" }))))
+            .with_generate(Ok(text_response("Done")));
+
+        let result = generate_text()
+            .model(model.clone())
+            .prompt("Hi")
+            .tools(vec![Arc::new(EchoTool)])
+            .max_steps(2)
+            .execute()
+            .await
+            .unwrap();
+
+        let messages = result.response_messages();
+        assert_eq!(messages.len(), 3);
+        assert!(matches!(messages[0], Message::Assistant { .. }));
+        assert!(matches!(messages[1], Message::Tool { .. }));
+        assert!(matches!(
+            &messages[2],
+            Message::Assistant { content } if matches!(
+                &content[..],
+                [AssistantContentPart::Text(part)] if part.text == "Done"
+            )
+        ));
+
+        // The second call saw the prompt followed by the first two messages
+        let calls = model.calls();
+        assert_eq!(calls[1].prompt.len(), 3);
+        assert_eq!(calls[1].prompt[1..], messages[..2]);
+    }
+
+    #[tokio::test]
+    async fn test_lifecycle_callbacks() {
+        let model = MockLanguageModel::new()
+            .with_generate(Ok(tool_call_response("echo", json!({}))))
+            .with_generate(Ok(text_response("Done")));
+        let events = Arc::new(std::sync::Mutex::new(Vec::new()));
+
+        let on_step_finish: OnStepFinishCallback = {
+            let events = events.clone();
+            Arc::new(move |step| {
+                events
+                    .lock()
+                    .unwrap()
+                    .push(format!("step {} {:?}", step.step_index, step.finish_reason));
+                Box::pin(async {})
+            })
+        };
+        let on_finish: OnFinishCallback = {
+            let events = events.clone();
+            Arc::new(move |event| {
+                events.lock().unwrap().push(format!(
+                    "finish {} {} {:?}",
+                    event.text,
+                    event.steps.len(),
+                    event.total_usage.output_tokens
+                ));
+                Box::pin(async {})
+            })
+        };
+
+        generate_text()
+            .model(model)
+            .prompt("Hi")
+            .tools(vec![Arc::new(EchoTool)])
+            .max_steps(2)
+            .on_step_finish(on_step_finish)
+            .on_finish(on_finish)
+            .execute()
+            .await
+            .unwrap();
+
+        assert_eq!(
+            *events.lock().unwrap(),
+            ["step 0 ToolCalls", "step 1 Stop", "finish Done 2 Some(10)"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_on_error_callback() {
+        let model = MockLanguageModel::new()
+            .with_generate(Err(
+                ApiCallError::new("invalid", "url").with_status_code(400)
+            ));
+        let errors = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let on_error: OnErrorCallback<GenerateTextError> = {
+            let errors = errors.clone();
+            Arc::new(move |error| {
+                errors.lock().unwrap().push(error.to_string());
+                Box::pin(async {})
+            })
+        };
+
+        let result = generate_text()
+            .model(model)
+            .prompt("Hi")
+            .on_error(on_error)
+            .execute()
+            .await;
+
+        assert!(result.is_err());
+        assert_eq!(*errors.lock().unwrap(), ["Model error: invalid"]);
+    }
+
+    #[tokio::test]
+    async fn test_abort_cancels_in_flight_model_call() {
+        let model = MockLanguageModel::new().hanging();
+        let controller = AbortController::new();
+
+        let generation = tokio::spawn(
+            generate_text()
+                .model(model.clone())
+                .prompt("Hi")
+                .abort_signal(controller.signal())
+                .execute(),
+        );
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        controller.abort();
+
+        let result = generation.await.unwrap();
+        assert!(matches!(result, Err(GenerateTextError::Aborted(_))));
+        // The signal is forwarded so providers can cancel the request too
+        assert_eq!(model.calls()[0].abort_signal, Some(controller.signal()));
+    }
+
+    #[tokio::test]
+    async fn test_abort_cancels_running_tools() {
+        let model = MockLanguageModel::new()
+            .with_generate(Ok(tool_call_response("stuck", json!({}))))
+            .with_generate(Ok(text_response("unreachable")));
+        let tool = Arc::new(StuckTool::default());
+        let controller = AbortController::new();
+
+        let generation = tokio::spawn(
+            generate_text()
+                .model(model.clone())
+                .prompt("Hi")
+                .tools(vec![tool.clone()])
+                .max_steps(2)
+                .abort_signal(controller.signal())
+                .execute(),
+        );
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        controller.abort();
+
+        let result = generation.await.unwrap();
+        assert!(matches!(result, Err(GenerateTextError::Aborted(_))));
+        assert!(tool.saw_abort_signal.load(Ordering::SeqCst));
+        assert_eq!(model.calls().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_model_call_timeout() {
+        let model = MockLanguageModel::new().hanging();
+
+        let result = generate_text()
+            .model(model.clone())
+            .prompt("Hi")
+            .retry_policy(RetryPolicy::default().with_max_retries(0))
+            .timeouts(Timeouts::new().with_model_call(Duration::from_millis(20)))
+            .execute()
+            .await;
+
+        match result {
+            Err(GenerateTextError::Timeout(error)) => {
+                assert_eq!(error.stage, TimeoutStage::ModelCall);
+                assert_eq!(error.timeout, Duration::from_millis(20));
+            }
+            other => panic!("Expected model call timeout, got {:?}", other.err()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tool_execution_and_total_timeouts() {
+        let run = |timeouts: Timeouts| {
+            let model =
+                MockLanguageModel::new().with_generate(Ok(tool_call_response("stuck", json!({}))));
+            generate_text()
+                .model(model)
+                .prompt("Hi")
+                .tools(vec![Arc::new(StuckTool::default())])
+                .max_steps(2)
+                .timeouts(timeouts)
+                .execute()
+        };
+
+        let result = run(Timeouts::new().with_tool_execution(Duration::from_millis(20))).await;
+        assert!(matches!(
+            result,
+            Err(GenerateTextError::Timeout(TimeoutError {
+                stage: TimeoutStage::ToolExecution,
+                ..
+            }))
+        ));
+
+        let result = run(Timeouts::new().with_total(Duration::from_millis(20))).await;
+        assert!(matches!(
+            result,
+            Err(GenerateTextError::Timeout(TimeoutError {
+                stage: TimeoutStage::Total,
+                ..
+            }))
+        ));
+    }
+
+    /// Tool named `slow` that takes 20ms
+    struct SlowTool;
+
+    #[async_trait]
+    impl Tool for SlowTool {
+        fn name(&self) -> &str {
+            "slow"
+        }
+
+        fn description(&self) -> &str {
+            "Takes a while"
+        }
+
+        fn input_schema(&self) -> Value {
+            json!({ "type": "object" })
+        }
+
+        async fn execute(
+            &self,
+            _input: Value,
+            _context: &ToolContext,
+        ) -> Result<ToolOutput, ToolError> {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            Ok(ToolOutput::Value(JsonValue::String("done".into())))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tool_execution_timeout_applies_to_each_call() {
+        let calls = (0..3)
+            .map(|i| {
+                Content::ToolCall(ToolCallPart {
+                    tool_call_id: format!("call_{}", i),
+                    tool_name: "slow".into(),
+                    input: "{}".into(),
+                    provider_executed: None,
+                    dynamic: None,
+                    provider_metadata: None,
+                })
+            })
+            .collect();
+        let model = MockLanguageModel::new()
+            .with_generate(Ok(GenerateResponse {
+                content: calls,
+                finish_reason: FinishReason::ToolCalls,
+                ..text_response("")
+            }))
+            .with_generate(Ok(text_response("Done")));
+
+        // Each call is under the limit, the three of them run one after
+        // another are over it
+        let result = generate_text()
+            .model(model)
+            .prompt("Hi")
+            .tools(vec![Arc::new(SlowTool)])
+            .tool_execution_policy(ToolExecutionPolicy::sequential())
+            .timeouts(Timeouts::new().with_tool_execution(Duration::from_millis(50)))
+            .max_steps(2)
+            .execute()
+            .await
+            .unwrap();
+
+        assert_eq!(result.text(), "Done");
+        assert_eq!(result.steps[0].tool_results.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_pauses_for_approval_and_resumes_from_serialized_state() {
+        let model = MockLanguageModel::new()
+            .with_generate(Ok(tool_call_response("delete", json!({ "id": 7 }))))
+            .with_generate(Ok(text_response("Deleted")));
+        let prompt = vec![Message::User {
+            content: vec![UserContentPart::Text {
+                text: "Delete resource 7".into(),
+            }],
+        }];
+
+        let paused = generate_text()
+            .model(model.clone())
+            .messages(prompt.clone())
+            .tools(vec![Arc::new(DeleteTool)])
+            .execute()
+            .await
+            .unwrap();
+
+        assert_eq!(
+            paused.pending_approvals(),
+            [ToolApprovalRequest {
+                tool_call_id: "call_1".into(),
+                tool_name: "delete".into(),
+                input: json!({ "id": 7 }),
+            }]
+        );
+        assert_eq!(paused.response_messages().len(), 1);
+        assert_eq!(model.calls().len(), 1);
+
+        // Store the conversation and the decision, then resume from them
+        let mut history = prompt;
+        history.extend(paused.response_messages().iter().cloned());
+        let state = serde_json::to_string(&history).unwrap();
+        let decision = serde_json::to_string(&[ToolApprovalResponse::approve("call_1")]).unwrap();
+
+        let resumed = generate_text()
+            .model(model.clone())
+            .messages(serde_json::from_str(&state).unwrap())
+            .tools(vec![Arc::new(DeleteTool)])
+            .approvals(serde_json::from_str(&decision).unwrap())
+            .execute()
+            .await
+            .unwrap();
+
+        assert!(resumed.pending_approvals().is_empty());
+        assert_eq!(resumed.text(), "Deleted");
+        match &resumed.response_messages()[0] {
+            Message::Tool { content } => assert_eq!(
+                content[0].output,
+                ToolResultOutput::Text {
+                    value: "deleted".into(),
+                    provider_metadata: None,
+                }
+            ),
+            other => panic!("expected tool message, got {:?}", other),
+        }
+        // The resumed model call sees the tool result
+        let calls = model.calls();
+        assert_eq!(calls.len(), 2);
+        assert!(matches!(calls[1].prompt.last(), Some(Message::Tool { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_repaired_calls_pause_for_approval() {
+        let model = MockLanguageModel::new()
+            .with_generate(Ok(tool_call_response("delet", json!({ "id": 7 }))));
+
+        let result = generate_text()
+            .model(model.clone())
+            .prompt("Delete resource 7")
+            .tools(vec![Arc::new(DeleteTool)])
+            .repair_tool_call(Arc::new(|request: ToolCallRepairRequest| {
+                Box::pin(async move {
+                    Some(ToolCallPart {
+                        tool_name: "delete".into(),
+                        ..request.tool_call
+                    })
+                })
+            }))
+            .execute()
+            .await
+            .unwrap();
+
+        assert_eq!(
+            result.pending_approvals(),
+            [ToolApprovalRequest {
+                tool_call_id: "call_1".into(),
+                tool_name: "delete".into(),
+                input: json!({ "id": 7 }),
+            }]
+        );
+        // The repaired call is kept, so resuming runs the call that was approved
+        match &result.response_messages()[0] {
+            Message::Assistant { content } => assert!(matches!(
+                &content[0],
+                AssistantContentPart::ToolCall(call) if call.tool_name == "delete"
+            )),
+            other => panic!("expected assistant message, got {:?}", other),
+        }
+        assert_eq!(result.steps[0].tool_calls[0].tool_name, "delete");
+    }
+
+    #[tokio::test]
+    async fn test_resume_without_decision_stays_paused() {
+        let model = MockLanguageModel::new();
+        let history = vec![Message::Assistant {
+            content: vec![AssistantContentPart::ToolCall(ToolCallPart {
+                tool_call_id: "call_1".into(),
+                tool_name: "delete".into(),
+                input: "{}".into(),
                 provider_executed: None,
                 dynamic: None,
                 provider_metadata: None,
-            }),
-        ];
+            })],
+        }];
+
+        let result = generate_text()
+            .model(model.clone())
+            .messages(history)
+            .tools(vec![Arc::new(DeleteTool)])
+            .execute()
+            .await
+            .unwrap();
 
-        let tool_calls = extract_tool_calls(&content);
-        assert_eq!(tool_calls.len(), 1);
-        assert_eq!(tool_calls[0].tool_name, "weather");
+        assert_eq!(result.pending_approvals().len(), 1);
+        assert!(model.calls().is_empty());
     }
 
-    #[test]
-    fn test_extract_text() {
-        let content = vec![
-            Content::Text(TextPart {
-                text: "Hello".to_string(),
+    #[tokio::test]
+    async fn test_approval_handler_decides_during_run() {
+        let model = MockLanguageModel::new()
+            .with_generate(Ok(tool_call_response("delete", json!({ "id": 7 }))))
+            .with_generate(Ok(text_response("Not deleted")));
+
+        let result = generate_text()
+            .model(model.clone())
+            .prompt("Delete resource 7")
+            .tools(vec![Arc::new(DeleteTool)])
+            .max_steps(2)
+            .tool_approval(Arc::new(|request: ToolApprovalRequest| {
+                Box::pin(async move {
+                    ToolApprovalResponse::deny(request.tool_call_id).with_reason("Protected")
+                })
+            }))
+            .execute()
+            .await
+            .unwrap();
+
+        assert!(result.pending_approvals().is_empty());
+        assert_eq!(result.text(), "Not deleted");
+        assert_eq!(
+            result.steps()[0].tool_results[0].output,
+            ToolResultOutput::ExecutionDenied {
+                reason: Some("Protected".into()),
                 provider_metadata: None,
-            }),
-            Content::Text(TextPart {
-                text: " world".to_string(),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_tools_get_history_step_and_user_context() {
+        let model = MockLanguageModel::new()
+            .with_generate(Ok(tool_call_response("context", json!({}))))
+            .with_generate(Ok(tool_call_response("context", json!({}))))
+            .with_generate(Ok(text_response("Done")));
+
+        let result = generate_text()
+            .model(model)
+            .prompt("Hi")
+            .tools(vec![Arc::new(ContextTool)])
+            .max_steps(3)
+            .tool_context(RequestId("req-42"))
+            .execute()
+            .await
+            .unwrap();
+
+        // Tools see the history up to the assistant message with their call
+        let outputs: Vec<_> = result
+            .steps()
+            .iter()
+            .flat_map(|step| step.tool_results.iter().map(|r| r.output.clone()))
+            .collect();
+        assert_eq!(
+            outputs,
+            ["req-42/0/2", "req-42/1/4"].map(|value| ToolResultOutput::Text {
+                value: value.into(),
                 provider_metadata: None,
-            }),
-        ];
+            })
+        );
+        assert_eq!(result.text(), "Done");
+    }
+
+    #[tokio::test]
+    async fn test_strict_tool_calls_fail_on_unknown_tool() {
+        let model = MockLanguageModel::new()
+            .with_generate(Ok(tool_call_response("missing", json!({}))))
+            .with_generate(Ok(text_response("Done")));
+
+        let result = generate_text()
+            .model(model)
+            .prompt("Hi")
+            .tools(vec![Arc::new(EchoTool)])
+            .max_steps(2)
+            .strict_tool_calls(true)
+            .execute()
+            .await;
+
+        match result {
+            Err(GenerateTextError::ToolError(ToolError::NoSuchTool {
+                tool_name,
+                available_tools,
+            })) => {
+                assert_eq!(tool_name, "missing");
+                assert_eq!(available_tools, ["echo"]);
+            }
+            other => panic!("Expected NoSuchTool, got {:?}", other.err()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_content_tool_output_reaches_next_model_call() {
+        let model = MockLanguageModel::new()
+            .with_generate(Ok(tool_call_response("screenshot", json!({}))))
+            .with_generate(Ok(text_response("A login page")));
+
+        let result = generate_text()
+            .model(model.clone())
+            .prompt("What is on screen?")
+            .tools(vec![Arc::new(ScreenshotTool)])
+            .max_steps(2)
+            .execute()
+            .await
+            .unwrap();
+
+        assert_eq!(
+            result.steps()[0].tool_results[0].output,
+            ScreenshotTool::output()
+        );
+        match model.calls()[1].prompt.last() {
+            Some(Message::Tool { content }) => {
+                assert_eq!(content[0].output, ScreenshotTool::output());
+            }
+            other => panic!("Expected tool message, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_tool_without_error_output_fails_generation() {
+        struct FailingTool;
+
+        #[async_trait]
+        impl Tool for FailingTool {
+            fn name(&self) -> &str {
+                "fail"
+            }
+
+            fn description(&self) -> &str {
+                "Always fails"
+            }
+
+            fn input_schema(&self) -> Value {
+                json!({ "type": "object" })
+            }
+
+            async fn execute(
+                &self,
+                _input: Value,
+                _context: &ToolContext,
+            ) -> Result<ToolOutput, ToolError> {
+                Err(ToolError::coded("unavailable", "Service down"))
+            }
+
+            fn error_mode(&self) -> ErrorMode {
+                ErrorMode::Propagate
+            }
+        }
+
+        let model = MockLanguageModel::new()
+            .with_generate(Ok(tool_call_response("fail", json!({}))))
+            .with_generate(Ok(text_response("Unused")));
+
+        let result = generate_text()
+            .model(model.clone())
+            .prompt("Hi")
+            .tools(vec![Arc::new(FailingTool)])
+            .max_steps(2)
+            .execute()
+            .await;
 
-        let text = extract_text(&content);
-        assert_eq!(text, "Hello world");
+        assert!(matches!(
+            result,
+            Err(GenerateTextError::ToolError(ToolError::Coded { ref code, .. })) if code == "unavailable"
+        ));
+        assert_eq!(model.calls().len(), 1);
     }
 
     #[test]
     fn test_builder_defaults() {
         let builder = GenerateTextBuilder::new();
         assert_eq!(builder.max_steps, 1);
-        assert!(builder.temperature.is_none());
-        assert!(builder.max_tokens.is_none());
+        assert_eq!(builder.settings, CallSettings::default());
     }
 }
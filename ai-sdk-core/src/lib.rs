@@ -74,7 +74,7 @@
 //! let mut stream = result.into_stream();
 //! while let Some(part) = stream.next().await {
 //!     match part? {
-//!         TextStreamPart::TextDelta(delta) => print!("{}", delta),
+//!         TextStreamPart::TextDelta { text, .. } => print!("{}", text),
 //!         _ => {}
 //!     }
 //! }
@@ -83,13 +83,20 @@
 #![warn(missing_docs)]
 #![warn(rustdoc::broken_intra_doc_links)]
 
+// Lets `#[tool]` expansions name this crate from within it
+extern crate self as ai_sdk_core;
+
+mod call_settings;
 mod embed;
 mod embed_many;
 mod error;
 mod generate_text;
 mod retry;
-mod stop_condition;
+mod step_result;
 mod stream_text;
+#[cfg(test)]
+mod test_utils;
+mod timeout;
 mod tool;
 
 /// Utility functions for media type detection, file download, and base64 encoding
@@ -98,6 +105,9 @@ pub mod util;
 /// Generate structured objects with schema validation
 pub mod generate_object;
 
+/// Composable conditions that end a tool loop
+pub mod stop_condition;
+
 /// Agent framework for autonomous tool-using agents
 pub mod agent;
 
@@ -109,16 +119,46 @@ pub mod registry;
 
 // Re-export commonly used types from ai-sdk-provider
 pub use ai_sdk_provider::language_model::{
-    CallOptions, Content, FinishReason, LanguageModel, Message, ToolCallPart, ToolResultPart, Usage,
+    CallOptions, Content, FinishReason, LanguageModel, Message, ResponseFormat, ToolCallPart,
+    ToolChoice, ToolResultPart, Usage,
+};
+pub use ai_sdk_provider::{
+    AbortController, AbortError, AbortSignal, ApiCallError, EmbeddingModel, EmbeddingUsage,
+    JsonValue, SharedProviderOptions,
 };
-pub use ai_sdk_provider::{EmbeddingModel, EmbeddingUsage, JsonValue};
 
 // Re-export core functionality
+pub use call_settings::CallSettings;
 pub use embed::{embed, EmbedBuilder, EmbedResult};
 pub use embed_many::{embed_many, EmbedManyBuilder, EmbedManyResult};
-pub use error::{EmbedError, GenerateTextError, StreamTextError, ToolError};
-pub use generate_text::{generate_text, GenerateTextBuilder, GenerateTextResult, StepResult};
-pub use retry::RetryPolicy;
-pub use stop_condition::{stop_after_steps, stop_on_finish, StopCondition};
-pub use stream_text::{stream_text, StreamTextBuilder, StreamTextResult, TextStreamPart};
-pub use tool::{Tool, ToolContext, ToolExecutor, ToolOutput};
+pub use error::{
+    EmbedError, GenerateTextError, StreamInterruptedError, StreamTextError, TimeoutError,
+    TimeoutStage, ToolError,
+};
+pub use generate_text::{
+    generate_text, FinishEvent, GenerateTextBuilder, GenerateTextResult, OnErrorCallback,
+    OnFinishCallback, OnPreliminaryToolResultCallback, OnStepFinishCallback,
+};
+pub use retry::{is_retryable_error, OnRetryFn, RetryEvent, RetryPolicy, RetryableError};
+pub use step_result::StepResult;
+pub use stop_condition::{StopCondition, StopConditionContext, TokenPrice};
+pub use stream_text::{
+    stream_text, OnChunkCallback, StreamTextBuilder, StreamTextResult, TextStreamPart,
+};
+pub use timeout::Timeouts;
+pub use tool::{
+    input_schema_for, repair_tool_call_with_model, ErrorMode, Tool, ToolApprovalFn,
+    ToolApprovalRequest, ToolApprovalResponse, ToolCallRepairFn, ToolCallRepairRequest,
+    ToolContext, ToolExecutionPolicy, ToolExecutor, ToolOutput, TypedTool, UserContext,
+};
+
+/// Define a tool from an async function; see [`TypedTool`]
+pub use ai_sdk_macros::tool;
+
+/// The `schemars` version tool input types derive `JsonSchema` from
+pub use schemars;
+
+#[doc(hidden)]
+pub mod __private {
+    pub use async_trait::async_trait;
+}
@@ -65,6 +65,10 @@ fn merge_call_options(base: &CallOptions, overrides: &CallOptions) -> CallOption
             .provider_options
             .clone()
             .or_else(|| base.provider_options.clone()),
+        abort_signal: overrides
+            .abort_signal
+            .clone()
+            .or_else(|| base.abort_signal.clone()),
     }
 }
 
@@ -1,7 +1,113 @@
+use crate::error::{TimeoutError, TimeoutStage};
+use crate::timeout::Timeouts;
+use ai_sdk_provider::language_model::{CallOptions, LanguageModel, StreamPart, StreamResponse};
+use ai_sdk_provider::ApiCallError;
 use backoff::{backoff::Backoff, ExponentialBackoff};
-use std::time::Duration;
+use std::error::Error;
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio_stream::StreamExt;
+
+/// Callback invoked before each retry
+pub type OnRetryFn = Arc<dyn Fn(&RetryEvent<'_>) + Send + Sync>;
+
+/// Information about an upcoming retry, passed to [`RetryPolicy::on_retry`]
+pub struct RetryEvent<'a> {
+    /// Retry number, starting at 1
+    pub attempt: u32,
+    /// Maximum number of retries allowed by the policy
+    pub max_retries: u32,
+    /// Delay before the retry is made
+    pub delay: Duration,
+    /// Time elapsed since the first attempt started
+    pub elapsed: Duration,
+    /// The error that triggered the retry
+    pub error: &'a dyn fmt::Display,
+}
+
+/// Errors that [`RetryPolicy`] knows how to classify
+pub trait RetryableError {
+    /// Whether repeating the failed operation may succeed
+    fn is_retryable(&self) -> bool;
+
+    /// Delay requested by the server before retrying, if any
+    fn retry_after(&self) -> Option<Duration> {
+        None
+    }
+}
+
+impl RetryableError for ApiCallError {
+    fn is_retryable(&self) -> bool {
+        self.is_retryable
+    }
+
+    fn retry_after(&self) -> Option<Duration> {
+        ApiCallError::retry_after(self)
+    }
+}
+
+impl RetryableError for Box<dyn Error + Send + Sync> {
+    fn is_retryable(&self) -> bool {
+        is_retryable_error(self.as_ref())
+    }
+
+    fn retry_after(&self) -> Option<Duration> {
+        ApiCallError::find(self.as_ref()).and_then(ApiCallError::retry_after)
+    }
+}
+
+/// Returns whether an error, or any error in its source chain, is worth
+/// retrying
+///
+/// Retryable errors are [`ApiCallError`]s flagged as retryable (429, 5xx,
+/// connection failures), connection failures and timeouts reported by
+/// `reqwest` or `std::io`, elapsed `tokio` timeouts, and [`TimeoutError`]s
+/// for model calls and stalled streams.
+pub fn is_retryable_error(error: &(dyn Error + 'static)) -> bool {
+    use std::io::ErrorKind;
+
+    let mut current = Some(error);
+    while let Some(error) = current {
+        if let Some(api_error) = error.downcast_ref::<ApiCallError>() {
+            return api_error.is_retryable;
+        }
+        if let Some(timeout) = error.downcast_ref::<TimeoutError>() {
+            return timeout.is_retryable();
+        }
+        if let Some(error) = error.downcast_ref::<reqwest::Error>() {
+            if error.is_connect() || error.is_timeout() {
+                return true;
+            }
+        }
+        if let Some(error) = error.downcast_ref::<std::io::Error>() {
+            if matches!(
+                error.kind(),
+                ErrorKind::ConnectionReset
+                    | ErrorKind::ConnectionAborted
+                    | ErrorKind::ConnectionRefused
+                    | ErrorKind::BrokenPipe
+                    | ErrorKind::TimedOut
+                    | ErrorKind::UnexpectedEof
+            ) {
+                return true;
+            }
+        }
+        if error.is::<tokio::time::error::Elapsed>() {
+            return true;
+        }
+        current = error.source();
+    }
+    false
+}
 
 /// Retry policy for API calls
+///
+/// Only retryable errors are retried (see [`is_retryable_error`]). The delay
+/// between attempts grows exponentially with random jitter, unless the
+/// server asks for a specific delay with a `Retry-After` header. Delays
+/// asked for by the server are capped at
+/// [`max_retry_after`](Self::max_retry_after).
 #[derive(Clone)]
 pub struct RetryPolicy {
     /// Maximum number of retry attempts
@@ -10,6 +116,15 @@ pub struct RetryPolicy {
     pub initial_delay: Duration,
     /// Maximum delay between retries
     pub max_delay: Duration,
+    /// Maximum delay taken from a server's `Retry-After` header
+    pub max_retry_after: Duration,
+    /// Random jitter applied to each backoff delay, as a fraction of the
+    /// delay (0.0 disables jitter)
+    pub jitter: f64,
+    /// Maximum total time spent on attempts and delays before giving up
+    pub max_elapsed_time: Option<Duration>,
+    /// Callback invoked before each retry
+    pub on_retry: Option<OnRetryFn>,
 }
 
 impl Default for RetryPolicy {
@@ -18,10 +133,28 @@ impl Default for RetryPolicy {
             max_retries: 2,
             initial_delay: Duration::from_millis(500),
             max_delay: Duration::from_secs(32),
+            max_retry_after: Duration::from_secs(60),
+            jitter: 0.5,
+            max_elapsed_time: None,
+            on_retry: None,
         }
     }
 }
 
+impl fmt::Debug for RetryPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RetryPolicy")
+            .field("max_retries", &self.max_retries)
+            .field("initial_delay", &self.initial_delay)
+            .field("max_delay", &self.max_delay)
+            .field("max_retry_after", &self.max_retry_after)
+            .field("jitter", &self.jitter)
+            .field("max_elapsed_time", &self.max_elapsed_time)
+            .field("on_retry", &self.on_retry.is_some())
+            .finish()
+    }
+}
+
 impl RetryPolicy {
     /// Creates a new RetryPolicy with default settings
     pub fn new() -> Self {
@@ -46,16 +179,67 @@ impl RetryPolicy {
         self
     }
 
-    /// Executes the given function with retry logic using exponential backoff
+    /// Sets the maximum delay taken from a server's `Retry-After` header
+    ///
+    /// Longer delays asked for by the server are shortened to this limit.
+    pub fn with_max_retry_after(mut self, delay: Duration) -> Self {
+        self.max_retry_after = delay;
+        self
+    }
+
+    /// Sets the jitter factor, clamped to `0.0..=1.0`
+    ///
+    /// A factor of 0.5 picks each delay at random between 50% and 150% of
+    /// the exponential backoff interval.
+    pub fn with_jitter(mut self, jitter: f64) -> Self {
+        self.jitter = jitter.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Sets the maximum total time before giving up
+    ///
+    /// A retry is skipped when its delay would end after this deadline.
+    pub fn with_max_elapsed_time(mut self, max_elapsed_time: Duration) -> Self {
+        self.max_elapsed_time = Some(max_elapsed_time);
+        self
+    }
+
+    /// Sets a callback invoked before each retry, e.g. for logging
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ai_sdk_core::RetryPolicy;
+    ///
+    /// let policy = RetryPolicy::new().on_retry(|event| {
+    ///     eprintln!(
+    ///         "retry {}/{} in {:?}: {}",
+    ///         event.attempt, event.max_retries, event.delay, event.error
+    ///     );
+    /// });
+    /// ```
+    pub fn on_retry<F>(mut self, on_retry: F) -> Self
+    where
+        F: Fn(&RetryEvent<'_>) + Send + Sync + 'static,
+    {
+        self.on_retry = Some(Arc::new(on_retry));
+        self
+    }
+
+    /// Executes the given function, retrying retryable errors with
+    /// exponential backoff
     pub async fn retry<F, Fut, T, E>(&self, mut f: F) -> Result<T, E>
     where
         F: FnMut() -> Fut,
         Fut: std::future::Future<Output = Result<T, E>>,
-        E: std::fmt::Debug,
+        E: RetryableError + fmt::Display,
     {
+        let start = Instant::now();
         let mut backoff = ExponentialBackoff {
+            current_interval: self.initial_delay,
             initial_interval: self.initial_delay,
             max_interval: self.max_delay,
+            randomization_factor: self.jitter,
             max_elapsed_time: None,
             ..Default::default()
         };
@@ -66,36 +250,118 @@ impl RetryPolicy {
                 Ok(result) => return Ok(result),
                 Err(err) => {
                     attempt += 1;
-                    if attempt > self.max_retries {
+                    if attempt > self.max_retries || !err.is_retryable() {
                         return Err(err);
                     }
 
-                    if let Some(delay) = backoff.next_backoff() {
-                        tracing::debug!(
-                            "Retrying after {:?}, attempt {}/{}",
-                            delay,
+                    // Always advance the backoff so later retries keep growing
+                    let backoff_delay = backoff
+                        .next_backoff()
+                        .unwrap_or(self.max_delay)
+                        .min(self.max_delay);
+                    let delay = err
+                        .retry_after()
+                        .map_or(backoff_delay, |delay| delay.min(self.max_retry_after));
+
+                    let elapsed = start.elapsed();
+                    if let Some(max_elapsed_time) = self.max_elapsed_time {
+                        if elapsed.saturating_add(delay) > max_elapsed_time {
+                            return Err(err);
+                        }
+                    }
+
+                    tracing::debug!(
+                        "Retrying after {:?}, attempt {}/{}: {}",
+                        delay,
+                        attempt,
+                        self.max_retries,
+                        err
+                    );
+                    if let Some(on_retry) = &self.on_retry {
+                        on_retry(&RetryEvent {
                             attempt,
-                            self.max_retries
-                        );
-                        tokio::time::sleep(delay).await;
-                    } else {
-                        return Err(err);
+                            max_retries: self.max_retries,
+                            delay,
+                            elapsed,
+                            error: &err,
+                        });
                     }
+                    tokio::time::sleep(delay).await;
                 }
             }
         }
     }
+
+    /// Starts a model stream, retrying failures that happen before the
+    /// first chunk of output
+    ///
+    /// Parts read while waiting for the first chunk (stream start, response
+    /// metadata, raw chunks) are replayed at the start of the returned
+    /// stream, so callers see the stream exactly as the model produced it.
+    ///
+    /// Each attempt is bounded by the model call and stream idle limits in
+    /// `timeouts`.
+    pub(crate) async fn retry_stream(
+        &self,
+        model: &Arc<dyn LanguageModel>,
+        options: &CallOptions,
+        timeouts: &Timeouts,
+    ) -> Result<StreamResponse, Box<dyn Error + Send + Sync>> {
+        self.retry(|| {
+            let model = model.clone();
+            let options = options.clone();
+            async move {
+                let mut response = timeouts
+                    .limit(TimeoutStage::ModelCall, model.do_stream(options))
+                    .await??;
+
+                let mut buffered = Vec::new();
+                while let Some(part) = timeouts
+                    .limit(TimeoutStage::StreamIdle, response.stream.next())
+                    .await?
+                {
+                    let part = part?;
+                    let is_output = !matches!(
+                        part,
+                        StreamPart::StreamStart { .. }
+                            | StreamPart::ResponseMetadata { .. }
+                            | StreamPart::Raw { .. }
+                    );
+                    buffered.push(Ok(part));
+                    if is_output {
+                        break;
+                    }
+                }
+
+                let rest = response.stream;
+                response.stream = Box::pin(tokio_stream::iter(buffered).chain(rest));
+                Ok(response)
+            }
+        })
+        .await
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::collections::HashMap;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Mutex;
+
+    fn api_error(status_code: u16) -> ApiCallError {
+        ApiCallError::new("failed", "https://api.example.com").with_status_code(status_code)
+    }
+
+    fn fast_policy() -> RetryPolicy {
+        RetryPolicy::default().with_initial_delay(Duration::from_millis(1))
+    }
 
     #[tokio::test]
     async fn test_retry_succeeds_on_first_attempt() {
         let policy = RetryPolicy::default();
         let result = policy
-            .retry(|| async { Ok::<i32, String>(42) })
+            .retry(|| async { Ok::<i32, ApiCallError>(42) })
             .await
             .unwrap();
         assert_eq!(result, 42);
@@ -103,9 +369,6 @@ mod tests {
 
     #[tokio::test]
     async fn test_retry_succeeds_after_failures() {
-        use std::sync::atomic::{AtomicU32, Ordering};
-        use std::sync::Arc;
-
         let policy = RetryPolicy::default();
         let attempts = Arc::new(AtomicU32::new(0));
         let attempts_clone = attempts.clone();
@@ -116,7 +379,7 @@ mod tests {
                 async move {
                     let count = attempts.fetch_add(1, Ordering::SeqCst) + 1;
                     if count < 2 {
-                        Err("transient error")
+                        Err(api_error(503))
                     } else {
                         Ok(42)
                     }
@@ -131,9 +394,6 @@ mod tests {
 
     #[tokio::test]
     async fn test_retry_fails_after_max_retries() {
-        use std::sync::atomic::{AtomicU32, Ordering};
-        use std::sync::Arc;
-
         let policy = RetryPolicy::default().with_max_retries(1);
         let attempts = Arc::new(AtomicU32::new(0));
         let attempts_clone = attempts.clone();
@@ -143,7 +403,7 @@ mod tests {
                 let attempts = attempts_clone.clone();
                 async move {
                     attempts.fetch_add(1, Ordering::SeqCst);
-                    Err::<i32, _>("permanent error")
+                    Err::<i32, _>(api_error(500))
                 }
             })
             .await;
@@ -151,4 +411,177 @@ mod tests {
         assert!(result.is_err());
         assert_eq!(attempts.load(Ordering::SeqCst), 2); // Initial attempt + 1 retry
     }
+
+    #[tokio::test]
+    async fn test_non_retryable_errors_are_not_retried() {
+        for status in [400, 401, 403, 404] {
+            let attempts = AtomicU32::new(0);
+            let result = fast_policy()
+                .retry(|| {
+                    attempts.fetch_add(1, Ordering::SeqCst);
+                    async move { Err::<i32, _>(api_error(status)) }
+                })
+                .await;
+
+            assert_eq!(result.unwrap_err().status_code, Some(status));
+            assert_eq!(attempts.load(Ordering::SeqCst), 1, "status {}", status);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_boxed_errors_are_classified() {
+        let cases: Vec<(Box<dyn Error + Send + Sync>, bool)> = vec![
+            (Box::new(api_error(429)), true),
+            (Box::new(api_error(422)), false),
+            (
+                Box::new(std::io::Error::from(std::io::ErrorKind::ConnectionReset)),
+                true,
+            ),
+            (
+                Box::new(std::io::Error::from(std::io::ErrorKind::NotFound)),
+                false,
+            ),
+            ("malformed request".into(), false),
+        ];
+
+        for (error, expected) in cases {
+            assert_eq!(error.is_retryable(), expected, "{}", error);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retry_after_is_honoured() {
+        let delays = Arc::new(Mutex::new(Vec::new()));
+        let delays_clone = delays.clone();
+        let policy = fast_policy()
+            .with_jitter(0.0)
+            .on_retry(move |event| delays_clone.lock().unwrap().push(event.delay));
+
+        let attempts = AtomicU32::new(0);
+        let result = policy
+            .retry(|| {
+                let count = attempts.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    if count == 0 {
+                        Err(api_error(429).with_response_headers(HashMap::from([(
+                            "retry-after-ms".to_string(),
+                            "20".to_string(),
+                        )])))
+                    } else if count == 1 {
+                        Err(api_error(503))
+                    } else {
+                        Ok(count)
+                    }
+                }
+            })
+            .await;
+
+        assert_eq!(result.unwrap(), 2);
+        assert_eq!(
+            *delays.lock().unwrap(),
+            vec![Duration::from_millis(20), Duration::from_micros(1500)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_retry_after_is_capped() {
+        let delays = Arc::new(Mutex::new(Vec::new()));
+        let delays_clone = delays.clone();
+        let policy = fast_policy()
+            .with_max_retries(1)
+            .with_max_retry_after(Duration::from_millis(10))
+            .on_retry(move |event| delays_clone.lock().unwrap().push(event.delay));
+
+        let attempts = AtomicU32::new(0);
+        let result = policy
+            .retry(|| {
+                let count = attempts.fetch_add(1, Ordering::SeqCst);
+                async move {
+                    if count == 0 {
+                        Err(api_error(429).with_response_headers(HashMap::from([(
+                            "retry-after".to_string(),
+                            "86400".to_string(),
+                        )])))
+                    } else {
+                        Ok(count)
+                    }
+                }
+            })
+            .await;
+
+        assert_eq!(result.unwrap(), 1);
+        assert_eq!(*delays.lock().unwrap(), vec![Duration::from_millis(10)]);
+    }
+
+    #[tokio::test]
+    async fn test_max_elapsed_time_with_huge_delay() {
+        let policy = fast_policy()
+            .with_max_retry_after(Duration::MAX)
+            .with_max_elapsed_time(Duration::from_millis(100));
+
+        let result = policy
+            .retry(|| async {
+                Err::<i32, _>(api_error(429).with_response_headers(HashMap::from([(
+                    "retry-after".to_string(),
+                    "1e19".to_string(),
+                )])))
+            })
+            .await;
+
+        assert_eq!(result.unwrap_err().status_code, Some(429));
+    }
+
+    #[tokio::test]
+    async fn test_max_elapsed_time() {
+        let attempts = AtomicU32::new(0);
+        let policy = RetryPolicy::default()
+            .with_max_retries(5)
+            .with_max_elapsed_time(Duration::from_millis(100));
+
+        let start = Instant::now();
+        let result = policy
+            .retry(|| {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                async {
+                    Err::<i32, _>(api_error(429).with_response_headers(HashMap::from([(
+                        "retry-after".to_string(),
+                        "10".to_string(),
+                    )])))
+                }
+            })
+            .await;
+
+        assert!(result.is_err());
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+        assert!(start.elapsed() < Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn test_on_retry_hook() {
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let events_clone = events.clone();
+        let policy = fast_policy().with_max_retries(2).on_retry(move |event| {
+            events_clone.lock().unwrap().push((
+                event.attempt,
+                event.max_retries,
+                event.error.to_string(),
+            ));
+        });
+
+        let result = policy
+            .retry(|| async { Err::<i32, _>(api_error(500)) })
+            .await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![(1, 2, "failed".to_string()), (2, 2, "failed".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_jitter_is_clamped() {
+        assert_eq!(RetryPolicy::new().with_jitter(3.0).jitter, 1.0);
+        assert_eq!(RetryPolicy::new().with_jitter(-1.0).jitter, 0.0);
+    }
 }
@@ -0,0 +1,208 @@
+use ai_sdk_provider::language_model::{
+    CallWarning, Content, FilePart, FinishReason, GenerateResponse, ReasoningPart, RequestInfo,
+    ResponseInfo, SourcePart, ToolCallPart, ToolResultPart, Usage,
+};
+use ai_sdk_provider::SharedProviderMetadata;
+
+/// Result of a single generation step
+///
+/// Shared by `generate_text`, `stream_text` and agents. `content` keeps the
+/// model output in the order it was produced; the accessors pick out the
+/// parts of one kind.
+#[derive(Debug, Clone)]
+pub struct StepResult {
+    /// Index of this step in the generation sequence
+    pub step_index: u32,
+    /// Content returned by the model in this step
+    pub content: Vec<Content>,
+    /// Tool calls made by the model in this step
+    pub tool_calls: Vec<ToolCallPart>,
+    /// Results of provider-executed tools and of the tools run after this step
+    pub tool_results: Vec<ToolResultPart>,
+    /// Reason why generation finished
+    pub finish_reason: FinishReason,
+    /// Token usage for this step
+    pub usage: Usage,
+    /// Warnings reported by the provider for this step
+    pub warnings: Vec<CallWarning>,
+    /// Information about the request sent to the provider
+    pub request: Option<RequestInfo>,
+    /// Information about the provider's response
+    pub response: Option<ResponseInfo>,
+    /// Provider-specific metadata
+    pub provider_metadata: Option<SharedProviderMetadata>,
+}
+
+impl StepResult {
+    /// Builds the step from a model response
+    pub(crate) fn new(step_index: u32, response: GenerateResponse) -> Self {
+        let mut step = Self {
+            step_index,
+            content: Vec::new(),
+            tool_calls: Vec::new(),
+            tool_results: Vec::new(),
+            finish_reason: response.finish_reason,
+            usage: response.usage,
+            warnings: response.warnings,
+            request: response.request,
+            response: response.response,
+            provider_metadata: response.provider_metadata,
+        };
+        step.set_content(response.content);
+        step
+    }
+
+    /// Replaces tool calls with the calls of the same ID in `tool_calls`
+    pub(crate) fn set_tool_calls(&mut self, tool_calls: &[ToolCallPart]) {
+        let content = std::mem::take(&mut self.content)
+            .into_iter()
+            .map(|c| match c {
+                Content::ToolCall(tc) => Content::ToolCall(
+                    tool_calls
+                        .iter()
+                        .find(|call| call.tool_call_id == tc.tool_call_id)
+                        .cloned()
+                        .unwrap_or(tc),
+                ),
+                other => other,
+            })
+            .collect();
+        self.set_content(content);
+    }
+
+    /// Replaces the content and the tool calls and results taken from it
+    pub(crate) fn set_content(&mut self, content: Vec<Content>) {
+        self.tool_calls = content
+            .iter()
+            .filter_map(|c| match c {
+                Content::ToolCall(tc) => Some(tc.clone()),
+                _ => None,
+            })
+            .collect();
+        self.tool_results = content
+            .iter()
+            .filter_map(|c| match c {
+                Content::ToolResult(tr) => Some(tr.clone()),
+                _ => None,
+            })
+            .collect();
+        self.content = content;
+    }
+
+    /// Text generated in this step
+    pub fn text(&self) -> String {
+        self.content
+            .iter()
+            .filter_map(|c| match c {
+                Content::Text(text_part) => Some(text_part.text.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Reasoning parts generated in this step
+    pub fn reasoning(&self) -> Vec<&ReasoningPart> {
+        self.content
+            .iter()
+            .filter_map(|c| match c {
+                Content::Reasoning(part) => Some(part),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Reasoning text generated in this step, if any
+    pub fn reasoning_text(&self) -> Option<String> {
+        let reasoning = self.reasoning();
+        if reasoning.is_empty() {
+            None
+        } else {
+            Some(
+                reasoning
+                    .iter()
+                    .map(|part| part.reasoning.as_str())
+                    .collect(),
+            )
+        }
+    }
+
+    /// Sources cited in this step
+    pub fn sources(&self) -> Vec<&SourcePart> {
+        self.content
+            .iter()
+            .filter_map(|c| match c {
+                Content::Source(source) => Some(source),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Files generated in this step
+    pub fn files(&self) -> Vec<&FilePart> {
+        self.content
+            .iter()
+            .filter_map(|c| match c {
+                Content::File(file) => Some(file),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::usage;
+    use ai_sdk_provider::language_model::{SourceType, TextPart};
+
+    #[test]
+    fn test_accessors_pick_parts_by_kind() {
+        let response = GenerateResponse {
+            content: vec![
+                Content::Reasoning(ReasoningPart {
+                    reasoning: "Think".to_string(),
+                    provider_metadata: None,
+                }),
+                Content::Text(TextPart {
+                    text: "Hello".to_string(),
+                    provider_metadata: None,
+                }),
+                Content::Source(SourcePart {
+                    source_type: SourceType::Url,
+                    id: "src_1".to_string(),
+                    url: Some("https://example.com".to_string()),
+                    title: None,
+                    provider_metadata: None,
+                }),
+                Content::Text(TextPart {
+                    text: " world".to_string(),
+                    provider_metadata: None,
+                }),
+                Content::ToolCall(ToolCallPart {
+                    tool_call_id: "call_123".to_string(),
+                    tool_name: "weather".to_string(),
+                    input: r#"{"location":"Tokyo"}"#.to_string(),
+                    provider_executed: None,
+                    dynamic: None,
+                    provider_metadata: None,
+                }),
+            ],
+            finish_reason: FinishReason::ToolCalls,
+            usage: usage(1, 2),
+            provider_metadata: None,
+            request: None,
+            response: None,
+            warnings: vec![],
+        };
+
+        let step = StepResult::new(0, response);
+
+        assert_eq!(step.text(), "Hello world");
+        assert_eq!(step.reasoning_text().as_deref(), Some("Think"));
+        assert_eq!(step.sources().len(), 1);
+        assert!(step.files().is_empty());
+        assert_eq!(step.tool_calls.len(), 1);
+        assert_eq!(step.tool_calls[0].tool_name, "weather");
+        assert!(step.tool_results.is_empty());
+    }
+}
@@ -1,65 +1,394 @@
-use ai_sdk_provider::FinishReason;
+use crate::step_result::StepResult;
+use futures::future::BoxFuture;
+use regex::Regex;
+use std::sync::Arc;
+use std::time::Duration;
 
-/// Trait for determining when to stop the tool execution loop
-pub trait StopCondition: Send + Sync {
-    /// Returns true if the tool execution loop should stop
-    fn should_stop(&self, step: u32, finish_reason: &FinishReason) -> bool;
+/// Function type for stop conditions
+pub type StopConditionFn =
+    Arc<dyn Fn(&StopConditionContext<'_>) -> BoxFuture<'static, bool> + Send + Sync>;
+
+/// Context provided to stop conditions after each step
+#[derive(Debug, Clone, Copy)]
+pub struct StopConditionContext<'a> {
+    /// All steps executed so far, including their tool results
+    pub steps: &'a [StepResult],
+    /// Time since the generation started
+    pub elapsed: Duration,
 }
 
-/// Stop after a maximum number of steps
-pub struct StopAfterSteps {
-    max_steps: u32,
+/// A condition that ends a tool loop once it holds
+///
+/// `generate_text`, `stream_text` and agents check their conditions after
+/// each step whose tool calls were executed, and stop before the next model
+/// call if any of them is met. Conditions compose with [`any`] and [`all`].
+///
+/// # Example
+///
+/// ```
+/// use ai_sdk_core::stop_condition::{any, has_tool_call, step_count_is, token_budget};
+///
+/// let stop = any(vec![
+///     step_count_is(10),
+///     token_budget(50_000),
+///     has_tool_call("final_answer"),
+/// ]);
+/// ```
+#[derive(Clone)]
+pub struct StopCondition {
+    condition: StopConditionFn,
 }
 
-impl StopAfterSteps {
-    pub fn new(max_steps: u32) -> Self {
-        Self { max_steps }
+impl StopCondition {
+    /// Create a new stop condition from a function
+    pub fn new<F>(f: F) -> Self
+    where
+        F: Fn(&StopConditionContext<'_>) -> BoxFuture<'static, bool> + Send + Sync + 'static,
+    {
+        Self {
+            condition: Arc::new(f),
+        }
+    }
+
+    /// Create a stop condition from a synchronous predicate
+    pub fn from_fn<F>(f: F) -> Self
+    where
+        F: Fn(&StopConditionContext<'_>) -> bool + Send + Sync + 'static,
+    {
+        Self::new(move |ctx| {
+            let met = f(ctx);
+            Box::pin(async move { met })
+        })
+    }
+
+    /// Evaluate this stop condition
+    pub async fn evaluate(&self, context: &StopConditionContext<'_>) -> bool {
+        (self.condition)(context).await
     }
 }
 
-impl StopCondition for StopAfterSteps {
-    fn should_stop(&self, step: u32, _finish_reason: &FinishReason) -> bool {
-        step >= self.max_steps
+impl std::fmt::Debug for StopCondition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StopCondition").finish_non_exhaustive()
     }
 }
 
-/// Stop when model returns a finish reason other than ToolCalls
-pub struct StopOnFinish;
+/// Per-token prices used by [`cost_budget`]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct TokenPrice {
+    /// Price of one million input tokens
+    pub input_per_million: f64,
+    /// Price of one million output tokens
+    pub output_per_million: f64,
+}
+
+impl TokenPrice {
+    /// Creates prices from the cost of one million input and output tokens
+    pub fn per_million(input: f64, output: f64) -> Self {
+        Self {
+            input_per_million: input,
+            output_per_million: output,
+        }
+    }
+
+    /// Cost of the tokens used by `steps`
+    pub fn cost(&self, steps: &[StepResult]) -> f64 {
+        steps
+            .iter()
+            .map(|step| {
+                let input = step.usage.input_tokens.unwrap_or(0) as f64;
+                let output = step.usage.output_tokens.unwrap_or(0) as f64;
+                (input * self.input_per_million + output * self.output_per_million) / 1_000_000.0
+            })
+            .sum()
+    }
+}
 
-impl StopCondition for StopOnFinish {
-    fn should_stop(&self, _step: u32, finish_reason: &FinishReason) -> bool {
-        !matches!(finish_reason, FinishReason::ToolCalls)
+/// Check if any stop condition is met
+pub async fn is_stop_condition_met(
+    conditions: &[StopCondition],
+    context: &StopConditionContext<'_>,
+) -> bool {
+    if conditions.is_empty() {
+        return false;
     }
+
+    // Evaluate all conditions in parallel
+    let results = futures::future::join_all(conditions.iter().map(|c| c.evaluate(context))).await;
+
+    // Return true if ANY condition is met
+    results.iter().any(|&result| result)
+}
+
+/// Stop after a certain number of steps
+pub fn step_count_is(count: usize) -> StopCondition {
+    StopCondition::from_fn(move |ctx| ctx.steps.len() >= count)
+}
+
+/// Stop when a specific tool was called in the last step
+pub fn has_tool_call(tool_name: impl Into<String>) -> StopCondition {
+    let tool_name = tool_name.into();
+    StopCondition::from_fn(move |ctx| {
+        ctx.steps
+            .last()
+            .map(|step| {
+                step.tool_calls
+                    .iter()
+                    .any(|call| call.tool_name == tool_name)
+            })
+            .unwrap_or(false)
+    })
+}
+
+/// Stop when a specific tool returned a result in the last step
+pub fn has_tool_result(tool_name: impl Into<String>) -> StopCondition {
+    let tool_name = tool_name.into();
+    StopCondition::from_fn(move |ctx| {
+        ctx.steps
+            .last()
+            .map(|step| {
+                step.tool_results
+                    .iter()
+                    .any(|result| result.tool_name == tool_name)
+            })
+            .unwrap_or(false)
+    })
+}
+
+/// Stop once the steps used at least `max_tokens` tokens in total
+pub fn token_budget(max_tokens: u32) -> StopCondition {
+    StopCondition::from_fn(move |ctx| {
+        let used: u64 = ctx
+            .steps
+            .iter()
+            .map(|step| u64::from(step.usage.total_tokens.unwrap_or(0)))
+            .sum();
+        used >= u64::from(max_tokens)
+    })
 }
 
-/// Creates a stop condition that stops after a maximum number of steps
-pub fn stop_after_steps(max_steps: u32) -> Box<dyn StopCondition> {
-    Box::new(StopAfterSteps::new(max_steps))
+/// Stop once the steps cost at least `max_cost` at `price`
+pub fn cost_budget(max_cost: f64, price: TokenPrice) -> StopCondition {
+    StopCondition::from_fn(move |ctx| price.cost(ctx.steps) >= max_cost)
 }
 
-/// Creates a stop condition that stops when the model returns a non-ToolCalls finish reason
-pub fn stop_on_finish() -> Box<dyn StopCondition> {
-    Box::new(StopOnFinish)
+/// Stop once the generation has run for at least `limit`
+///
+/// Unlike [`Timeouts::total`](crate::Timeouts::total) this never interrupts
+/// a step; the current step finishes and its result is kept.
+pub fn elapsed_time(limit: Duration) -> StopCondition {
+    StopCondition::from_fn(move |ctx| ctx.elapsed >= limit)
+}
+
+/// Stop when the text of the last step matches `pattern`
+pub fn text_matches(pattern: Regex) -> StopCondition {
+    StopCondition::from_fn(move |ctx| {
+        ctx.steps
+            .last()
+            .map(|step| pattern.is_match(&step.text()))
+            .unwrap_or(false)
+    })
+}
+
+/// Stop when any of `conditions` is met
+pub fn any(conditions: Vec<StopCondition>) -> StopCondition {
+    StopCondition::new(move |ctx| {
+        let evaluations: Vec<_> = conditions.iter().map(|c| (c.condition)(ctx)).collect();
+        Box::pin(async move {
+            futures::future::join_all(evaluations)
+                .await
+                .into_iter()
+                .any(|met| met)
+        })
+    })
+}
+
+/// Stop when all of `conditions` are met
+pub fn all(conditions: Vec<StopCondition>) -> StopCondition {
+    StopCondition::new(move |ctx| {
+        let evaluations: Vec<_> = conditions.iter().map(|c| (c.condition)(ctx)).collect();
+        Box::pin(async move {
+            futures::future::join_all(evaluations)
+                .await
+                .into_iter()
+                .all(|met| met)
+        })
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_utils::{text_response, tool_call_response, usage};
+    use ai_sdk_provider::language_model::{
+        Content, FinishReason, GenerateResponse, TextPart, ToolCallPart, ToolResultOutput,
+        ToolResultPart, Usage,
+    };
+
+    fn create_test_step(tool_name: Option<&str>) -> StepResult {
+        let content = if let Some(name) = tool_name {
+            vec![Content::ToolCall(ToolCallPart {
+                tool_call_id: "call_1".to_string(),
+                tool_name: name.to_string(),
+                input: "{}".to_string(),
+                provider_executed: None,
+                dynamic: None,
+                provider_metadata: None,
+            })]
+        } else {
+            vec![Content::Text(TextPart {
+                text: "Hello".to_string(),
+                provider_metadata: None,
+            })]
+        };
+
+        StepResult::new(
+            0,
+            GenerateResponse {
+                content,
+                finish_reason: FinishReason::Stop,
+                usage: Usage {
+                    input_tokens: Some(10),
+                    output_tokens: Some(20),
+                    total_tokens: Some(30),
+                    reasoning_tokens: None,
+                    cached_input_tokens: None,
+                },
+                provider_metadata: None,
+                request: None,
+                response: None,
+                warnings: vec![],
+            },
+        )
+    }
+
+    fn context(steps: &[StepResult]) -> StopConditionContext<'_> {
+        StopConditionContext {
+            steps,
+            elapsed: Duration::ZERO,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_step_count_is() {
+        let condition = step_count_is(3);
+        let steps = vec![create_test_step(None), create_test_step(None)];
+        assert!(!condition.evaluate(&context(&steps)).await);
+
+        let steps = vec![
+            create_test_step(None),
+            create_test_step(None),
+            create_test_step(None),
+        ];
+        assert!(condition.evaluate(&context(&steps)).await);
+    }
+
+    #[tokio::test]
+    async fn test_has_tool_call() {
+        let condition = has_tool_call("weather");
+
+        let steps = vec![create_test_step(Some("weather"))];
+        assert!(condition.evaluate(&context(&steps)).await);
+
+        let steps = vec![create_test_step(Some("other_tool"))];
+        assert!(!condition.evaluate(&context(&steps)).await);
+    }
+
+    #[tokio::test]
+    async fn test_has_tool_result() {
+        let condition = has_tool_result("weather");
+        let mut step = StepResult::new(0, tool_call_response("weather", serde_json::json!({})));
+        assert!(!condition.evaluate(&context(&[step.clone()])).await);
+
+        step.tool_results.push(ToolResultPart {
+            tool_call_id: "call_1".into(),
+            tool_name: "weather".into(),
+            output: ToolResultOutput::Text {
+                value: "sunny".into(),
+                provider_metadata: None,
+            },
+            provider_executed: None,
+            preliminary: None,
+            provider_metadata: None,
+        });
+        assert!(condition.evaluate(&context(&[step])).await);
+    }
+
+    #[tokio::test]
+    async fn test_budgets() {
+        // Each step uses 10 input and 5 output tokens
+        let steps = vec![
+            StepResult::new(0, text_response("a")),
+            StepResult::new(1, text_response("b")),
+        ];
+        assert!(token_budget(30).evaluate(&context(&steps)).await);
+        assert!(!token_budget(31).evaluate(&context(&steps)).await);
+
+        // 20 input tokens at $1/M plus 10 output tokens at $2/M
+        let price = TokenPrice::per_million(1.0, 2.0);
+        assert!((price.cost(&steps) - 0.00004).abs() < 1e-12);
+        assert!(cost_budget(0.00004, price).evaluate(&context(&steps)).await);
+        assert!(!cost_budget(0.0001, price).evaluate(&context(&steps)).await);
+
+        let mut step = StepResult::new(0, text_response(""));
+        step.usage = usage(0, 0);
+        step.usage.total_tokens = None;
+        assert!(!token_budget(1).evaluate(&context(&[step])).await);
+    }
+
+    #[tokio::test]
+    async fn test_elapsed_time_and_text_matches() {
+        let steps = vec![StepResult::new(0, text_response("FINAL ANSWER: 42"))];
+        let ctx = StopConditionContext {
+            steps: &steps,
+            elapsed: Duration::from_secs(5),
+        };
+
+        assert!(elapsed_time(Duration::from_secs(5)).evaluate(&ctx).await);
+        assert!(!elapsed_time(Duration::from_secs(6)).evaluate(&ctx).await);
+        let pattern = Regex::new(r"FINAL ANSWER: \d+").unwrap();
+        assert!(text_matches(pattern).evaluate(&ctx).await);
+        let pattern = Regex::new(r"^DONE$").unwrap();
+        assert!(!text_matches(pattern).evaluate(&ctx).await);
+    }
+
+    #[tokio::test]
+    async fn test_combinators() {
+        let steps = vec![create_test_step(Some("weather"))];
+        let ctx = context(&steps);
+
+        assert!(
+            any(vec![step_count_is(5), has_tool_call("weather")])
+                .evaluate(&ctx)
+                .await
+        );
+        assert!(
+            !all(vec![step_count_is(5), has_tool_call("weather")])
+                .evaluate(&ctx)
+                .await
+        );
+        assert!(
+            all(vec![step_count_is(1), has_tool_call("weather")])
+                .evaluate(&ctx)
+                .await
+        );
+        assert!(!any(vec![]).evaluate(&ctx).await);
+    }
+
+    #[tokio::test]
+    async fn test_is_stop_condition_met() {
+        let conditions = vec![step_count_is(2), has_tool_call("weather")];
+
+        // No conditions met
+        let steps = vec![create_test_step(None)];
+        assert!(!is_stop_condition_met(&conditions, &context(&steps)).await);
+
+        // Step count condition met
+        let steps = vec![create_test_step(None), create_test_step(None)];
+        assert!(is_stop_condition_met(&conditions, &context(&steps)).await);
 
-    #[test]
-    fn test_stop_after_steps() {
-        let condition = StopAfterSteps::new(3);
-        assert!(!condition.should_stop(0, &FinishReason::Stop));
-        assert!(!condition.should_stop(2, &FinishReason::Stop));
-        assert!(condition.should_stop(3, &FinishReason::Stop));
-        assert!(condition.should_stop(4, &FinishReason::Stop));
-    }
-
-    #[test]
-    fn test_stop_on_finish() {
-        let condition = StopOnFinish;
-        assert!(condition.should_stop(0, &FinishReason::Stop));
-        assert!(condition.should_stop(0, &FinishReason::Length));
-        assert!(!condition.should_stop(0, &FinishReason::ToolCalls));
+        // Tool call condition met
+        let steps = vec![create_test_step(Some("weather"))];
+        assert!(is_stop_condition_met(&conditions, &context(&steps)).await);
     }
 }
@@ -1,23 +1,61 @@
-use crate::error::StreamTextError;
-use crate::tool::{Tool, ToolExecutor};
+use crate::agent::{PrepareStepContext, PrepareStepFn};
+use crate::call_settings::CallSettings;
+use crate::error::{StreamInterruptedError, StreamTextError, TimeoutError, TimeoutStage};
+use crate::generate_text::{
+    assistant_message, repair_step_tool_calls, FinishEvent, OnErrorCallback, OnFinishCallback,
+    OnStepFinishCallback,
+};
+use crate::retry::RetryPolicy;
+use crate::step_result::StepResult;
+use crate::stop_condition::{is_stop_condition_met, StopCondition, StopConditionContext};
+use crate::timeout::Timeouts;
+use crate::tool::{
+    unresolved_tool_calls, Tool, ToolApprovalFn, ToolApprovalRequest, ToolApprovalResponse,
+    ToolCallRepairFn, ToolExecutionPolicy, ToolExecutor, UserContext,
+};
+use ai_sdk_provider::language_model::StreamError;
 use ai_sdk_provider::language_model::{
-    AssistantContentPart, CallOptions, Content, FinishReason, LanguageModel, Message, StreamPart,
+    CallOptions, CallWarning, Content, FilePart, FinishReason, GenerateResponse, LanguageModel,
+    Message, ReasoningPart, ResponseFormat, ResponseInfo, ResponseMetadata, SourcePart, StreamPart,
     TextPart, Tool as ProviderTool, ToolCallPart, ToolChoice, ToolResultPart, Usage,
     UserContentPart,
 };
+use ai_sdk_provider::{
+    abortable, AbortSignal, JsonValue, SharedProviderMetadata, SharedProviderOptions,
+};
 use async_stream::stream;
+use futures::future::BoxFuture;
+use std::collections::{HashMap, VecDeque};
 use std::pin::Pin;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Instant;
+use tokio::sync::{oneshot, watch};
+use tokio::task::JoinHandle;
 use tokio_stream::{Stream, StreamExt};
 
+/// Callback invoked for each content part of a text stream
+pub type OnChunkCallback = Arc<dyn Fn(TextStreamPart) -> BoxFuture<'static, ()> + Send + Sync>;
+
 /// Builder for streaming text generation
 pub struct StreamTextBuilder {
     model: Option<Arc<dyn LanguageModel>>,
     prompt: Option<Vec<Message>>,
     tools: Vec<Arc<dyn Tool>>,
     max_steps: u32,
-    temperature: Option<f32>,
-    max_tokens: Option<u32>,
+    stop_conditions: Vec<StopCondition>,
+    settings: CallSettings,
+    retry_policy: RetryPolicy,
+    callbacks: StreamCallbacks,
+    on_step_finish: Option<OnStepFinishCallback>,
+    abort_signal: Option<AbortSignal>,
+    timeouts: Timeouts,
+    tool_approval: Option<ToolApprovalFn>,
+    approvals: Vec<ToolApprovalResponse>,
+    tool_context: Option<UserContext>,
+    validate_tool_inputs: bool,
+    repair_tool_call: Option<ToolCallRepairFn>,
+    strict_tool_calls: bool,
+    tool_execution_policy: ToolExecutionPolicy,
 }
 
 impl StreamTextBuilder {
@@ -28,8 +66,20 @@ impl StreamTextBuilder {
             prompt: None,
             tools: Vec::new(),
             max_steps: 5,
-            temperature: None,
-            max_tokens: None,
+            stop_conditions: Vec::new(),
+            settings: CallSettings::default(),
+            retry_policy: RetryPolicy::default(),
+            callbacks: StreamCallbacks::default(),
+            on_step_finish: None,
+            abort_signal: None,
+            timeouts: Timeouts::default(),
+            tool_approval: None,
+            approvals: Vec::new(),
+            tool_context: None,
+            validate_tool_inputs: false,
+            repair_tool_call: None,
+            strict_tool_calls: false,
+            tool_execution_policy: ToolExecutionPolicy::default(),
         }
     }
 
@@ -66,15 +116,228 @@ impl StreamTextBuilder {
         self
     }
 
+    /// Add a condition that ends the tool loop before `max_steps`
+    ///
+    /// Conditions are checked after each step whose tools were executed; the
+    /// loop stops once any of them is met.
+    pub fn stop_when(mut self, condition: StopCondition) -> Self {
+        self.stop_conditions.push(condition);
+        self
+    }
+
     /// Set temperature
     pub fn temperature(mut self, temperature: f32) -> Self {
-        self.temperature = Some(temperature);
+        self.settings.temperature = Some(temperature);
         self
     }
 
     /// Set maximum tokens
     pub fn max_tokens(mut self, max_tokens: u32) -> Self {
-        self.max_tokens = Some(max_tokens);
+        self.settings.max_tokens = Some(max_tokens);
+        self
+    }
+
+    /// Set nucleus sampling probability (0.0 to 1.0)
+    pub fn top_p(mut self, top_p: f32) -> Self {
+        self.settings.top_p = Some(top_p);
+        self
+    }
+
+    /// Set top-k sampling
+    pub fn top_k(mut self, top_k: u32) -> Self {
+        self.settings.top_k = Some(top_k);
+        self
+    }
+
+    /// Set presence penalty (-2.0 to 2.0)
+    pub fn presence_penalty(mut self, presence_penalty: f32) -> Self {
+        self.settings.presence_penalty = Some(presence_penalty);
+        self
+    }
+
+    /// Set frequency penalty (-2.0 to 2.0)
+    pub fn frequency_penalty(mut self, frequency_penalty: f32) -> Self {
+        self.settings.frequency_penalty = Some(frequency_penalty);
+        self
+    }
+
+    /// Set sequences that stop generation
+    pub fn stop_sequences(mut self, stop_sequences: Vec<String>) -> Self {
+        self.settings.stop_sequences = Some(stop_sequences);
+        self
+    }
+
+    /// Set random seed for deterministic sampling
+    pub fn seed(mut self, seed: i64) -> Self {
+        self.settings.seed = Some(seed);
+        self
+    }
+
+    /// Set tool choice strategy (default: Auto when tools are given)
+    pub fn tool_choice(mut self, tool_choice: ToolChoice) -> Self {
+        self.settings.tool_choice = Some(tool_choice);
+        self
+    }
+
+    /// Set the response format
+    pub fn response_format(mut self, response_format: ResponseFormat) -> Self {
+        self.settings.response_format = Some(response_format);
+        self
+    }
+
+    /// Set additional HTTP headers sent with each request
+    pub fn headers(mut self, headers: HashMap<String, String>) -> Self {
+        self.settings.headers = Some(headers);
+        self
+    }
+
+    /// Add an HTTP header sent with each request
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.settings = self.settings.with_header(name, value);
+        self
+    }
+
+    /// Set provider-specific options, keyed by provider name
+    pub fn provider_options(mut self, provider_options: SharedProviderOptions) -> Self {
+        self.settings.provider_options = Some(provider_options);
+        self
+    }
+
+    /// Set one provider-specific option, e.g. `("openai", "reasoningEffort", "low")`
+    pub fn provider_option(
+        mut self,
+        provider: impl Into<String>,
+        key: impl Into<String>,
+        value: JsonValue,
+    ) -> Self {
+        self.settings = self.settings.with_provider_option(provider, key, value);
+        self
+    }
+
+    /// Replace all model call settings at once
+    pub fn call_settings(mut self, settings: CallSettings) -> Self {
+        self.settings = settings;
+        self
+    }
+
+    /// Set retry policy
+    ///
+    /// Each step's stream is retried if it fails before its first chunk.
+    /// Failures after that end the stream with
+    /// [`StreamTextError::Interrupted`].
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Set callback invoked for every content part as it arrives
+    ///
+    /// Step and finish parts are reported through
+    /// [`on_step_finish`](Self::on_step_finish) and
+    /// [`on_finish`](Self::on_finish) instead. The callbacks run whether or
+    /// not the stream is read.
+    pub fn on_chunk(mut self, callback: OnChunkCallback) -> Self {
+        self.callbacks.on_chunk = Some(callback);
+        self
+    }
+
+    /// Set callback invoked after each step, once its tool calls have run
+    pub fn on_step_finish(mut self, callback: OnStepFinishCallback) -> Self {
+        self.on_step_finish = Some(callback);
+        self
+    }
+
+    /// Set callback invoked with the aggregated result once generation succeeds
+    pub fn on_finish(mut self, callback: OnFinishCallback) -> Self {
+        self.callbacks.on_finish = Some(callback);
+        self
+    }
+
+    /// Set callback invoked with the error that ends the stream
+    pub fn on_error(mut self, callback: OnErrorCallback<StreamTextError>) -> Self {
+        self.callbacks.on_error = Some(callback);
+        self
+    }
+
+    /// Set a signal that cancels the generation
+    ///
+    /// Aborting closes the model stream and drops any running tools; the
+    /// stream then ends with [`StreamTextError::Aborted`].
+    pub fn abort_signal(mut self, abort_signal: AbortSignal) -> Self {
+        self.abort_signal = Some(abort_signal);
+        self
+    }
+
+    /// Set time limits for the run, each model call, each tool execution and
+    /// the gap between stream chunks
+    ///
+    /// A stage that runs over its limit ends the stream with
+    /// [`StreamTextError::Timeout`].
+    pub fn timeouts(mut self, timeouts: Timeouts) -> Self {
+        self.timeouts = timeouts;
+        self
+    }
+
+    /// Set the handler asked to approve calls of tools that need approval
+    ///
+    /// Without a handler, the generation stops at such calls, emits a
+    /// [`TextStreamPart::ToolApprovalRequest`] for each and reports them as
+    /// [`pending_approvals`](StreamTextResult::pending_approvals).
+    pub fn tool_approval(mut self, handler: ToolApprovalFn) -> Self {
+        self.tool_approval = Some(handler);
+        self
+    }
+
+    /// Set the decisions on the calls a previous run left pending
+    ///
+    /// To resume, pass the earlier prompt followed by its response messages
+    /// as [`messages`](Self::messages); the pending calls then run or are
+    /// denied before the next model call.
+    pub fn approvals(mut self, approvals: Vec<ToolApprovalResponse>) -> Self {
+        self.approvals = approvals;
+        self
+    }
+
+    /// Attach a value every tool can read through [`ToolContext::get`]
+    ///
+    /// [`ToolContext::get`]: crate::ToolContext::get
+    pub fn tool_context<T: std::any::Any + Send + Sync>(mut self, value: T) -> Self {
+        self.tool_context = Some(Arc::new(value));
+        self
+    }
+
+    /// Validate tool call arguments against each tool's input schema
+    ///
+    /// Arguments that do not match get an error result, or go through the
+    /// [`repair_tool_call`](Self::repair_tool_call) hook first.
+    pub fn validate_tool_inputs(mut self, validate: bool) -> Self {
+        self.validate_tool_inputs = validate;
+        self
+    }
+
+    /// Set the hook that fixes calls of unknown tools or with invalid
+    /// arguments before they fall back to an error result
+    pub fn repair_tool_call(mut self, repair: ToolCallRepairFn) -> Self {
+        self.repair_tool_call = Some(repair);
+        self
+    }
+
+    /// Fail with [`ToolError::NoSuchTool`] or
+    /// [`ToolError::InvalidToolArguments`] when a tool call stays invalid
+    /// after repair, instead of sending the model an error result
+    ///
+    /// [`ToolError::NoSuchTool`]: crate::ToolError::NoSuchTool
+    /// [`ToolError::InvalidToolArguments`]: crate::ToolError::InvalidToolArguments
+    pub fn strict_tool_calls(mut self, strict: bool) -> Self {
+        self.strict_tool_calls = strict;
+        self
+    }
+
+    /// Set how the tool calls of a step are scheduled and isolated
+    ///
+    /// See [`ToolExecutionPolicy`] for the defaults.
+    pub fn tool_execution_policy(mut self, policy: ToolExecutionPolicy) -> Self {
+        self.tool_execution_policy = policy;
         self
     }
 
@@ -83,21 +346,35 @@ impl StreamTextBuilder {
         let model = self.model.ok_or(StreamTextError::MissingModel)?;
         let messages = self.prompt.ok_or(StreamTextError::MissingPrompt)?;
 
-        let tool_executor = ToolExecutor::new(self.tools);
+        let tool_executor = ToolExecutor::new(self.tools)
+            .with_abort_signal(self.abort_signal.clone())
+            .with_user_context(self.tool_context)
+            .with_approval_handler(self.tool_approval)
+            .with_approvals(self.approvals)
+            .with_input_validation(self.validate_tool_inputs)
+            .with_tool_call_repair(self.repair_tool_call)
+            .with_strict_tool_calls(self.strict_tool_calls)
+            .with_execution_policy(self.tool_execution_policy)
+            .with_timeouts(self.timeouts);
 
-        // Create the stream
-        let stream_impl = create_multi_step_stream(
+        // Settings shared by every step, with the initial prompt
+        let call_options = CallOptions {
+            abort_signal: self.abort_signal,
+            ..self.settings.call_options(messages)
+        };
+
+        let step_loop = StepLoop {
             model,
-            messages,
             tool_executor,
-            self.max_steps,
-            self.temperature,
-            self.max_tokens,
-        );
-
-        Ok(StreamTextResult {
-            stream: Box::pin(stream_impl),
-        })
+            max_steps: self.max_steps,
+            stop_conditions: self.stop_conditions,
+            call_options,
+            retry_policy: self.retry_policy,
+            timeouts: self.timeouts,
+            prepare_step: None,
+            on_step_finish: self.on_step_finish,
+        };
+        Ok(step_loop.spawn(self.callbacks))
     }
 }
 
@@ -107,12 +384,110 @@ impl Default for StreamTextBuilder {
     }
 }
 
-/// Result containing the text stream
+/// Result of a streaming text generation
+///
+/// The generation runs in the background as soon as `execute` returns. Its
+/// parts can be read through [`stream_mut`](Self::stream_mut) or
+/// [`into_stream`](Self::into_stream), and any number of extra readers can be
+/// started with [`full_stream`](Self::full_stream) or
+/// [`text_stream`](Self::text_stream); each sees every part from the start.
+/// The aggregate getters wait for the generation to finish, whether or not
+/// the stream is read. Dropping the result and all of its streams cancels the
+/// generation.
+///
+/// While the result exists every part is kept in memory so that new readers
+/// can start from the first one; for long generations that is the whole
+/// stream. Once the result is dropped or turned into a stream with
+/// [`into_stream`](Self::into_stream), parts are released as soon as every
+/// remaining reader has read them.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let mut result = stream_text().model(model).prompt("Hi").execute().await?;
+///
+/// let mut text = result.text_stream();
+/// while let Some(delta) = text.next().await {
+///     print!("{}", delta?);
+/// }
+/// println!("\n{:?}", result.usage().await?);
+/// ```
 pub struct StreamTextResult {
     stream: Pin<Box<dyn Stream<Item = Result<TextStreamPart, StreamTextError>> + Send>>,
+    shared: Arc<SharedParts>,
+    task: Arc<AbortOnDrop>,
+    _replay: EndReplayOnDrop,
 }
 
 impl StreamTextResult {
+    /// Runs `inner` in a background task that records its parts and output
+    fn spawn(
+        inner: impl Stream<Item = Result<TextStreamPart, StreamTextError>> + Send + 'static,
+        output: oneshot::Receiver<StreamTextOutput>,
+        callbacks: StreamCallbacks,
+    ) -> Self {
+        let (changes_tx, changes_rx) = watch::channel(());
+        let shared = Arc::new(SharedParts {
+            buffer: Mutex::new(PartBuffer {
+                replay: true,
+                ..PartBuffer::default()
+            }),
+            output: OnceLock::new(),
+            changes: changes_rx,
+        });
+
+        let task = tokio::spawn({
+            let shared = shared.clone();
+            async move {
+                let mut inner = std::pin::pin!(inner);
+                let mut error = None;
+                while let Some(part) = inner.next().await {
+                    match &part {
+                        Ok(
+                            TextStreamPart::StepStart { .. }
+                            | TextStreamPart::StepFinish { .. }
+                            | TextStreamPart::Finish { .. },
+                        ) => {}
+                        Ok(chunk) => {
+                            if let Some(callback) = &callbacks.on_chunk {
+                                callback(chunk.clone()).await;
+                            }
+                        }
+                        Err(e) => {
+                            error.get_or_insert_with(|| e.clone());
+                        }
+                    }
+                    shared.buffer.lock().unwrap().parts.push_back(part);
+                    changes_tx.send_replace(());
+                }
+
+                let output = match (error, output.await) {
+                    (Some(error), _) => Err(error),
+                    (None, Ok(output)) => Ok(output),
+                    (None, Err(_)) => Err(StreamTextError::StreamError(
+                        "stream ended before finishing".into(),
+                    )),
+                };
+                match (&output, &callbacks.on_finish, &callbacks.on_error) {
+                    (Ok(output), Some(callback), _) => callback(output.finish_event()).await,
+                    (Err(error), _, Some(callback)) => callback(error).await,
+                    _ => {}
+                }
+                let _ = shared.output.set(output);
+                shared.buffer.lock().unwrap().done = true;
+                changes_tx.send_replace(());
+            }
+        });
+
+        let task = Arc::new(AbortOnDrop(task));
+        Self {
+            stream: subscribe(&shared, &task),
+            _replay: EndReplayOnDrop(shared.clone()),
+            shared,
+            task,
+        }
+    }
+
     /// Get a mutable reference to the stream
     pub fn stream_mut(
         &mut self,
@@ -126,42 +501,520 @@ impl StreamTextResult {
     ) -> Pin<Box<dyn Stream<Item = Result<TextStreamPart, StreamTextError>> + Send>> {
         self.stream
     }
+
+    /// A new stream of every part, starting from the first
+    pub fn full_stream(
+        &self,
+    ) -> Pin<Box<dyn Stream<Item = Result<TextStreamPart, StreamTextError>> + Send>> {
+        subscribe(&self.shared, &self.task)
+    }
+
+    /// A new stream of the text deltas only, starting from the first
+    pub fn text_stream(
+        &self,
+    ) -> Pin<Box<dyn Stream<Item = Result<String, StreamTextError>> + Send>> {
+        Box::pin(self.full_stream().filter_map(|part| match part {
+            Ok(TextStreamPart::TextDelta { text, .. }) => Some(Ok(text)),
+            Ok(_) => None,
+            Err(e) => Some(Err(e)),
+        }))
+    }
+
+    /// Waits for the generation to finish without reading the stream
+    pub async fn consume(&self) -> Result<(), StreamTextError> {
+        self.output().await.map(|_| ())
+    }
+
+    /// Text generated in the last step
+    pub async fn text(&self) -> Result<String, StreamTextError> {
+        let output = self.output().await?;
+        Ok(output
+            .steps
+            .last()
+            .map(StepResult::text)
+            .unwrap_or_default())
+    }
+
+    /// Reasoning generated in the last step
+    pub async fn reasoning_text(&self) -> Result<String, StreamTextError> {
+        let output = self.output().await?;
+        Ok(output
+            .steps
+            .last()
+            .and_then(StepResult::reasoning_text)
+            .unwrap_or_default())
+    }
+
+    /// All steps of the generation
+    pub async fn steps(&self) -> Result<Vec<StepResult>, StreamTextError> {
+        Ok(self.output().await?.steps.clone())
+    }
+
+    /// Total token usage across all steps
+    pub async fn usage(&self) -> Result<Usage, StreamTextError> {
+        Ok(self.output().await?.total_usage.clone())
+    }
+
+    /// Finish reason of the last step
+    pub async fn finish_reason(&self) -> Result<FinishReason, StreamTextError> {
+        let output = self.output().await?;
+        Ok(output
+            .steps
+            .last()
+            .map(|step| step.finish_reason)
+            .unwrap_or(FinishReason::Stop))
+    }
+
+    /// Warnings reported by the provider across all steps
+    pub async fn warnings(&self) -> Result<Vec<CallWarning>, StreamTextError> {
+        Ok(self.output().await?.warnings.clone())
+    }
+
+    /// Assistant and tool messages generated during the run, ready to be
+    /// appended to the conversation history
+    pub async fn response_messages(&self) -> Result<Vec<Message>, StreamTextError> {
+        Ok(self.output().await?.response_messages.clone())
+    }
+
+    /// Tool calls the generation stopped at to wait for approval
+    ///
+    /// Empty unless a tool needed approval and no approval handler was set.
+    pub async fn pending_approvals(&self) -> Result<Vec<ToolApprovalRequest>, StreamTextError> {
+        Ok(self.output().await?.pending_approvals.clone())
+    }
+
+    async fn output(&self) -> Result<&StreamTextOutput, StreamTextError> {
+        let mut changes = self.shared.changes.clone();
+        loop {
+            changes.borrow_and_update();
+            if let Some(output) = self.shared.output.get() {
+                return output.as_ref().map_err(Clone::clone);
+            }
+            if changes.changed().await.is_err() {
+                return match self.shared.output.get() {
+                    Some(output) => output.as_ref().map_err(Clone::clone),
+                    None => Err(StreamTextError::StreamError(
+                        "stream ended before finishing".into(),
+                    )),
+                };
+            }
+        }
+    }
+}
+
+/// Aggregates of a finished streaming generation
+struct StreamTextOutput {
+    steps: Vec<StepResult>,
+    total_usage: Usage,
+    warnings: Vec<CallWarning>,
+    response_messages: Vec<Message>,
+    pending_approvals: Vec<ToolApprovalRequest>,
+}
+
+impl StreamTextOutput {
+    fn finish_event(&self) -> FinishEvent {
+        let last_step = self.steps.last();
+        FinishEvent {
+            text: last_step.map(StepResult::text).unwrap_or_default(),
+            finish_reason: last_step
+                .map(|step| step.finish_reason)
+                .unwrap_or(FinishReason::Stop),
+            steps: self.steps.clone(),
+            total_usage: self.total_usage.clone(),
+            response_messages: self.response_messages.clone(),
+            pending_approvals: self.pending_approvals.clone(),
+        }
+    }
+}
+
+/// Callbacks run by the background task of a [`StreamTextResult`]
+#[derive(Default)]
+pub(crate) struct StreamCallbacks {
+    pub(crate) on_chunk: Option<OnChunkCallback>,
+    pub(crate) on_finish: Option<OnFinishCallback>,
+    pub(crate) on_error: Option<OnErrorCallback<StreamTextError>>,
+}
+
+/// Parts and output recorded by the background task, shared with readers
+struct SharedParts {
+    buffer: Mutex<PartBuffer>,
+    output: OnceLock<Result<StreamTextOutput, StreamTextError>>,
+    /// Notified whenever a part is recorded or the generation ends
+    changes: watch::Receiver<()>,
+}
+
+#[derive(Default)]
+struct PartBuffer {
+    parts: VecDeque<Result<TextStreamPart, StreamTextError>>,
+    /// Index of the first part still in `parts`
+    offset: usize,
+    /// Index of the next part of every live reader, by reader id
+    cursors: HashMap<u64, usize>,
+    next_reader: u64,
+    /// Whether new readers may still start from the first part
+    replay: bool,
+    done: bool,
+}
+
+impl PartBuffer {
+    fn get(&self, index: usize) -> Option<Result<TextStreamPart, StreamTextError>> {
+        let index = index.checked_sub(self.offset)?;
+        self.parts.get(index).cloned()
+    }
+
+    /// Releases the parts every reader has read, once no new reader can start
+    fn trim(&mut self) {
+        if self.replay {
+            return;
+        }
+        let end = self.offset + self.parts.len();
+        let keep_from = self.cursors.values().copied().min().unwrap_or(end);
+        while self.offset < keep_from && self.parts.pop_front().is_some() {
+            self.offset += 1;
+        }
+    }
+}
+
+/// Stops keeping parts for new readers once the result is gone
+struct EndReplayOnDrop(Arc<SharedParts>);
+
+impl Drop for EndReplayOnDrop {
+    fn drop(&mut self) {
+        let mut buffer = self.0.buffer.lock().unwrap();
+        buffer.replay = false;
+        buffer.trim();
+    }
+}
+
+/// Registers a reader's cursor for as long as the reader lives
+struct ReaderCursor {
+    shared: Arc<SharedParts>,
+    id: u64,
+}
+
+impl ReaderCursor {
+    fn new(shared: Arc<SharedParts>) -> Self {
+        let id = {
+            let mut buffer = shared.buffer.lock().unwrap();
+            let id = buffer.next_reader;
+            buffer.next_reader += 1;
+            buffer.cursors.insert(id, 0);
+            id
+        };
+        Self { shared, id }
+    }
+}
+
+impl Drop for ReaderCursor {
+    fn drop(&mut self) {
+        let mut buffer = self.shared.buffer.lock().unwrap();
+        buffer.cursors.remove(&self.id);
+        buffer.trim();
+    }
+}
+
+/// Cancels the background task once the result and its streams are dropped
+struct AbortOnDrop(JoinHandle<()>);
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+/// A reader of the recorded parts, starting from the first
+fn subscribe(
+    shared: &Arc<SharedParts>,
+    task: &Arc<AbortOnDrop>,
+) -> Pin<Box<dyn Stream<Item = Result<TextStreamPart, StreamTextError>> + Send>> {
+    let cursor = ReaderCursor::new(shared.clone());
+    let task = task.clone();
+    Box::pin(stream! {
+        let _task = task;
+        let shared = cursor.shared.clone();
+        let mut changes = shared.changes.clone();
+        let mut next = 0;
+        let mut closed = false;
+        loop {
+            changes.borrow_and_update();
+            let (part, done) = {
+                let mut buffer = shared.buffer.lock().unwrap();
+                let part = buffer.get(next);
+                if part.is_some() {
+                    buffer.cursors.insert(cursor.id, next + 1);
+                    buffer.trim();
+                }
+                (part, buffer.done)
+            };
+            match part {
+                Some(part) => {
+                    next += 1;
+                    yield part;
+                }
+                None if done || closed => return,
+                None => closed = changes.changed().await.is_err(),
+            }
+        }
+    })
 }
 
 /// Parts emitted by the text stream
+///
+/// Every part a provider streams is forwarded, framed by `StepStart` and
+/// `StepFinish` for each model call and a final `Finish` for the whole run.
 #[derive(Debug, Clone)]
 pub enum TextStreamPart {
+    /// A generation step has started
+    StepStart {
+        /// Index of the step that started
+        step_index: u32,
+        /// Warnings reported by the provider for this call
+        warnings: Vec<CallWarning>,
+    },
+    /// Start of a text block
+    TextStart {
+        /// Identifier of the text block
+        id: String,
+        /// Provider-specific metadata
+        provider_metadata: Option<SharedProviderMetadata>,
+    },
     /// A chunk of text from the model
-    TextDelta(String),
+    TextDelta {
+        /// Identifier of the text block
+        id: String,
+        /// The text chunk
+        text: String,
+        /// Provider-specific metadata
+        provider_metadata: Option<SharedProviderMetadata>,
+    },
+    /// End of a text block
+    TextEnd {
+        /// Identifier of the text block
+        id: String,
+        /// Provider-specific metadata
+        provider_metadata: Option<SharedProviderMetadata>,
+    },
+    /// Start of a reasoning block
+    ReasoningStart {
+        /// Identifier of the reasoning block
+        id: String,
+        /// Provider-specific metadata
+        provider_metadata: Option<SharedProviderMetadata>,
+    },
+    /// A chunk of reasoning from the model
+    ReasoningDelta {
+        /// Identifier of the reasoning block
+        id: String,
+        /// The reasoning chunk
+        text: String,
+        /// Provider-specific metadata
+        provider_metadata: Option<SharedProviderMetadata>,
+    },
+    /// End of a reasoning block
+    ReasoningEnd {
+        /// Identifier of the reasoning block
+        id: String,
+        /// Provider-specific metadata
+        provider_metadata: Option<SharedProviderMetadata>,
+    },
+    /// The model started writing the input of a tool call
+    ToolInputStart {
+        /// Identifier of the tool call
+        id: String,
+        /// Name of the tool being called
+        tool_name: String,
+        /// Whether the provider executes the tool
+        provider_executed: Option<bool>,
+        /// Whether the tool call is dynamic
+        dynamic: Option<bool>,
+        /// Provider-specific metadata
+        provider_metadata: Option<SharedProviderMetadata>,
+    },
+    /// A chunk of tool call input (partial JSON)
+    ToolInputDelta {
+        /// Identifier of the tool call
+        id: String,
+        /// The input chunk
+        delta: String,
+        /// Provider-specific metadata
+        provider_metadata: Option<SharedProviderMetadata>,
+    },
+    /// The model finished writing the input of a tool call
+    ToolInputEnd {
+        /// Identifier of the tool call
+        id: String,
+        /// Provider-specific metadata
+        provider_metadata: Option<SharedProviderMetadata>,
+    },
     /// A tool call made by the model
     ToolCall(ToolCallPart),
     /// The result of a tool execution
     ToolResult(ToolResultPart),
-    /// Indicates a generation step has finished
+    /// A tool call the generation stopped at to wait for approval
+    ToolApprovalRequest(ToolApprovalRequest),
+    /// A file generated by the model
+    File(FilePart),
+    /// A source cited by the model
+    Source(SourcePart),
+    /// Response metadata reported by the provider
+    ResponseMetadata(ResponseMetadata),
+    /// A raw provider chunk, sent when `include_raw_chunks` is set
+    Raw(serde_json::Value),
+    /// An error reported by the provider inside the stream
+    Error(String),
+    /// A generation step has finished
     StepFinish {
         /// Index of the step that finished
         step_index: u32,
         /// Reason why the step finished
         finish_reason: FinishReason,
+        /// Token usage for this step
+        usage: Usage,
+        /// Response metadata for this step
+        response: Option<ResponseMetadata>,
+        /// Provider-specific metadata
+        provider_metadata: Option<SharedProviderMetadata>,
     },
     /// Indicates the entire generation is complete
     Finish {
+        /// Reason why the last step finished
+        finish_reason: FinishReason,
         /// Total token usage across all steps
         total_usage: Usage,
     },
 }
 
+/// Content of one step, assembled from stream parts in arrival order
+#[derive(Default)]
+struct StepContent {
+    content: Vec<Content>,
+    /// Index in `content` of each text block still streaming
+    open_text: HashMap<String, usize>,
+    /// Index in `content` of each reasoning block still streaming
+    open_reasoning: HashMap<String, usize>,
+}
+
+impl StepContent {
+    fn push_text(&mut self, id: &str, delta: &str) {
+        let content = &mut self.content;
+        let index = *self.open_text.entry(id.to_string()).or_insert_with(|| {
+            content.push(Content::Text(TextPart {
+                text: String::new(),
+                provider_metadata: None,
+            }));
+            content.len() - 1
+        });
+        if let Content::Text(part) = &mut self.content[index] {
+            part.text.push_str(delta);
+        }
+    }
+
+    fn end_text(&mut self, id: &str, metadata: Option<SharedProviderMetadata>) {
+        if let Some(index) = self.open_text.remove(id) {
+            if let Content::Text(part) = &mut self.content[index] {
+                part.provider_metadata = metadata.or(part.provider_metadata.take());
+            }
+        }
+    }
+
+    fn push_reasoning(&mut self, id: &str, delta: &str) {
+        let content = &mut self.content;
+        let index = *self
+            .open_reasoning
+            .entry(id.to_string())
+            .or_insert_with(|| {
+                content.push(Content::Reasoning(ReasoningPart {
+                    reasoning: String::new(),
+                    provider_metadata: None,
+                }));
+                content.len() - 1
+            });
+        if let Content::Reasoning(part) = &mut self.content[index] {
+            part.reasoning.push_str(delta);
+        }
+    }
+
+    fn end_reasoning(&mut self, id: &str, metadata: Option<SharedProviderMetadata>) {
+        if let Some(index) = self.open_reasoning.remove(id) {
+            if let Content::Reasoning(part) = &mut self.content[index] {
+                part.provider_metadata = metadata.or(part.provider_metadata.take());
+            }
+        }
+    }
+
+    /// All text generated so far in this step
+    fn text(&self) -> String {
+        self.content
+            .iter()
+            .filter_map(|c| match c {
+                Content::Text(part) => Some(part.text.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The finished content, without empty text blocks
+    fn into_content(self) -> Vec<Content> {
+        self.content
+            .into_iter()
+            .filter(|c| !matches!(c, Content::Text(part) if part.text.is_empty()))
+            .collect()
+    }
+}
+
+/// Everything the multi-step loop of `stream_text` runs with
+///
+/// Agents build one directly to stream their tool loop.
+pub(crate) struct StepLoop {
+    pub(crate) model: Arc<dyn LanguageModel>,
+    pub(crate) tool_executor: ToolExecutor,
+    pub(crate) max_steps: u32,
+    pub(crate) stop_conditions: Vec<StopCondition>,
+    /// Settings for every call; the prompt is the initial conversation
+    pub(crate) call_options: CallOptions,
+    pub(crate) retry_policy: RetryPolicy,
+    pub(crate) timeouts: Timeouts,
+    pub(crate) prepare_step: Option<PrepareStepFn>,
+    pub(crate) on_step_finish: Option<OnStepFinishCallback>,
+}
+
+impl StepLoop {
+    /// Starts the loop in a background task and returns its result
+    pub(crate) fn spawn(self, callbacks: StreamCallbacks) -> StreamTextResult {
+        let timeouts = self.timeouts;
+        let (output_tx, output_rx) = oneshot::channel();
+        let stream_impl = create_multi_step_stream(self, output_tx);
+        StreamTextResult::spawn(timeouts.limit_stream(stream_impl), output_rx, callbacks)
+    }
+}
+
 /// Create a stream that handles multiple steps with tool calling
+///
+/// Once the last step finishes, the aggregates of the run are sent through
+/// `output`.
 fn create_multi_step_stream(
-    model: Arc<dyn LanguageModel>,
-    initial_messages: Vec<Message>,
-    tool_executor: ToolExecutor,
-    max_steps: u32,
-    temperature: Option<f32>,
-    max_tokens: Option<u32>,
+    step_loop: StepLoop,
+    output: oneshot::Sender<StreamTextOutput>,
 ) -> impl Stream<Item = Result<TextStreamPart, StreamTextError>> {
+    let StepLoop {
+        model,
+        tool_executor,
+        max_steps,
+        stop_conditions,
+        call_options,
+        retry_policy,
+        timeouts,
+        prepare_step,
+        on_step_finish,
+    } = step_loop;
+
     stream! {
-        let mut messages = initial_messages;
+        let abort_signal = call_options.abort_signal.clone();
+        let started = Instant::now();
+        let mut messages = call_options.prompt.clone();
+        let mut steps = Vec::new();
+        let mut warnings = Vec::new();
+        let mut response_messages = Vec::new();
         let mut total_usage = Usage {
             input_tokens: Some(0),
             output_tokens: Some(0),
@@ -169,14 +1022,46 @@ fn create_multi_step_stream(
             reasoning_tokens: None,
             cached_input_tokens: None,
         };
+        let mut last_finish_reason = FinishReason::Stop;
+
+        // Run the tool calls a previous run left waiting for approval
+        let unresolved = unresolved_tool_calls(&messages);
+        let mut pending_approvals = tool_executor.pending_approvals(&unresolved);
+        if !unresolved.is_empty() && pending_approvals.is_empty() {
+            let execution = tool_executor.execute_step_tools(unresolved, 0, &messages);
+            let tool_results = match abortable(abort_signal.as_ref(), execution).await {
+                Ok(Ok(results)) => results,
+                Ok(Err(e)) => {
+                    yield Err(e.into());
+                    return;
+                }
+                Err(e) => {
+                    yield Err(e.into());
+                    return;
+                }
+            };
+            for result in &tool_results {
+                yield Ok(TextStreamPart::ToolResult(result.clone()));
+            }
+            let tool_message = Message::Tool {
+                content: tool_results,
+            };
+            response_messages.push(tool_message.clone());
+            messages.push(tool_message);
+        }
+        for request in &pending_approvals {
+            yield Ok(TextStreamPart::ToolApprovalRequest(request.clone()));
+        }
 
         for step_index in 0..max_steps {
+            if !pending_approvals.is_empty() {
+                break;
+            }
+
             // Prepare options
             let mut options = CallOptions {
                 prompt: messages.clone(),
-                temperature,
-                max_output_tokens: max_tokens,
-                ..Default::default()
+                ..call_options.clone()
             };
 
             if !tool_executor.tools().is_empty() {
@@ -187,45 +1072,148 @@ fn create_multi_step_stream(
                         .map(ProviderTool::Function)
                         .collect(),
                 );
-                options.tool_choice = Some(ToolChoice::Auto);
+                options.tool_choice.get_or_insert(ToolChoice::Auto);
             }
 
-            // Call model streaming
-            let stream_response = match model.do_stream(options).await {
-                Ok(resp) => resp,
-                Err(e) => {
-                    yield Err(StreamTextError::ModelError(format!("{:?}", e)));
-                    return;
+            // Let the prepare_step hook override this step
+            let mut step_model = model.clone();
+            if let Some(prepare_step) = &prepare_step {
+                let context = PrepareStepContext {
+                    model: model.as_ref(),
+                    steps: &steps,
+                    step_number: step_index as usize,
+                    messages: &messages,
+                };
+                match prepare_step(context).await {
+                    Ok(step_override) => step_model = step_override.apply(&model, &mut options),
+                    Err(e) => {
+                        yield Err(e.into());
+                        return;
+                    }
                 }
-            };
+            }
+
+            // Call model streaming with retry
+            let stream_response =
+                match abortable(abort_signal.as_ref(), retry_policy.retry_stream(&step_model, &options, &timeouts)).await {
+                    Ok(Ok(resp)) => resp,
+                    Ok(Err(e)) => {
+                        yield Err(match e.downcast::<TimeoutError>() {
+                            Ok(timeout) => StreamTextError::Timeout(*timeout),
+                            Err(e) => StreamTextError::ModelError(Arc::from(e)),
+                        });
+                        return;
+                    }
+                    Err(e) => {
+                        yield Err(e.into());
+                        return;
+                    }
+                };
 
             let mut step_stream = stream_response.stream;
-            let mut step_content: Vec<Content> = Vec::new();
-            let mut text_accumulator = String::new();
-            let mut tool_calls: Vec<ToolCallPart> = Vec::new();
+            let mut step_content = StepContent::default();
+            let mut step_started = false;
+            let mut step_warnings = Vec::new();
             let mut finish_reason = None;
+            let mut step_usage = Usage::default();
+            let mut step_response = None;
+            let mut step_info = stream_response.response;
+            let mut step_provider_metadata = None;
 
             // Process stream
-            while let Some(part_result) = step_stream.next().await {
-                let part = match part_result {
-                    Ok(p) => p,
-                    Err(e) => {
-                        yield Err(StreamTextError::StreamError(format!("{:?}", e)));
+            loop {
+                let next = timeouts.limit(TimeoutStage::StreamIdle, step_stream.next());
+                let part = match abortable(abort_signal.as_ref(), next).await {
+                    Ok(Ok(Some(Ok(p)))) => p,
+                    Ok(Ok(None)) => break,
+                    Ok(Ok(Some(Err(StreamError::Aborted(e))))) | Err(e) => {
+                        yield Err(e.into());
+                        return;
+                    }
+                    Ok(Err(e)) => {
+                        yield Err(e.into());
+                        return;
+                    }
+                    Ok(Ok(Some(Err(e)))) => {
+                        yield Err(StreamInterruptedError::new(step_index, step_content.text(), &e).into());
                         return;
                     }
                 };
 
+                // Announce the step with the call warnings, or before the
+                // first part if the provider sends no stream start
+                if !step_started {
+                    step_started = true;
+                    if let StreamPart::StreamStart { warnings: call_warnings } = &part {
+                        step_warnings = call_warnings.clone();
+                    }
+                    warnings.extend(step_warnings.iter().cloned());
+                    yield Ok(TextStreamPart::StepStart { step_index, warnings: step_warnings.clone() });
+                }
+
                 match part {
-                    StreamPart::TextDelta { delta, .. } => {
-                        yield Ok(TextStreamPart::TextDelta(delta.clone()));
-                        text_accumulator.push_str(&delta);
+                    StreamPart::StreamStart { .. } => {}
+                    StreamPart::TextStart { id, provider_metadata } => {
+                        step_content.push_text(&id, "");
+                        yield Ok(TextStreamPart::TextStart { id, provider_metadata });
+                    }
+                    StreamPart::TextDelta { id, delta, provider_metadata } => {
+                        step_content.push_text(&id, &delta);
+                        yield Ok(TextStreamPart::TextDelta { id, text: delta, provider_metadata });
+                    }
+                    StreamPart::TextEnd { id, provider_metadata } => {
+                        step_content.end_text(&id, provider_metadata.clone());
+                        yield Ok(TextStreamPart::TextEnd { id, provider_metadata });
+                    }
+                    StreamPart::ReasoningStart { id, provider_metadata } => {
+                        step_content.push_reasoning(&id, "");
+                        yield Ok(TextStreamPart::ReasoningStart { id, provider_metadata });
+                    }
+                    StreamPart::ReasoningDelta { id, delta, provider_metadata } => {
+                        step_content.push_reasoning(&id, &delta);
+                        yield Ok(TextStreamPart::ReasoningDelta { id, text: delta, provider_metadata });
+                    }
+                    StreamPart::ReasoningEnd { id, provider_metadata } => {
+                        step_content.end_reasoning(&id, provider_metadata.clone());
+                        yield Ok(TextStreamPart::ReasoningEnd { id, provider_metadata });
+                    }
+                    StreamPart::ToolInputStart { id, tool_name, provider_metadata, provider_executed, dynamic, .. } => {
+                        yield Ok(TextStreamPart::ToolInputStart { id, tool_name, provider_executed, dynamic, provider_metadata });
+                    }
+                    StreamPart::ToolInputDelta { id, delta, provider_metadata } => {
+                        yield Ok(TextStreamPart::ToolInputDelta { id, delta, provider_metadata });
+                    }
+                    StreamPart::ToolInputEnd { id, provider_metadata } => {
+                        yield Ok(TextStreamPart::ToolInputEnd { id, provider_metadata });
                     }
                     StreamPart::ToolCall(tc) => {
-                        tool_calls.push(tc.clone());
-                        step_content.push(Content::ToolCall(tc.clone()));
+                        step_content.content.push(Content::ToolCall(tc.clone()));
                         yield Ok(TextStreamPart::ToolCall(tc));
                     }
-                    StreamPart::Finish { finish_reason: fr, usage, .. } => {
+                    StreamPart::ToolResult(tr) => {
+                        step_content.content.push(Content::ToolResult(tr.clone()));
+                        yield Ok(TextStreamPart::ToolResult(tr));
+                    }
+                    StreamPart::File(file) => {
+                        step_content.content.push(Content::File(file.clone()));
+                        yield Ok(TextStreamPart::File(file));
+                    }
+                    StreamPart::Source(source) => {
+                        step_content.content.push(Content::Source(source.clone()));
+                        yield Ok(TextStreamPart::Source(source));
+                    }
+                    StreamPart::ResponseMetadata { metadata } => {
+                        merge_response_metadata(&mut step_info, &metadata);
+                        step_response = Some(metadata.clone());
+                        yield Ok(TextStreamPart::ResponseMetadata(metadata));
+                    }
+                    StreamPart::Raw { raw_value } => {
+                        yield Ok(TextStreamPart::Raw(raw_value));
+                    }
+                    StreamPart::Error { error } => {
+                        yield Ok(TextStreamPart::Error(error));
+                    }
+                    StreamPart::Finish { finish_reason: fr, usage, provider_metadata } => {
                         finish_reason = Some(fr);
                         // Accumulate usage
                         if let (Some(total_input), Some(input)) =
@@ -243,61 +1231,138 @@ fn create_multi_step_stream(
                         {
                             total_usage.total_tokens = Some(total + step_total);
                         }
-                    }
-                    _ => {
-                        // Ignore other stream parts for now
+                        step_usage = usage;
+                        step_provider_metadata = provider_metadata;
                     }
                 }
             }
 
-            // Add accumulated text to content
-            if !text_accumulator.is_empty() {
-                step_content.push(Content::Text(TextPart {
-                    text: text_accumulator,
-                    provider_metadata: None,
-                }));
+            if !step_started {
+                yield Ok(TextStreamPart::StepStart { step_index, warnings: Vec::new() });
             }
 
             // Emit step finish
             let fr = finish_reason.unwrap_or(FinishReason::Stop);
+            last_finish_reason = fr;
+            let mut step = StepResult::new(step_index, GenerateResponse {
+                content: step_content.into_content(),
+                finish_reason: fr,
+                usage: step_usage.clone(),
+                provider_metadata: step_provider_metadata.clone(),
+                request: stream_response.request,
+                response: step_info,
+                warnings: step_warnings,
+            });
+            let repair = repair_step_tool_calls(&tool_executor, &mut step, &messages);
+            if let Err(e) = abortable(abort_signal.as_ref(), repair).await {
+                yield Err(e.into());
+                return;
+            }
+            let tool_calls = step.tool_calls.clone();
+            let assistant_message = assistant_message(step.content.clone());
+            steps.push(step);
             yield Ok(TextStreamPart::StepFinish {
                 step_index,
                 finish_reason: fr,
+                usage: step_usage,
+                response: step_response,
+                provider_metadata: step_provider_metadata,
             });
 
+            response_messages.push(assistant_message.clone());
+
             // Check if we should continue
             if tool_calls.is_empty() || fr != FinishReason::ToolCalls {
+                if let Some(callback) = &on_step_finish {
+                    callback(steps[steps.len() - 1].clone()).await;
+                }
+                break;
+            }
+
+            // Stop for calls that need a human decision
+            pending_approvals = tool_executor.pending_approvals(&tool_calls);
+            if !pending_approvals.is_empty() {
+                for request in &pending_approvals {
+                    yield Ok(TextStreamPart::ToolApprovalRequest(request.clone()));
+                }
+                if let Some(callback) = &on_step_finish {
+                    callback(steps[steps.len() - 1].clone()).await;
+                }
                 break;
             }
 
-            // Execute tools
-            let tool_results = tool_executor.execute_tools(tool_calls.clone()).await;
+            // Execute tools; they see the history with the assistant message
+            messages.push(assistant_message);
+            let execution = tool_executor.execute_step_tools(tool_calls, step_index, &messages);
+            let tool_results = match abortable(abort_signal.as_ref(), execution).await {
+                Ok(Ok(results)) => results,
+                Ok(Err(e)) => {
+                    yield Err(e.into());
+                    return;
+                }
+                Err(e) => {
+                    yield Err(e.into());
+                    return;
+                }
+            };
 
             // Emit tool results
             for result in &tool_results {
                 yield Ok(TextStreamPart::ToolResult(result.clone()));
             }
+            let step = steps.last_mut().expect("step was just pushed");
+            step.tool_results.extend(tool_results.iter().cloned());
+            if let Some(callback) = &on_step_finish {
+                callback(steps[steps.len() - 1].clone()).await;
+            }
 
             // Append messages
-            messages.push(Message::Assistant {
-                content: step_content
-                    .into_iter()
-                    .filter_map(|c| match c {
-                        Content::Text(tp) => Some(AssistantContentPart::Text(tp)),
-                        Content::ToolCall(tc) => Some(AssistantContentPart::ToolCall(tc)),
-                        Content::Reasoning(rp) => Some(AssistantContentPart::Reasoning(rp)),
-                        Content::File(fp) => Some(AssistantContentPart::File(fp)),
-                        Content::ToolResult(tr) => Some(AssistantContentPart::ToolResult(tr)),
-                        Content::Source(_) => None,
-                    })
-                    .collect(),
-            });
-            messages.push(Message::Tool {
+            let tool_message = Message::Tool {
                 content: tool_results,
-            });
+            };
+            response_messages.push(tool_message.clone());
+            messages.push(tool_message);
+
+            let context = StopConditionContext {
+                steps: &steps,
+                elapsed: started.elapsed(),
+            };
+            if is_stop_condition_met(&stop_conditions, &context).await {
+                break;
+            }
         }
 
-        yield Ok(TextStreamPart::Finish { total_usage });
+        let _ = output.send(StreamTextOutput {
+            steps,
+            total_usage: total_usage.clone(),
+            warnings,
+            response_messages,
+            pending_approvals,
+        });
+        yield Ok(TextStreamPart::Finish {
+            finish_reason: last_finish_reason,
+            total_usage,
+        });
+    }
+}
+
+/// Folds streamed response metadata into the response information of a step
+fn merge_response_metadata(info: &mut Option<ResponseInfo>, metadata: &ResponseMetadata) {
+    let info = info.get_or_insert_with(|| ResponseInfo {
+        headers: None,
+        body: None,
+        id: None,
+        timestamp: None,
+        model_id: None,
+    });
+    if metadata.id.is_some() {
+        info.id = metadata.id.clone();
+    }
+    if metadata.timestamp.is_some() {
+        info.timestamp = metadata.timestamp.clone();
+    }
+    if metadata.model_id.is_some() {
+        info.model_id = metadata.model_id.clone();
     }
 }
 
@@ -309,13 +1374,37 @@ pub fn stream_text() -> StreamTextBuilder {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_utils::{
+        text_stream_parts, tool_call_stream_parts, usage, DeleteTool, EchoTool, MockLanguageModel,
+        ScreenshotTool,
+    };
+    use ai_sdk_provider::language_model::AssistantContentPart;
+    use ai_sdk_provider::ApiCallError;
+    use std::time::Duration;
+
+    fn fast_retry() -> RetryPolicy {
+        RetryPolicy::default().with_initial_delay(Duration::from_millis(1))
+    }
+
+    async fn collect(result: StreamTextResult) -> Vec<Result<TextStreamPart, StreamTextError>> {
+        result.into_stream().collect().await
+    }
+
+    fn text_of(parts: &[Result<TextStreamPart, StreamTextError>]) -> String {
+        parts
+            .iter()
+            .filter_map(|part| match part {
+                Ok(TextStreamPart::TextDelta { text, .. }) => Some(text.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
 
     #[test]
     fn test_builder_defaults() {
         let builder = StreamTextBuilder::new();
         assert_eq!(builder.max_steps, 5);
-        assert!(builder.temperature.is_none());
-        assert!(builder.max_tokens.is_none());
+        assert_eq!(builder.settings, CallSettings::default());
     }
 
     #[test]
@@ -326,7 +1415,659 @@ mod tests {
             .max_tokens(1000);
 
         assert_eq!(builder.max_steps, 10);
-        assert_eq!(builder.temperature, Some(0.7));
-        assert_eq!(builder.max_tokens, Some(1000));
+        assert_eq!(builder.settings.temperature, Some(0.7));
+        assert_eq!(builder.settings.max_tokens, Some(1000));
+    }
+
+    #[tokio::test]
+    async fn test_stop_when_ends_loop_after_tool_step() {
+        let model = MockLanguageModel::new()
+            .with_stream(reasoning_tool_call_parts())
+            .with_stream(text_stream_parts(&["Unused"]));
+
+        let result = stream_text()
+            .model(model.clone())
+            .prompt("Hi")
+            .tools(vec![Arc::new(EchoTool)])
+            .stop_when(crate::stop_condition::has_tool_result("echo"))
+            .execute()
+            .await
+            .unwrap();
+
+        let steps = result.steps().await.unwrap();
+        assert_eq!(steps.len(), 1);
+        assert_eq!(steps[0].tool_results.len(), 1);
+        assert_eq!(model.calls().len(), 1);
+        assert_eq!(
+            result.finish_reason().await.unwrap(),
+            FinishReason::ToolCalls
+        );
+    }
+
+    #[tokio::test]
+    async fn test_call_settings_are_sent_to_model() {
+        let model = MockLanguageModel::new().with_stream(text_stream_parts(&["Hello"]));
+
+        let result = stream_text()
+            .model(model.clone())
+            .prompt("Hi")
+            .seed(7)
+            .top_p(0.9)
+            .stop_sequences(vec!["STOP".into()])
+            .provider_option("openai", "user", JsonValue::String("user-1".into()))
+            .execute()
+            .await
+            .unwrap();
+        collect(result).await;
+
+        let call = &model.calls()[0];
+        assert_eq!(call.seed, Some(7));
+        assert_eq!(call.top_p, Some(0.9));
+        assert_eq!(call.stop_sequences, Some(vec!["STOP".to_string()]));
+        assert_eq!(
+            call.provider_options.as_ref().unwrap()["openai"]["user"],
+            JsonValue::String("user-1".into())
+        );
+    }
+
+    /// Stream parts for a step that reasons, streams a call to `echo` and
+    /// finishes with `ToolCalls`
+    fn reasoning_tool_call_parts() -> Vec<Result<StreamPart, StreamError>> {
+        vec![
+            Ok(StreamPart::StreamStart {
+                warnings: vec![CallWarning {
+                    message: "topK is not supported".into(),
+                }],
+            }),
+            Ok(StreamPart::ResponseMetadata {
+                metadata: ResponseMetadata {
+                    id: Some("resp_1".into()),
+                    timestamp: None,
+                    model_id: Some("mock-model".into()),
+                },
+            }),
+            Ok(StreamPart::ReasoningStart {
+                id: "r0".into(),
+                provider_metadata: None,
+            }),
+            Ok(StreamPart::ReasoningDelta {
+                id: "r0".into(),
+                delta: "Need the tool".into(),
+                provider_metadata: None,
+            }),
+            Ok(StreamPart::ReasoningEnd {
+                id: "r0".into(),
+                provider_metadata: None,
+            }),
+            Ok(StreamPart::ToolInputStart {
+                id: "call_1".into(),
+                tool_name: "echo".into(),
+                provider_metadata: None,
+                provider_executed: None,
+                dynamic: None,
+                title: None,
+            }),
+            Ok(StreamPart::ToolInputDelta {
+                id: "call_1".into(),
+                delta: "{}".into(),
+                provider_metadata: None,
+            }),
+            Ok(StreamPart::ToolInputEnd {
+                id: "call_1".into(),
+                provider_metadata: None,
+            }),
+            Ok(StreamPart::ToolCall(ToolCallPart {
+                tool_call_id: "call_1".into(),
+                tool_name: "echo".into(),
+                input: "{}".into(),
+                provider_executed: None,
+                dynamic: None,
+                provider_metadata: None,
+            })),
+            Ok(StreamPart::Finish {
+                usage: usage(3, 4),
+                finish_reason: FinishReason::ToolCalls,
+                provider_metadata: None,
+            }),
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_forwards_every_part_with_step_framing() {
+        let model = MockLanguageModel::new()
+            .with_stream(reasoning_tool_call_parts())
+            .with_stream(text_stream_parts(&["Done"]));
+
+        let result = stream_text()
+            .model(model.clone())
+            .prompt("Hi")
+            .tools(vec![Arc::new(EchoTool)])
+            .execute()
+            .await
+            .unwrap();
+        let parts: Vec<TextStreamPart> = collect(result)
+            .await
+            .into_iter()
+            .map(|part| part.unwrap())
+            .collect();
+
+        let kinds: Vec<&str> = parts
+            .iter()
+            .map(|part| match part {
+                TextStreamPart::StepStart { .. } => "step-start",
+                TextStreamPart::TextStart { .. } => "text-start",
+                TextStreamPart::TextDelta { .. } => "text-delta",
+                TextStreamPart::TextEnd { .. } => "text-end",
+                TextStreamPart::ReasoningStart { .. } => "reasoning-start",
+                TextStreamPart::ReasoningDelta { .. } => "reasoning-delta",
+                TextStreamPart::ReasoningEnd { .. } => "reasoning-end",
+                TextStreamPart::ToolInputStart { .. } => "tool-input-start",
+                TextStreamPart::ToolInputDelta { .. } => "tool-input-delta",
+                TextStreamPart::ToolInputEnd { .. } => "tool-input-end",
+                TextStreamPart::ToolCall(_) => "tool-call",
+                TextStreamPart::ToolResult(_) => "tool-result",
+                TextStreamPart::ToolApprovalRequest(_) => "tool-approval-request",
+                TextStreamPart::File(_) => "file",
+                TextStreamPart::Source(_) => "source",
+                TextStreamPart::ResponseMetadata(_) => "response-metadata",
+                TextStreamPart::Raw(_) => "raw",
+                TextStreamPart::Error(_) => "error",
+                TextStreamPart::StepFinish { .. } => "step-finish",
+                TextStreamPart::Finish { .. } => "finish",
+            })
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                "step-start",
+                "response-metadata",
+                "reasoning-start",
+                "reasoning-delta",
+                "reasoning-end",
+                "tool-input-start",
+                "tool-input-delta",
+                "tool-input-end",
+                "tool-call",
+                "step-finish",
+                "tool-result",
+                "step-start",
+                "text-start",
+                "text-delta",
+                "text-end",
+                "step-finish",
+                "finish",
+            ]
+        );
+
+        assert!(matches!(
+            &parts[0],
+            TextStreamPart::StepStart { step_index: 0, warnings } if warnings.len() == 1
+        ));
+        match &parts[9] {
+            TextStreamPart::StepFinish {
+                step_index,
+                finish_reason,
+                usage,
+                response,
+                ..
+            } => {
+                assert_eq!(*step_index, 0);
+                assert_eq!(*finish_reason, FinishReason::ToolCalls);
+                assert_eq!(usage.output_tokens, Some(4));
+                assert_eq!(response.as_ref().unwrap().id.as_deref(), Some("resp_1"));
+            }
+            other => panic!("Expected StepFinish, got {:?}", other),
+        }
+        match parts.last() {
+            Some(TextStreamPart::Finish {
+                finish_reason,
+                total_usage,
+            }) => {
+                assert_eq!(*finish_reason, FinishReason::Stop);
+                assert_eq!(total_usage.output_tokens, Some(9));
+            }
+            other => panic!("Expected Finish, got {:?}", other),
+        }
+
+        // Reasoning is kept in the assistant message sent back to the model
+        let calls = model.calls();
+        match &calls[1].prompt[1] {
+            Message::Assistant { content } => {
+                assert!(matches!(
+                    &content[0],
+                    AssistantContentPart::Reasoning(part) if part.reasoning == "Need the tool"
+                ));
+                assert!(matches!(&content[1], AssistantContentPart::ToolCall(_)));
+            }
+            other => panic!("Expected assistant message, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_aggregates_while_reading_stream() {
+        let model = MockLanguageModel::new()
+            .with_stream(reasoning_tool_call_parts())
+            .with_stream(text_stream_parts(&["Hello", " world"]));
+
+        let mut result = stream_text()
+            .model(model)
+            .prompt("Hi")
+            .tools(vec![Arc::new(EchoTool)])
+            .execute()
+            .await
+            .unwrap();
+
+        // A second reader sees the same parts from the start
+        let text_deltas = result.text_stream();
+        let mut parts = Vec::new();
+        while let Some(part) = result.stream_mut().next().await {
+            parts.push(part.unwrap());
+        }
+        let deltas: Vec<String> = text_deltas.map(|delta| delta.unwrap()).collect().await;
+        assert_eq!(deltas, ["Hello", " world"]);
+        let replayed: Vec<_> = result.full_stream().collect().await;
+        assert_eq!(replayed.len(), parts.len());
+
+        assert_eq!(result.text().await.unwrap(), "Hello world");
+        assert_eq!(result.reasoning_text().await.unwrap(), "");
+        assert_eq!(result.finish_reason().await.unwrap(), FinishReason::Stop);
+        assert_eq!(result.usage().await.unwrap().output_tokens, Some(9));
+        assert_eq!(result.warnings().await.unwrap().len(), 1);
+
+        let steps = result.steps().await.unwrap();
+        assert_eq!(steps.len(), 2);
+        assert_eq!(steps[0].tool_calls.len(), 1);
+        assert!(matches!(
+            &steps[0].content[0],
+            Content::Reasoning(part) if part.reasoning == "Need the tool"
+        ));
+        assert_eq!(steps[0].tool_results.len(), 1);
+        assert_eq!(steps[0].warnings.len(), 1);
+        let response = steps[0].response.as_ref().unwrap();
+        assert_eq!(response.id.as_deref(), Some("resp_1"));
+        assert_eq!(response.model_id.as_deref(), Some("mock-model"));
+        assert!(steps[1].tool_results.is_empty());
+
+        let messages = result.response_messages().await.unwrap();
+        assert_eq!(messages.len(), 3);
+        assert!(matches!(messages[0], Message::Assistant { .. }));
+        assert!(matches!(messages[1], Message::Tool { .. }));
+        assert!(matches!(
+            &messages[2],
+            Message::Assistant { content } if matches!(
+                &content[..],
+                [AssistantContentPart::Text(part)] if part.text == "Hello world"
+            )
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_consume_without_reading_stream() {
+        let model = MockLanguageModel::new().with_stream(text_stream_parts(&["Hello"]));
+
+        let result = stream_text()
+            .model(model)
+            .prompt("Hi")
+            .execute()
+            .await
+            .unwrap();
+
+        result.consume().await.unwrap();
+        assert_eq!(result.text().await.unwrap(), "Hello");
+    }
+
+    #[tokio::test]
+    async fn test_releases_parts_once_read() {
+        let model = MockLanguageModel::new().with_stream(text_stream_parts(&["Hello", " world"]));
+
+        let result = stream_text()
+            .model(model)
+            .prompt("Hi")
+            .execute()
+            .await
+            .unwrap();
+        let shared = result.shared.clone();
+        let mut lagging = result.full_stream();
+        let mut stream = result.into_stream();
+
+        // Parts are kept until the slowest reader has read them
+        let mut count = 0;
+        while let Some(part) = stream.next().await {
+            part.unwrap();
+            count += 1;
+        }
+        assert_eq!(shared.buffer.lock().unwrap().parts.len(), count);
+
+        lagging.next().await.unwrap().unwrap();
+        assert_eq!(shared.buffer.lock().unwrap().parts.len(), count - 1);
+
+        drop(lagging);
+        assert!(shared.buffer.lock().unwrap().parts.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_aggregates_report_stream_error() {
+        let model = MockLanguageModel::new()
+            .with_stream_error(ApiCallError::new("bad request", "url").with_status_code(400));
+
+        let result = stream_text()
+            .model(model)
+            .prompt("Hi")
+            .execute()
+            .await
+            .unwrap();
+
+        let error = result.consume().await.unwrap_err();
+        assert!(matches!(&error, StreamTextError::ModelError(e) if e.to_string() == "bad request"));
+        assert_eq!(error.api_call_error().unwrap().status_code, Some(400));
+        assert!(matches!(
+            result.text().await,
+            Err(StreamTextError::ModelError(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_lifecycle_callbacks_run_without_reading_stream() {
+        let model = MockLanguageModel::new()
+            .with_stream(reasoning_tool_call_parts())
+            .with_stream(text_stream_parts(&["Done"]));
+        let events = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let record = |label: &'static str| {
+            let events = events.clone();
+            move |detail: String| events.lock().unwrap().push(format!("{label} {detail}"))
+        };
+
+        let on_chunk: OnChunkCallback = {
+            let record = record("chunk");
+            Arc::new(move |part| {
+                if let TextStreamPart::TextDelta { text, .. } = part {
+                    record(text);
+                }
+                Box::pin(async {})
+            })
+        };
+        let on_step_finish: OnStepFinishCallback = {
+            let record = record("step");
+            Arc::new(move |step| {
+                record(step.step_index.to_string());
+                Box::pin(async {})
+            })
+        };
+        let on_finish: OnFinishCallback = {
+            let record = record("finish");
+            Arc::new(move |event| {
+                record(event.text);
+                Box::pin(async {})
+            })
+        };
+
+        let result = stream_text()
+            .model(model)
+            .prompt("Hi")
+            .tools(vec![Arc::new(EchoTool)])
+            .on_chunk(on_chunk)
+            .on_step_finish(on_step_finish)
+            .on_finish(on_finish)
+            .execute()
+            .await
+            .unwrap();
+        result.consume().await.unwrap();
+
+        assert_eq!(
+            *events.lock().unwrap(),
+            ["step 0", "chunk Done", "step 1", "finish Done"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_on_error_callback() {
+        let model = MockLanguageModel::new()
+            .with_stream_error(ApiCallError::new("bad request", "url").with_status_code(400));
+        let errors = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let on_error: OnErrorCallback<StreamTextError> = {
+            let errors = errors.clone();
+            Arc::new(move |error| {
+                errors.lock().unwrap().push(error.to_string());
+                Box::pin(async {})
+            })
+        };
+
+        let result = stream_text()
+            .model(model)
+            .prompt("Hi")
+            .on_error(on_error)
+            .execute()
+            .await
+            .unwrap();
+        assert!(result.consume().await.is_err());
+
+        assert_eq!(*errors.lock().unwrap(), ["Model error: bad request"]);
+    }
+
+    #[tokio::test]
+    async fn test_retries_connection_failure_before_first_chunk() {
+        let model = MockLanguageModel::new()
+            .with_stream_error(ApiCallError::new("overloaded", "url").with_status_code(503))
+            .with_stream(vec![
+                Ok(StreamPart::StreamStart { warnings: vec![] }),
+                Err(StreamError::ApiCall(
+                    ApiCallError::new("connection reset", "url").with_retryable(true),
+                )),
+            ])
+            .with_stream(text_stream_parts(&["Hello", " world"]));
+
+        let result = stream_text()
+            .model(model.clone())
+            .prompt("Hi")
+            .retry_policy(fast_retry())
+            .execute()
+            .await
+            .unwrap();
+        let parts = collect(result).await;
+
+        assert_eq!(model.calls().len(), 3);
+        assert!(parts.iter().all(|part| part.is_ok()));
+        assert_eq!(text_of(&parts), "Hello world");
+    }
+
+    #[tokio::test]
+    async fn test_non_retryable_failure_is_not_retried() {
+        let model = MockLanguageModel::new()
+            .with_stream_error(ApiCallError::new("bad request", "url").with_status_code(400));
+
+        let result = stream_text()
+            .model(model.clone())
+            .prompt("Hi")
+            .retry_policy(fast_retry())
+            .execute()
+            .await
+            .unwrap();
+        let parts = collect(result).await;
+
+        assert_eq!(model.calls().len(), 1);
+        assert!(matches!(
+            parts.as_slice(),
+            [Err(StreamTextError::ModelError(e))] if e.to_string() == "bad request"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_mid_stream_disconnect_is_reported_with_partial_text() {
+        let mut parts = text_stream_parts(&["Hello", " wor"]);
+        parts.truncate(4); // StreamStart, TextStart, two deltas
+        parts.push(Err(StreamError::ApiCall(
+            ApiCallError::new("connection reset", "url").with_retryable(true),
+        )));
+        let model = MockLanguageModel::new().with_stream(parts);
+
+        let result = stream_text()
+            .model(model.clone())
+            .prompt("Hi")
+            .retry_policy(fast_retry())
+            .execute()
+            .await
+            .unwrap();
+        let parts = collect(result).await;
+
+        // Output was already delivered, so the call is not repeated
+        assert_eq!(model.calls().len(), 1);
+        assert_eq!(text_of(&parts), "Hello wor");
+        match parts.last() {
+            Some(Err(StreamTextError::Interrupted(error))) => {
+                assert_eq!(error.step_index, 0);
+                assert_eq!(error.partial_text, "Hello wor");
+                assert!(error.is_retryable);
+                assert!(error.message.contains("connection reset"));
+            }
+            other => panic!("Expected Interrupted error, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_abort_ends_stream_mid_step() {
+        let mut parts = text_stream_parts(&["Hello"]);
+        parts.truncate(3); // StreamStart, TextStart, one delta
+        let model = MockLanguageModel::new().with_stream(parts).hanging();
+        let controller = ai_sdk_provider::AbortController::new();
+
+        let mut stream = stream_text()
+            .model(model.clone())
+            .prompt("Hi")
+            .abort_signal(controller.signal())
+            .execute()
+            .await
+            .unwrap()
+            .into_stream();
+
+        assert!(matches!(
+            stream.next().await,
+            Some(Ok(TextStreamPart::StepStart { step_index: 0, .. }))
+        ));
+        assert!(matches!(
+            stream.next().await,
+            Some(Ok(TextStreamPart::TextStart { .. }))
+        ));
+        assert!(matches!(
+            stream.next().await,
+            Some(Ok(TextStreamPart::TextDelta { text, .. })) if text == "Hello"
+        ));
+        controller.abort();
+
+        assert!(matches!(
+            stream.next().await,
+            Some(Err(StreamTextError::Aborted(_)))
+        ));
+        assert!(stream.next().await.is_none());
+        assert!(model.calls()[0].abort_signal.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_stream_idle_timeout() {
+        let mut parts = text_stream_parts(&["Hello"]);
+        parts.truncate(3); // StreamStart, TextStart, one delta
+        let model = MockLanguageModel::new().with_stream(parts).hanging();
+
+        let result = stream_text()
+            .model(model)
+            .prompt("Hi")
+            .timeouts(Timeouts::new().with_stream_idle(Duration::from_millis(20)))
+            .execute()
+            .await
+            .unwrap();
+        let parts = collect(result).await;
+
+        assert_eq!(text_of(&parts), "Hello");
+        match parts.last() {
+            Some(Err(StreamTextError::Timeout(error))) => {
+                assert_eq!(error.stage, TimeoutStage::StreamIdle);
+                assert_eq!(error.to_string(), "stream idle time timed out after 20ms");
+            }
+            other => panic!("Expected stream idle timeout, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pauses_for_approval_and_resumes() {
+        let model = MockLanguageModel::new()
+            .with_stream(tool_call_stream_parts(
+                "delete",
+                serde_json::json!({ "id": 7 }),
+            ))
+            .with_stream(text_stream_parts(&["Deleted"]));
+
+        let paused = stream_text()
+            .model(model.clone())
+            .prompt("Delete resource 7")
+            .tools(vec![Arc::new(DeleteTool)])
+            .execute()
+            .await
+            .unwrap();
+        let parts: Vec<_> = paused.full_stream().collect().await;
+
+        let requests: Vec<_> = parts
+            .iter()
+            .filter_map(|part| match part {
+                Ok(TextStreamPart::ToolApprovalRequest(request)) => Some(request.clone()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].tool_call_id, "call_1");
+        assert_eq!(paused.pending_approvals().await.unwrap(), requests);
+        assert_eq!(model.calls().len(), 1);
+
+        let mut history = vec![Message::User {
+            content: vec![UserContentPart::Text {
+                text: "Delete resource 7".into(),
+            }],
+        }];
+        history.extend(paused.response_messages().await.unwrap());
+
+        let resumed = stream_text()
+            .model(model.clone())
+            .messages(history)
+            .tools(vec![Arc::new(DeleteTool)])
+            .approvals(vec![ToolApprovalResponse::approve("call_1")])
+            .execute()
+            .await
+            .unwrap();
+        let parts: Vec<_> = resumed.full_stream().collect().await;
+
+        assert!(matches!(parts[0], Ok(TextStreamPart::ToolResult(_))));
+        assert_eq!(text_of(&parts), "Deleted");
+        assert!(resumed.pending_approvals().await.unwrap().is_empty());
+        assert!(matches!(
+            resumed.response_messages().await.unwrap()[0],
+            Message::Tool { .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_content_tool_output_is_streamed() {
+        let model = MockLanguageModel::new()
+            .with_stream(tool_call_stream_parts("screenshot", serde_json::json!({})))
+            .with_stream(text_stream_parts(&["A login page"]));
+
+        let result = stream_text()
+            .model(model.clone())
+            .prompt("What is on screen?")
+            .tools(vec![Arc::new(ScreenshotTool)])
+            .execute()
+            .await
+            .unwrap();
+        let parts = collect(result).await;
+
+        let outputs: Vec<_> = parts
+            .iter()
+            .filter_map(|part| match part {
+                Ok(TextStreamPart::ToolResult(result)) => Some(result.output.clone()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(outputs, [ScreenshotTool::output()]);
+        assert!(matches!(
+            model.calls()[1].prompt.last(),
+            Some(Message::Tool { content }) if content[0].output == ScreenshotTool::output()
+        ));
     }
 }
@@ -0,0 +1,342 @@
+//! Scripted language model and tools shared by unit tests.
+
+use crate::error::ToolError;
+use crate::tool::{Tool, ToolContext, ToolOutput};
+use ai_sdk_provider::language_model::{
+    CallOptions, Content, ContentPart, FinishReason, GenerateResponse, LanguageModel, StreamError,
+    StreamPart, StreamResponse, TextPart, ToolCallPart, ToolResultOutput, Usage,
+};
+use ai_sdk_provider::{ApiCallError, JsonValue};
+use async_trait::async_trait;
+use futures::StreamExt;
+use serde_json::Value;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+type MockStream = Result<Vec<Result<StreamPart, StreamError>>, ApiCallError>;
+
+/// Language model that replays queued responses and records every call
+///
+/// Clones share the same queues, so a test can keep a handle to inspect
+/// [`MockLanguageModel::calls`] after passing the model to a builder.
+#[derive(Clone, Default)]
+pub(crate) struct MockLanguageModel {
+    generate_responses: Arc<Mutex<VecDeque<Result<GenerateResponse, ApiCallError>>>>,
+    stream_responses: Arc<Mutex<VecDeque<MockStream>>>,
+    calls: Arc<Mutex<Vec<CallOptions>>>,
+    hang: Arc<AtomicBool>,
+}
+
+impl MockLanguageModel {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queues a `do_generate` result
+    pub(crate) fn with_generate(self, response: Result<GenerateResponse, ApiCallError>) -> Self {
+        self.generate_responses.lock().unwrap().push_back(response);
+        self
+    }
+
+    /// Queues a successful `do_stream` call producing `parts`
+    pub(crate) fn with_stream(self, parts: Vec<Result<StreamPart, StreamError>>) -> Self {
+        self.stream_responses.lock().unwrap().push_back(Ok(parts));
+        self
+    }
+
+    /// Queues a `do_stream` call that fails before streaming
+    pub(crate) fn with_stream_error(self, error: ApiCallError) -> Self {
+        self.stream_responses.lock().unwrap().push_back(Err(error));
+        self
+    }
+
+    /// Makes calls hang once the queues are empty, and queued streams stall
+    /// after their last part instead of ending, to exercise cancellation
+    pub(crate) fn hanging(self) -> Self {
+        self.hang.store(true, Ordering::SeqCst);
+        self
+    }
+
+    /// Options of every call made so far, in order
+    pub(crate) fn calls(&self) -> Vec<CallOptions> {
+        self.calls.lock().unwrap().clone()
+    }
+}
+
+#[async_trait]
+impl LanguageModel for MockLanguageModel {
+    fn provider(&self) -> &str {
+        "mock"
+    }
+
+    fn model_id(&self) -> &str {
+        "mock-model"
+    }
+
+    async fn do_generate(
+        &self,
+        options: CallOptions,
+    ) -> Result<GenerateResponse, Box<dyn std::error::Error + Send + Sync>> {
+        self.calls.lock().unwrap().push(options);
+        let response = self.generate_responses.lock().unwrap().pop_front();
+        match response {
+            Some(response) => Ok(response?),
+            None if self.hang.load(Ordering::SeqCst) => std::future::pending().await,
+            None => panic!("no mock generate response queued"),
+        }
+    }
+
+    async fn do_stream(
+        &self,
+        options: CallOptions,
+    ) -> Result<StreamResponse, Box<dyn std::error::Error + Send + Sync + 'static>> {
+        self.calls.lock().unwrap().push(options);
+        let hang = self.hang.load(Ordering::SeqCst);
+        let queued = self.stream_responses.lock().unwrap().pop_front();
+        let parts = match queued {
+            Some(parts) => parts?,
+            None if hang => std::future::pending().await,
+            None => panic!("no mock stream response queued"),
+        };
+        let stream = tokio_stream::iter(parts);
+        Ok(StreamResponse {
+            stream: if hang {
+                Box::pin(stream.chain(futures::stream::pending()))
+            } else {
+                Box::pin(stream)
+            },
+            request: None,
+            response: None,
+        })
+    }
+}
+
+/// Tool named `echo` that returns its input unchanged
+pub(crate) struct EchoTool;
+
+#[async_trait]
+impl Tool for EchoTool {
+    fn name(&self) -> &str {
+        "echo"
+    }
+
+    fn description(&self) -> &str {
+        "Returns its input"
+    }
+
+    fn input_schema(&self) -> Value {
+        serde_json::json!({ "type": "object" })
+    }
+
+    async fn execute(&self, input: Value, _context: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let output = serde_json::from_value(input).expect("JSON values convert losslessly");
+        Ok(ToolOutput::Value(output))
+    }
+}
+
+/// Tool named `delete` that needs approval and returns `"deleted"`
+pub(crate) struct DeleteTool;
+
+#[async_trait]
+impl Tool for DeleteTool {
+    fn name(&self) -> &str {
+        "delete"
+    }
+
+    fn description(&self) -> &str {
+        "Deletes a resource"
+    }
+
+    fn input_schema(&self) -> Value {
+        serde_json::json!({ "type": "object" })
+    }
+
+    async fn execute(
+        &self,
+        _input: Value,
+        _context: &ToolContext,
+    ) -> Result<ToolOutput, ToolError> {
+        Ok(ToolOutput::Value(JsonValue::String("deleted".into())))
+    }
+
+    fn needs_approval(&self, _input: &Value) -> bool {
+        true
+    }
+}
+
+/// Tool named `screenshot` that returns a caption and a PNG as
+/// [`ToolResultOutput::Content`]
+pub(crate) struct ScreenshotTool;
+
+impl ScreenshotTool {
+    /// The output every call returns
+    pub(crate) fn output() -> ToolResultOutput {
+        ToolResultOutput::Content {
+            value: vec![
+                ContentPart::Text {
+                    text: "Screenshot".into(),
+                    provider_metadata: None,
+                },
+                ContentPart::ImageData {
+                    data: "iVBORw0KGgo=".into(),
+                    media_type: "image/png".into(),
+                    provider_metadata: None,
+                },
+            ],
+            provider_metadata: None,
+        }
+    }
+}
+
+#[async_trait]
+impl Tool for ScreenshotTool {
+    fn name(&self) -> &str {
+        "screenshot"
+    }
+
+    fn description(&self) -> &str {
+        "Takes a screenshot"
+    }
+
+    fn input_schema(&self) -> Value {
+        serde_json::json!({ "type": "object" })
+    }
+
+    async fn execute(
+        &self,
+        _input: Value,
+        _context: &ToolContext,
+    ) -> Result<ToolOutput, ToolError> {
+        Ok(ToolOutput::Value(JsonValue::Null))
+    }
+
+    fn to_model_output(&self, _output: JsonValue) -> ToolResultOutput {
+        Self::output()
+    }
+}
+
+/// User context type read by [`ContextTool`]
+pub(crate) struct RequestId(pub(crate) &'static str);
+
+/// Tool named `context` that reports what its [`ToolContext`] holds
+///
+/// Returns `"<request id>/<step number>/<message count>"`, with `-` when no
+/// [`RequestId`] is attached.
+pub(crate) struct ContextTool;
+
+#[async_trait]
+impl Tool for ContextTool {
+    fn name(&self) -> &str {
+        "context"
+    }
+
+    fn description(&self) -> &str {
+        "Reports its context"
+    }
+
+    fn input_schema(&self) -> Value {
+        serde_json::json!({ "type": "object" })
+    }
+
+    async fn execute(&self, _input: Value, context: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let request_id = context.get::<RequestId>().map_or("-", |id| id.0);
+        Ok(ToolOutput::Value(JsonValue::String(format!(
+            "{}/{}/{}",
+            request_id,
+            context.step_number,
+            context.messages.len()
+        ))))
+    }
+}
+
+/// Usage with the given input and output token counts
+pub(crate) fn usage(input_tokens: u32, output_tokens: u32) -> Usage {
+    Usage {
+        input_tokens: Some(input_tokens),
+        output_tokens: Some(output_tokens),
+        total_tokens: Some(input_tokens + output_tokens),
+        reasoning_tokens: None,
+        cached_input_tokens: None,
+    }
+}
+
+/// A `do_generate` response containing only `text`
+pub(crate) fn text_response(text: &str) -> GenerateResponse {
+    GenerateResponse {
+        content: vec![Content::Text(TextPart {
+            text: text.into(),
+            provider_metadata: None,
+        })],
+        finish_reason: FinishReason::Stop,
+        usage: usage(10, 5),
+        provider_metadata: None,
+        request: None,
+        response: None,
+        warnings: vec![],
+    }
+}
+
+/// A `do_generate` response calling `tool_name` once with `input`
+pub(crate) fn tool_call_response(tool_name: &str, input: Value) -> GenerateResponse {
+    GenerateResponse {
+        content: vec![Content::ToolCall(ToolCallPart {
+            tool_call_id: "call_1".into(),
+            tool_name: tool_name.into(),
+            input: input.to_string(),
+            provider_executed: None,
+            dynamic: None,
+            provider_metadata: None,
+        })],
+        finish_reason: FinishReason::ToolCalls,
+        ..text_response("")
+    }
+}
+
+/// Stream parts for a single text block made of `deltas`, ending with `Finish`
+pub(crate) fn text_stream_parts(deltas: &[&str]) -> Vec<Result<StreamPart, StreamError>> {
+    let mut parts = vec![
+        Ok(StreamPart::StreamStart { warnings: vec![] }),
+        Ok(StreamPart::TextStart {
+            id: "0".into(),
+            provider_metadata: None,
+        }),
+    ];
+    for delta in deltas {
+        parts.push(Ok(StreamPart::TextDelta {
+            id: "0".into(),
+            delta: delta.to_string(),
+            provider_metadata: None,
+        }));
+    }
+    parts.push(Ok(StreamPart::TextEnd {
+        id: "0".into(),
+        provider_metadata: None,
+    }));
+    parts.push(Ok(StreamPart::Finish {
+        usage: usage(10, 5),
+        finish_reason: FinishReason::Stop,
+        provider_metadata: None,
+    }));
+    parts
+}
+
+/// Stream parts calling `tool_name` once with `input`, ending with `Finish`
+pub(crate) fn tool_call_stream_parts(
+    tool_name: &str,
+    input: Value,
+) -> Vec<Result<StreamPart, StreamError>> {
+    let Content::ToolCall(tool_call) = tool_call_response(tool_name, input).content.remove(0)
+    else {
+        unreachable!("tool_call_response returns a tool call")
+    };
+    vec![
+        Ok(StreamPart::StreamStart { warnings: vec![] }),
+        Ok(StreamPart::ToolCall(tool_call)),
+        Ok(StreamPart::Finish {
+            usage: usage(10, 5),
+            finish_reason: FinishReason::ToolCalls,
+            provider_metadata: None,
+        }),
+    ]
+}
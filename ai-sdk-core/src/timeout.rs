@@ -0,0 +1,161 @@
+use crate::error::{TimeoutError, TimeoutStage};
+use async_stream::stream;
+use futures::Stream;
+use std::future::Future;
+use std::time::Duration;
+use tokio_stream::StreamExt;
+
+/// Wall-clock limits for the stages of a generation
+///
+/// Every limit is off by default. A stage that runs over its limit fails the
+/// generation with a [`TimeoutError`] naming the stage. Model calls and
+/// stream idle time that run over are retried like other transient failures
+/// when a [`RetryPolicy`](crate::RetryPolicy) allows it.
+///
+/// # Example
+///
+/// ```
+/// use ai_sdk_core::Timeouts;
+/// use std::time::Duration;
+///
+/// let timeouts = Timeouts::new()
+///     .with_total(Duration::from_secs(120))
+///     .with_model_call(Duration::from_secs(30))
+///     .with_tool_execution(Duration::from_secs(10))
+///     .with_stream_idle(Duration::from_secs(15));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Timeouts {
+    /// Limit for the whole multi-step run
+    pub total: Option<Duration>,
+    /// Limit for each model call, up to the first chunk for streams
+    pub model_call: Option<Duration>,
+    /// Limit for each tool execution
+    pub tool_execution: Option<Duration>,
+    /// Limit on the gap between two chunks of a model stream
+    pub stream_idle: Option<Duration>,
+}
+
+impl Timeouts {
+    /// Creates a configuration with no limits
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the limit for the whole multi-step run
+    pub fn with_total(mut self, timeout: Duration) -> Self {
+        self.total = Some(timeout);
+        self
+    }
+
+    /// Sets the limit for each model call
+    pub fn with_model_call(mut self, timeout: Duration) -> Self {
+        self.model_call = Some(timeout);
+        self
+    }
+
+    /// Sets the limit for each tool execution
+    pub fn with_tool_execution(mut self, timeout: Duration) -> Self {
+        self.tool_execution = Some(timeout);
+        self
+    }
+
+    /// Sets the limit on the gap between two stream chunks
+    pub fn with_stream_idle(mut self, timeout: Duration) -> Self {
+        self.stream_idle = Some(timeout);
+        self
+    }
+
+    /// Returns the limit configured for `stage`
+    pub fn get(&self, stage: TimeoutStage) -> Option<Duration> {
+        match stage {
+            TimeoutStage::Total => self.total,
+            TimeoutStage::ModelCall => self.model_call,
+            TimeoutStage::ToolExecution => self.tool_execution,
+            TimeoutStage::StreamIdle => self.stream_idle,
+        }
+    }
+
+    /// Runs `future` within the limit for `stage`
+    pub(crate) async fn limit<F: Future>(
+        &self,
+        stage: TimeoutStage,
+        future: F,
+    ) -> Result<F::Output, TimeoutError> {
+        match self.get(stage) {
+            Some(timeout) => tokio::time::timeout(timeout, future)
+                .await
+                .map_err(|_| TimeoutError::new(stage, timeout)),
+            None => Ok(future.await),
+        }
+    }
+
+    /// Ends `stream` with a total timeout error once the total limit passes
+    ///
+    /// The clock starts when the stream is first polled.
+    pub(crate) fn limit_stream<S, T, E>(&self, inner: S) -> impl Stream<Item = Result<T, E>>
+    where
+        S: Stream<Item = Result<T, E>>,
+        E: From<TimeoutError>,
+    {
+        let total = self.total;
+        stream! {
+            let mut inner = std::pin::pin!(inner);
+            let Some(total) = total else {
+                while let Some(item) = inner.next().await {
+                    yield item;
+                }
+                return;
+            };
+
+            let deadline = tokio::time::sleep(total);
+            let mut deadline = std::pin::pin!(deadline);
+            loop {
+                tokio::select! {
+                    _ = &mut deadline => {
+                        yield Err(TimeoutError::new(TimeoutStage::Total, total).into());
+                        return;
+                    }
+                    item = inner.next() => match item {
+                        Some(item) => yield item,
+                        None => return,
+                    },
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_limit_reports_stage() {
+        let timeouts = Timeouts::new().with_tool_execution(Duration::from_millis(10));
+
+        let error = timeouts
+            .limit(TimeoutStage::ToolExecution, std::future::pending::<()>())
+            .await
+            .unwrap_err();
+        assert_eq!(error.stage, TimeoutStage::ToolExecution);
+        assert_eq!(error.timeout, Duration::from_millis(10));
+        assert_eq!(error.to_string(), "tool execution timed out after 10ms");
+
+        // Stages without a limit run to completion
+        let output = timeouts.limit(TimeoutStage::ModelCall, async { 1 }).await;
+        assert_eq!(output.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_limit_stream_ends_with_total_timeout() {
+        let timeouts = Timeouts::new().with_total(Duration::from_millis(20));
+        let inner = tokio_stream::iter([Ok(1)]).chain(futures::stream::pending());
+
+        let items: Vec<Result<i32, TimeoutError>> = timeouts.limit_stream(inner).collect().await;
+
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].as_ref().unwrap(), &1);
+        assert_eq!(items[1].as_ref().unwrap_err().stage, TimeoutStage::Total);
+    }
+}
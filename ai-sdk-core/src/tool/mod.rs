@@ -1,22 +1,57 @@
+mod tool_approval;
+mod tool_call_repair;
+mod tool_execution_policy;
 mod tool_output;
+mod typed_tool;
 
-pub use tool_output::ToolOutput;
+pub(crate) use tool_approval::unresolved_tool_calls;
+pub use tool_approval::{ToolApprovalFn, ToolApprovalRequest, ToolApprovalResponse};
+pub use tool_call_repair::{repair_tool_call_with_model, ToolCallRepairFn, ToolCallRepairRequest};
+pub use tool_execution_policy::ToolExecutionPolicy;
+pub use tool_output::{ErrorMode, ToolOutput};
+pub use typed_tool::{input_schema_for, TypedTool};
 
-use crate::error::ToolError;
+use crate::error::{TimeoutStage, ToolError};
+use crate::timeout::Timeouts;
 use ai_sdk_provider::language_model::{
     FunctionTool, Message, ToolCallPart, ToolResultOutput, ToolResultPart,
 };
-use ai_sdk_provider::JsonValue;
+use ai_sdk_provider::{AbortSignal, JsonValue};
 use async_trait::async_trait;
+use futures::future::BoxFuture;
 use serde_json::Value;
+use std::any::Any;
 use std::sync::Arc;
+use std::time::Duration;
+use tool_output::create_tool_output;
+
+/// Caller-supplied value passed to every tool of a generation
+pub type UserContext = Arc<dyn Any + Send + Sync>;
 
 /// Context provided to tools during execution
 pub struct ToolContext {
     /// ID of the tool call being executed
     pub tool_call_id: String,
-    /// Conversation messages leading up to this tool call
+    /// Conversation messages up to and including the assistant message that
+    /// made this tool call
     pub messages: Vec<Message>,
+    /// Index of the step whose model output made this tool call
+    pub step_number: u32,
+    /// Signal aborted when the surrounding generation is cancelled
+    ///
+    /// Tools that spawn work outside their own future should stop it when
+    /// this fires; the `execute` future itself is dropped on abort.
+    pub abort_signal: Option<AbortSignal>,
+    /// Value the caller attached to the generation, such as a database pool
+    /// or the authenticated user; read it with [`get`](Self::get)
+    pub user_context: Option<UserContext>,
+}
+
+impl ToolContext {
+    /// The user context, if one of type `T` was attached
+    pub fn get<T: Any + Send + Sync>(&self) -> Option<&T> {
+        self.user_context.as_deref()?.downcast_ref()
+    }
 }
 
 /// Trait that tools must implement
@@ -36,36 +71,185 @@ pub trait Tool: Send + Sync {
     async fn execute(&self, input: Value, context: &ToolContext) -> Result<ToolOutput, ToolError>;
 
     /// Check if this tool requires approval before execution
+    ///
+    /// Calls that need approval run only once approved, either by the
+    /// approval handler of the generation or by a decision passed when
+    /// resuming it.
     fn needs_approval(&self, _input: &Value) -> bool {
         false
     }
 
+    /// Whether calls of this tool may overlap with other calls
+    ///
+    /// Calls of tools that are not parallel safe run alone; see
+    /// [`ToolExecutionPolicy`].
+    fn parallel_safe(&self) -> bool {
+        true
+    }
+
+    /// Limit for each call of this tool, overriding
+    /// [`ToolExecutionPolicy::tool_timeout`]
+    fn timeout(&self) -> Option<Duration> {
+        None
+    }
+
+    /// How errors returned by `execute` reach the model
+    ///
+    /// Defaults to [`ErrorMode::Text`]; [`ErrorMode::Propagate`] fails the
+    /// generation with the error instead.
+    fn error_mode(&self) -> ErrorMode {
+        ErrorMode::Text
+    }
+
     /// Custom output formatting (optional)
     /// If not implemented, uses default conversion (string → text, object → json)
+    ///
+    /// Return [`ToolResultOutput::Content`] to send the model images or
+    /// files.
     fn to_model_output(&self, output: JsonValue) -> ToolResultOutput {
-        // Default implementation
-        match output {
-            JsonValue::String(s) => ToolResultOutput::Text {
-                value: s,
-                provider_metadata: None,
-            },
-            other => ToolResultOutput::Json {
-                value: other,
-                provider_metadata: None,
-            },
-        }
+        create_tool_output(output, ErrorMode::None, None)
     }
 }
 
 /// Executor that manages tool execution
 pub struct ToolExecutor {
     tools: Vec<Arc<dyn Tool>>,
+    abort_signal: Option<AbortSignal>,
+    user_context: Option<UserContext>,
+    approval_handler: Option<ToolApprovalFn>,
+    approvals: Vec<ToolApprovalResponse>,
+    validate_inputs: bool,
+    repair_tool_call: Option<ToolCallRepairFn>,
+    strict_tool_calls: bool,
+    policy: ToolExecutionPolicy,
+    timeouts: Timeouts,
 }
 
 impl ToolExecutor {
     /// Creates a new ToolExecutor with the given tools
     pub fn new(tools: Vec<Arc<dyn Tool>>) -> Self {
-        Self { tools }
+        Self {
+            tools,
+            abort_signal: None,
+            user_context: None,
+            approval_handler: None,
+            approvals: Vec::new(),
+            validate_inputs: false,
+            repair_tool_call: None,
+            strict_tool_calls: false,
+            policy: ToolExecutionPolicy::default(),
+            timeouts: Timeouts::default(),
+        }
+    }
+
+    /// Set the abort signal passed to tools in their [`ToolContext`]
+    pub fn with_abort_signal(mut self, abort_signal: Option<AbortSignal>) -> Self {
+        self.abort_signal = abort_signal;
+        self
+    }
+
+    /// Set the user context passed to tools in their [`ToolContext`]
+    pub fn with_user_context(mut self, user_context: Option<UserContext>) -> Self {
+        self.user_context = user_context;
+        self
+    }
+
+    /// Set the handler asked to approve calls of tools that need approval
+    pub fn with_approval_handler(mut self, approval_handler: Option<ToolApprovalFn>) -> Self {
+        self.approval_handler = approval_handler;
+        self
+    }
+
+    /// Set decisions already made on calls that need approval
+    ///
+    /// Calls with a decision are not passed to the approval handler.
+    pub fn with_approvals(mut self, approvals: Vec<ToolApprovalResponse>) -> Self {
+        self.approvals = approvals;
+        self
+    }
+
+    /// Validate call arguments against the tool's
+    /// [`input_schema`](Tool::input_schema) before it runs
+    ///
+    /// Without validation, arguments only need to be valid JSON.
+    pub fn with_input_validation(mut self, validate_inputs: bool) -> Self {
+        self.validate_inputs = validate_inputs;
+        self
+    }
+
+    /// Set the hook that fixes calls of unknown tools or with invalid
+    /// arguments
+    pub fn with_tool_call_repair(mut self, repair_tool_call: Option<ToolCallRepairFn>) -> Self {
+        self.repair_tool_call = repair_tool_call;
+        self
+    }
+
+    /// Fail on calls that stay invalid after repair
+    ///
+    /// By default such calls get an error result the model can react to;
+    /// when strict, execution fails with [`ToolError::NoSuchTool`] or
+    /// [`ToolError::InvalidToolArguments`].
+    pub fn with_strict_tool_calls(mut self, strict_tool_calls: bool) -> Self {
+        self.strict_tool_calls = strict_tool_calls;
+        self
+    }
+
+    /// Set how the calls of a step are scheduled and isolated
+    pub fn with_execution_policy(mut self, policy: ToolExecutionPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Set the limits of the generation the calls belong to
+    ///
+    /// The [tool execution limit](Timeouts::with_tool_execution) applies to
+    /// each call; a call that runs over it fails with
+    /// [`ToolError::Timeout`].
+    pub fn with_timeouts(mut self, timeouts: Timeouts) -> Self {
+        self.timeouts = timeouts;
+        self
+    }
+
+    /// Approval requests to answer before these calls can run
+    ///
+    /// Empty when an approval handler is set, since the handler is asked
+    /// during execution. Calls without a decision that are executed anyway
+    /// are denied.
+    pub fn pending_approvals(&self, tool_calls: &[ToolCallPart]) -> Vec<ToolApprovalRequest> {
+        if self.approval_handler.is_some() {
+            return Vec::new();
+        }
+        tool_calls
+            .iter()
+            .filter_map(|tool_call| self.approval_request(tool_call))
+            .filter(|request| self.decision(&request.tool_call_id).is_none())
+            .collect()
+    }
+
+    /// Repairs the calls that cannot run as made
+    ///
+    /// Used before deciding on approval, so the approved call is the one
+    /// that runs. Calls that cannot be repaired, and calls executed by the
+    /// provider, are returned as made. `messages` is the conversation up to
+    /// and including the assistant message that made the calls.
+    pub async fn repair_tool_calls(
+        &self,
+        tool_calls: Vec<ToolCallPart>,
+        messages: &[Message],
+    ) -> Vec<ToolCallPart> {
+        if self.repair_tool_call.is_none() {
+            return tool_calls;
+        }
+        futures::future::join_all(tool_calls.into_iter().map(|tool_call| async move {
+            if tool_call.provider_executed == Some(true) {
+                return tool_call;
+            }
+            match self.resolve(tool_call.clone(), messages).await {
+                Ok((_, repaired, _)) => repaired,
+                Err(_) => tool_call,
+            }
+        }))
+        .await
     }
 
     /// Get tool definitions for the model
@@ -81,137 +265,65 @@ impl ToolExecutor {
             .collect()
     }
 
-    /// Execute multiple tool calls in parallel
-    pub async fn execute_tools(&self, tool_calls: Vec<ToolCallPart>) -> Vec<ToolResultPart> {
-        let mut futures = Vec::new();
-
-        for tool_call in tool_calls {
-            let tool_opt = self.find_tool(&tool_call.tool_name);
-            let tool_call_id = tool_call.tool_call_id.clone();
-            let tool_name = tool_call.tool_name.clone();
-            let input_str = tool_call.input.clone();
-
-            let future = async move {
-                // Handle tool not found
-                let tool = match tool_opt {
-                    Some(t) => t,
-                    None => {
-                        return ToolResultPart {
-                            tool_call_id,
-                            tool_name: tool_name.clone(),
-                            output: ToolResultOutput::ErrorText {
-                                value: format!("Tool '{}' not found", tool_name),
-                                provider_metadata: None,
-                            },
-                            preliminary: None,
-                            provider_metadata: None,
-                        };
-                    }
-                };
-
-                let context = ToolContext {
-                    tool_call_id: tool_call_id.clone(),
-                    messages: vec![], // TODO: pass actual messages
-                };
-
-                // Parse input
-                let input: Value = match serde_json::from_str(&input_str) {
-                    Ok(v) => v,
-                    Err(e) => {
-                        // Return error result instead of propagating
-                        return ToolResultPart {
-                            tool_call_id,
-                            tool_name,
-                            output: ToolResultOutput::ErrorText {
-                                value: format!("Invalid input: {}", e),
-                                provider_metadata: None,
-                            },
-                            preliminary: None,
-                            provider_metadata: None,
-                        };
-                    }
-                };
-
-                // Check approval
-                if tool.needs_approval(&input) {
-                    // Return denial result
-                    return ToolResultPart {
-                        tool_call_id,
-                        tool_name,
-                        output: ToolResultOutput::ExecutionDenied {
-                            reason: Some("Execution denied by user".to_string()),
-                            provider_metadata: None,
-                        },
-                        preliminary: None,
-                        provider_metadata: None,
-                    };
-                }
+    /// Execute multiple tool calls, outside of a generation
+    ///
+    /// Tools see an empty message history; see
+    /// [`execute_step_tools`](Self::execute_step_tools).
+    pub async fn execute_tools(
+        &self,
+        tool_calls: Vec<ToolCallPart>,
+    ) -> Result<Vec<ToolResultPart>, ToolError> {
+        self.execute_step_tools(tool_calls, 0, &[]).await
+    }
 
-                // Execute tool and convert to structured output
-                let output = match tool.execute(input, &context).await {
-                    Ok(tool_output) => {
-                        // Handle both Value and Stream outputs
-                        match tool_output {
-                            ToolOutput::Value(raw_output) => {
-                                // Success - convert to structured output
-                                tool.to_model_output(raw_output)
-                            }
-                            ToolOutput::Stream(mut stream) => {
-                                // For non-streaming execute_tools, just get the last value
-                                use futures::stream::StreamExt;
-                                let mut last_output = None;
-                                while let Some(item) = stream.next().await {
-                                    match item {
-                                        Ok(output) => last_output = Some(output),
-                                        Err(e) => {
-                                            return ToolResultPart {
-                                                tool_call_id,
-                                                tool_name,
-                                                output: ToolResultOutput::ErrorText {
-                                                    value: e.to_string(),
-                                                    provider_metadata: None,
-                                                },
-                                                preliminary: None,
-                                                provider_metadata: None,
-                                            };
-                                        }
-                                    }
-                                }
-                                // Convert final output
-                                let final_value = last_output.unwrap_or(JsonValue::Null);
-                                tool.to_model_output(final_value)
-                            }
-                        }
-                    }
-                    Err(error) => {
-                        // Execution error - return error output
-                        ToolResultOutput::ErrorText {
-                            value: error.to_string(),
-                            provider_metadata: None,
-                        }
-                    }
-                };
-
-                ToolResultPart {
-                    tool_call_id,
-                    tool_name,
-                    output,
-                    preliminary: None,
-                    provider_metadata: None,
-                }
-            };
+    /// Execute the tool calls of a generation step
+    ///
+    /// Calls run concurrently as the [`ToolExecutionPolicy`] allows, and
+    /// results come back in the order of `tool_calls`. `messages` is the
+    /// conversation up to and including the assistant message that made the
+    /// calls; tools get it in their [`ToolContext`].
+    ///
+    /// # Errors
+    ///
+    /// Fails only with [strict tool calls](Self::with_strict_tool_calls);
+    /// otherwise every failure becomes an error result.
+    pub async fn execute_step_tools(
+        &self,
+        tool_calls: Vec<ToolCallPart>,
+        step_number: u32,
+        messages: &[Message],
+    ) -> Result<Vec<ToolResultPart>, ToolError> {
+        use futures::stream::{StreamExt, TryStreamExt};
 
-            futures.push(future);
+        let concurrency = self.policy.concurrency();
+        let mut results = Vec::with_capacity(tool_calls.len());
+        let mut tool_calls = tool_calls.into_iter().peekable();
+        while tool_calls.peek().is_some() {
+            // Run the next call alone, or the run of parallel-safe calls
+            // that starts with it
+            let mut group = vec![tool_calls.next().expect("peeked")];
+            if self.parallel_safe(&group[0]) {
+                while let Some(tool_call) = tool_calls.next_if(|call| self.parallel_safe(call)) {
+                    group.push(tool_call);
+                }
+            }
+            let group_results: Vec<_> = futures::stream::iter(group)
+                .map(|tool_call| self.run_tool_call(tool_call, step_number, messages, |_| {}))
+                .buffered(concurrency)
+                .try_collect()
+                .await?;
+            results.extend(group_results);
         }
-
-        // Execute all tools in parallel
-        futures::future::join_all(futures).await
+        Ok(results)
     }
 
     /// Execute a single tool and emit preliminary results via callback
     ///
     /// # Arguments
     /// * `tool_call` - The tool call to execute
+    /// * `step_number` - Index of the step that made the call
+    /// * `messages` - Conversation up to and including the assistant message
+    ///   that made the call
     /// * `on_preliminary` - Callback invoked for each preliminary result
     ///
     /// # Returns
@@ -219,142 +331,237 @@ impl ToolExecutor {
     pub async fn execute_tool_with_stream<F>(
         &self,
         tool_call: ToolCallPart,
+        step_number: u32,
+        messages: &[Message],
+        on_preliminary: F,
+    ) -> Result<ToolResultPart, ToolError>
+    where
+        F: Fn(ToolResultPart) + Send,
+    {
+        self.run_tool_call(tool_call, step_number, messages, on_preliminary)
+            .await
+    }
+
+    /// Checks, approves and executes one call
+    async fn run_tool_call<F>(
+        &self,
+        tool_call: ToolCallPart,
+        step_number: u32,
+        messages: &[Message],
         on_preliminary: F,
-    ) -> ToolResultPart
+    ) -> Result<ToolResultPart, ToolError>
     where
         F: Fn(ToolResultPart) + Send,
     {
+        let (tool, tool_call, input) = match self.resolve(tool_call, messages).await {
+            Ok(resolved) => resolved,
+            Err((_, error)) if self.strict_tool_calls => return Err(error),
+            Err((tool_call, error)) => {
+                return Ok(error_result(
+                    tool_call.tool_call_id,
+                    tool_call.tool_name,
+                    error.to_string(),
+                ))
+            }
+        };
         let tool_call_id = tool_call.tool_call_id.clone();
         let tool_name = tool_call.tool_name.clone();
+        let context = self.context(&tool_call, step_number, messages);
 
-        // Find tool
-        let tool = match self.find_tool(&tool_call.tool_name) {
-            Some(t) => t,
-            None => {
-                return ToolResultPart {
-                    tool_call_id,
-                    tool_name: tool_name.clone(),
-                    output: ToolResultOutput::ErrorText {
-                        value: format!("Tool '{}' not found", tool_name),
-                        provider_metadata: None,
-                    },
-                    preliminary: None,
-                    provider_metadata: None,
-                };
+        // Check approval
+        if let Some(approval) = self.approval(&tool_call) {
+            let response = approval.await;
+            if !response.approved {
+                return Ok(denied_result(tool_call_id, tool_name, response.reason));
             }
-        };
+        }
 
-        let context = ToolContext {
-            tool_call_id: tool_call_id.clone(),
-            messages: vec![], // TODO: pass actual messages
+        // Execute tool, isolated from the other calls
+        let execution = self.execute_tool(
+            tool.as_ref(),
+            input,
+            &context,
+            &tool_call_id,
+            &tool_name,
+            on_preliminary,
+        );
+        let execution = self.policy.isolate(&tool_name, tool.timeout(), execution);
+        let output = match self
+            .timeouts
+            .limit(TimeoutStage::ToolExecution, execution)
+            .await?
+        {
+            Ok(Ok(output)) => output,
+            Ok(Err(error)) => error_output(error, tool.error_mode())?,
+            Err(message) => return Ok(error_result(tool_call_id, tool_name, message)),
         };
 
-        // Parse input
-        let input: Value = match serde_json::from_str(&tool_call.input) {
-            Ok(v) => v,
-            Err(e) => {
-                return ToolResultPart {
-                    tool_call_id,
-                    tool_name,
-                    output: ToolResultOutput::ErrorText {
-                        value: format!("Invalid input: {}", e),
-                        provider_metadata: None,
-                    },
-                    preliminary: None,
-                    provider_metadata: None,
-                };
-            }
-        };
+        Ok(ToolResultPart {
+            tool_call_id,
+            tool_name,
+            output,
+            provider_executed: None,
+            preliminary: None,
+            provider_metadata: None,
+        })
+    }
 
-        // Check approval
-        if tool.needs_approval(&input) {
-            return ToolResultPart {
-                tool_call_id,
-                tool_name,
-                output: ToolResultOutput::ExecutionDenied {
-                    reason: Some("Execution denied by user".to_string()),
-                    provider_metadata: None,
-                },
-                preliminary: None,
-                provider_metadata: None,
-            };
-        }
-
-        // Execute tool
-        match tool.execute(input, &context).await {
-            Ok(ToolOutput::Value(value)) => {
-                // Simple case: single result
-                let output = tool.to_model_output(value);
-                ToolResultPart {
-                    tool_call_id,
-                    tool_name,
-                    output,
-                    preliminary: None,
-                    provider_metadata: None,
-                }
-            }
-            Ok(ToolOutput::Stream(mut stream)) => {
+    /// Runs a tool and converts its final output for the model
+    async fn execute_tool<F>(
+        &self,
+        tool: &dyn Tool,
+        input: Value,
+        context: &ToolContext,
+        tool_call_id: &str,
+        tool_name: &str,
+        on_preliminary: F,
+    ) -> Result<ToolResultOutput, ToolError>
+    where
+        F: Fn(ToolResultPart) + Send,
+    {
+        match tool.execute(input, context).await? {
+            ToolOutput::Value(value) => Ok(tool.to_model_output(value)),
+            ToolOutput::Stream(mut stream) => {
                 use futures::stream::StreamExt;
 
                 let mut last_output = None;
 
-                // Process all stream items
+                // Emit each item as a preliminary result
                 while let Some(item) = stream.next().await {
-                    match item {
-                        Ok(output) => {
-                            // Emit preliminary result
-                            let structured = tool.to_model_output(output.clone());
-                            let preliminary_result = ToolResultPart {
-                                tool_call_id: tool_call_id.clone(),
-                                tool_name: tool_name.clone(),
-                                output: structured,
-                                preliminary: Some(true),
-                                provider_metadata: None,
-                            };
-
-                            on_preliminary(preliminary_result);
-                            last_output = Some(output);
-                        }
-                        Err(e) => {
-                            return ToolResultPart {
-                                tool_call_id,
-                                tool_name,
-                                output: ToolResultOutput::ErrorText {
-                                    value: e.to_string(),
-                                    provider_metadata: None,
-                                },
-                                preliminary: None,
-                                provider_metadata: None,
-                            };
-                        }
-                    }
+                    let output = item?;
+                    on_preliminary(ToolResultPart {
+                        tool_call_id: tool_call_id.to_string(),
+                        tool_name: tool_name.to_string(),
+                        output: tool.to_model_output(output.clone()),
+                        provider_executed: None,
+                        preliminary: Some(true),
+                        provider_metadata: None,
+                    });
+                    last_output = Some(output);
                 }
 
-                // Return final result (last output without preliminary flag)
-                let final_value = last_output.unwrap_or(JsonValue::Null);
-                let final_output = tool.to_model_output(final_value);
-
-                ToolResultPart {
-                    tool_call_id,
-                    tool_name,
-                    output: final_output,
-                    preliminary: None, // Final result
-                    provider_metadata: None,
-                }
+                // The final result is the last output
+                Ok(tool.to_model_output(last_output.unwrap_or(JsonValue::Null)))
             }
-            Err(error) => ToolResultPart {
-                tool_call_id,
-                tool_name,
-                output: ToolResultOutput::ErrorText {
-                    value: error.to_string(),
-                    provider_metadata: None,
-                },
-                preliminary: None,
-                provider_metadata: None,
+        }
+    }
+
+    /// The tool and parsed input of a call
+    ///
+    /// Calls that cannot run as made go through the repair hook; the error
+    /// carries the call as last attempted.
+    async fn resolve(
+        &self,
+        tool_call: ToolCallPart,
+        messages: &[Message],
+    ) -> Result<(Arc<dyn Tool>, ToolCallPart, Value), (ToolCallPart, ToolError)> {
+        let error = match self.check(&tool_call) {
+            Ok((tool, input)) => return Ok((tool, tool_call, input)),
+            Err(error) => error,
+        };
+        let Some(repair) = &self.repair_tool_call else {
+            return Err((tool_call, error));
+        };
+        let request = ToolCallRepairRequest {
+            tool_call: tool_call.clone(),
+            error: error.clone(),
+            tools: self.tool_definitions(),
+            messages: messages.to_vec(),
+        };
+        match repair(request).await {
+            Some(repaired) => match self.check(&repaired) {
+                Ok((tool, input)) => Ok((tool, repaired, input)),
+                Err(error) => Err((repaired, error)),
             },
+            None => Err((tool_call, error)),
+        }
+    }
+
+    /// Finds the tool of a call and parses and validates its arguments
+    fn check(&self, tool_call: &ToolCallPart) -> Result<(Arc<dyn Tool>, Value), ToolError> {
+        let tool = self
+            .find_tool(&tool_call.tool_name)
+            .ok_or_else(|| ToolError::NoSuchTool {
+                tool_name: tool_call.tool_name.clone(),
+                available_tools: self.tools.iter().map(|t| t.name().to_string()).collect(),
+            })?;
+        let invalid = |message: String| ToolError::InvalidToolArguments {
+            tool_name: tool_call.tool_name.clone(),
+            input: tool_call.input.clone(),
+            message,
+        };
+        let input: Value = serde_json::from_str(&tool_call.input)
+            .map_err(|e| invalid(format!("Invalid JSON: {}", e)))?;
+        if self.validate_inputs {
+            validate_input(&tool.input_schema(), &input).map_err(invalid)?;
+        }
+        Ok((tool, input))
+    }
+
+    /// The context passed to the tool of a call
+    fn context(
+        &self,
+        tool_call: &ToolCallPart,
+        step_number: u32,
+        messages: &[Message],
+    ) -> ToolContext {
+        ToolContext {
+            tool_call_id: tool_call.tool_call_id.clone(),
+            messages: messages.to_vec(),
+            step_number,
+            abort_signal: self.abort_signal.clone(),
+            user_context: self.user_context.clone(),
         }
     }
 
+    /// The approval request for a call, if its tool needs approval
+    fn approval_request(&self, tool_call: &ToolCallPart) -> Option<ToolApprovalRequest> {
+        let tool = self.find_tool(&tool_call.tool_name)?;
+        let input: Value = serde_json::from_str(&tool_call.input).ok()?;
+        tool.needs_approval(&input).then(|| ToolApprovalRequest {
+            tool_call_id: tool_call.tool_call_id.clone(),
+            tool_name: tool_call.tool_name.clone(),
+            input,
+        })
+    }
+
+    /// The recorded decision on a call
+    fn decision(&self, tool_call_id: &str) -> Option<&ToolApprovalResponse> {
+        self.approvals
+            .iter()
+            .find(|approval| approval.tool_call_id == tool_call_id)
+    }
+
+    /// Resolves the decision on a call that needs approval
+    ///
+    /// Uses the recorded decision, else asks the approval handler; without
+    /// either the call is denied.
+    fn approval(
+        &self,
+        tool_call: &ToolCallPart,
+    ) -> Option<BoxFuture<'static, ToolApprovalResponse>> {
+        let request = self.approval_request(tool_call)?;
+        if let Some(decision) = self.decision(&request.tool_call_id).cloned() {
+            return Some(Box::pin(async move { decision }));
+        }
+        match &self.approval_handler {
+            Some(handler) => Some(handler(request)),
+            None => Some(Box::pin(async move {
+                ToolApprovalResponse::deny(request.tool_call_id)
+            })),
+        }
+    }
+
+    /// Whether a call may overlap with other calls
+    ///
+    /// Calls of unknown tools never run, so they count as parallel safe.
+    fn parallel_safe(&self, tool_call: &ToolCallPart) -> bool {
+        !self.policy.sequential
+            && self
+                .find_tool(&tool_call.tool_name)
+                .map_or(true, |tool| tool.parallel_safe())
+    }
+
     /// Find a tool by name
     fn find_tool(&self, name: &str) -> Option<Arc<dyn Tool>> {
         self.tools.iter().find(|t| t.name() == name).cloned()
@@ -366,9 +573,81 @@ impl ToolExecutor {
     }
 }
 
+/// Checks `input` against a JSON Schema
+fn validate_input(schema: &Value, input: &Value) -> Result<(), String> {
+    let validator = jsonschema::validator_for(schema)
+        .map_err(|e| format!("Tool has an invalid input schema: {}", e))?;
+    let errors: Vec<String> = validator
+        .iter_errors(input)
+        .map(|error| {
+            let path = error.instance_path.to_string();
+            if path.is_empty() {
+                error.to_string()
+            } else {
+                format!("{}: {}", path, error)
+            }
+        })
+        .collect();
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.join("; "))
+    }
+}
+
+/// A tool's error as output for the model, or the error itself if the tool
+/// does not report errors to the model
+fn error_output(error: ToolError, error_mode: ErrorMode) -> Result<ToolResultOutput, ToolError> {
+    let (value, error_mode) = match error_mode {
+        ErrorMode::Propagate => return Err(error),
+        ErrorMode::Json => match serde_json::from_value(error.to_json()) {
+            Ok(value) => (value, ErrorMode::Json),
+            // Fall back to the message if the details cannot be converted
+            Err(_) => (JsonValue::String(error.to_string()), ErrorMode::Text),
+        },
+        error_mode => (JsonValue::String(error.to_string()), error_mode),
+    };
+    Ok(create_tool_output(value, error_mode, None))
+}
+
+/// The result of a call that failed
+fn error_result(tool_call_id: String, tool_name: String, message: String) -> ToolResultPart {
+    ToolResultPart {
+        tool_call_id,
+        tool_name,
+        output: ToolResultOutput::ErrorText {
+            value: message,
+            provider_metadata: None,
+        },
+        provider_executed: None,
+        preliminary: None,
+        provider_metadata: None,
+    }
+}
+
+/// The result of a call that was not approved
+fn denied_result(
+    tool_call_id: String,
+    tool_name: String,
+    reason: Option<String>,
+) -> ToolResultPart {
+    ToolResultPart {
+        tool_call_id,
+        tool_name,
+        output: ToolResultOutput::ExecutionDenied {
+            reason: Some(reason.unwrap_or_else(|| "Execution denied by user".to_string())),
+            provider_metadata: None,
+        },
+        provider_executed: None,
+        preliminary: None,
+        provider_metadata: None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
 
     struct TestTool {
         name: String,
@@ -401,6 +680,152 @@ mod tests {
         }
     }
 
+    /// Tool named `square` that squares the integer `x`
+    struct SquareTool;
+
+    #[async_trait]
+    impl Tool for SquareTool {
+        fn name(&self) -> &str {
+            "square"
+        }
+
+        fn description(&self) -> &str {
+            "Squares a number"
+        }
+
+        fn input_schema(&self) -> Value {
+            serde_json::json!({
+                "type": "object",
+                "required": ["x"],
+                "properties": { "x": { "type": "integer" } }
+            })
+        }
+
+        async fn execute(
+            &self,
+            input: Value,
+            _context: &ToolContext,
+        ) -> Result<ToolOutput, ToolError> {
+            let x = input["x"]
+                .as_i64()
+                .ok_or_else(|| ToolError::invalid_input("x must be an integer"))?;
+            Ok(ToolOutput::Value(JsonValue::Number((x * x).into())))
+        }
+    }
+
+    /// Tool that records how many of its calls overlap
+    struct TrackedTool {
+        name: &'static str,
+        parallel_safe: bool,
+        running: Arc<AtomicUsize>,
+        peak: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl Tool for TrackedTool {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn description(&self) -> &str {
+            "Records overlapping calls"
+        }
+
+        fn input_schema(&self) -> Value {
+            serde_json::json!({ "type": "object" })
+        }
+
+        async fn execute(
+            &self,
+            _input: Value,
+            _context: &ToolContext,
+        ) -> Result<ToolOutput, ToolError> {
+            let running = self.running.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(running, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            self.running.fetch_sub(1, Ordering::SeqCst);
+            Ok(ToolOutput::Value(JsonValue::Number(running.into())))
+        }
+
+        fn parallel_safe(&self) -> bool {
+            self.parallel_safe
+        }
+    }
+
+    /// Tool named `misbehave` that panics on `{"panic":true}` and hangs
+    /// otherwise
+    struct MisbehavingTool;
+
+    #[async_trait]
+    impl Tool for MisbehavingTool {
+        fn name(&self) -> &str {
+            "misbehave"
+        }
+
+        fn description(&self) -> &str {
+            "Panics or hangs"
+        }
+
+        fn input_schema(&self) -> Value {
+            serde_json::json!({ "type": "object" })
+        }
+
+        async fn execute(
+            &self,
+            input: Value,
+            _context: &ToolContext,
+        ) -> Result<ToolOutput, ToolError> {
+            if input["panic"] == true {
+                panic!("boom");
+            }
+            std::future::pending().await
+        }
+    }
+
+    /// Tool named `lookup` that fails with a coded error
+    struct LookupTool {
+        error_mode: ErrorMode,
+    }
+
+    #[async_trait]
+    impl Tool for LookupTool {
+        fn name(&self) -> &str {
+            "lookup"
+        }
+
+        fn description(&self) -> &str {
+            "Fails to look anything up"
+        }
+
+        fn input_schema(&self) -> Value {
+            serde_json::json!({ "type": "object" })
+        }
+
+        async fn execute(
+            &self,
+            _input: Value,
+            _context: &ToolContext,
+        ) -> Result<ToolOutput, ToolError> {
+            Err(ToolError::coded("not_found", "No such record")
+                .with_details(serde_json::json!({ "id": 7 })))
+        }
+
+        fn error_mode(&self) -> ErrorMode {
+            self.error_mode
+        }
+    }
+
+    fn call(id: &str, tool_name: &str, input: &str) -> ToolCallPart {
+        ToolCallPart {
+            tool_call_id: id.to_string(),
+            tool_name: tool_name.to_string(),
+            input: input.to_string(),
+            provider_executed: None,
+            dynamic: None,
+            provider_metadata: None,
+        }
+    }
+
     #[tokio::test]
     async fn test_tool_executor_find_tool() {
         let tool = Arc::new(TestTool {
@@ -431,7 +856,7 @@ mod tests {
             provider_metadata: None,
         };
 
-        let results = executor.execute_tools(vec![tool_call]).await;
+        let results = executor.execute_tools(vec![tool_call]).await.unwrap();
         assert_eq!(results.len(), 1);
         assert_eq!(results[0].tool_call_id, "call_123");
         assert_eq!(results[0].tool_name, "test");
@@ -445,6 +870,22 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_tool_context_get_downcasts_user_context() {
+        struct RequestId(&'static str);
+
+        let context = ToolContext {
+            tool_call_id: "call_123".to_string(),
+            messages: vec![],
+            step_number: 0,
+            abort_signal: None,
+            user_context: Some(Arc::new(RequestId("req-1"))),
+        };
+
+        assert_eq!(context.get::<RequestId>().map(|id| id.0), Some("req-1"));
+        assert!(context.get::<String>().is_none());
+    }
+
     #[tokio::test]
     async fn test_tool_executor_tool_not_found() {
         let executor = ToolExecutor::new(vec![]);
@@ -458,7 +899,7 @@ mod tests {
             provider_metadata: None,
         };
 
-        let results = executor.execute_tools(vec![tool_call]).await;
+        let results = executor.execute_tools(vec![tool_call]).await.unwrap();
         assert_eq!(results.len(), 1);
 
         // Check that output is ErrorText variant
@@ -469,4 +910,266 @@ mod tests {
             _ => panic!("Expected ErrorText output variant"),
         }
     }
+
+    #[tokio::test]
+    async fn test_input_validation_rejects_arguments_not_matching_schema() {
+        let executor = ToolExecutor::new(vec![Arc::new(SquareTool)]).with_input_validation(true);
+
+        let results = executor
+            .execute_tools(vec![call("call_1", "square", r#"{"x":"three"}"#)])
+            .await
+            .unwrap();
+
+        match &results[0].output {
+            ToolResultOutput::ErrorText { value, .. } => {
+                assert!(value.starts_with("Invalid arguments for tool 'square'"));
+                assert!(value.contains("/x"));
+            }
+            other => panic!("Expected ErrorText output, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_repair_hook_fixes_invalid_call() {
+        let repair: ToolCallRepairFn = Arc::new(|request: ToolCallRepairRequest| {
+            Box::pin(async move {
+                assert!(matches!(
+                    request.error,
+                    ToolError::InvalidToolArguments { .. }
+                ));
+                assert!(request.input_schema().is_some());
+                Some(ToolCallPart {
+                    input: r#"{"x":3}"#.to_string(),
+                    ..request.tool_call
+                })
+            })
+        });
+        let executor = ToolExecutor::new(vec![Arc::new(SquareTool)])
+            .with_input_validation(true)
+            .with_tool_call_repair(Some(repair));
+
+        let results = executor
+            .execute_tools(vec![call("call_1", "square", r#"{"x":"3"}"#)])
+            .await
+            .unwrap();
+
+        assert_eq!(
+            results[0].output,
+            ToolResultOutput::Json {
+                value: JsonValue::Number(9.into()),
+                provider_metadata: None,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_strict_tool_calls_return_typed_errors() {
+        let executor = ToolExecutor::new(vec![Arc::new(SquareTool)]).with_strict_tool_calls(true);
+
+        let error = executor
+            .execute_tools(vec![call("call_1", "square", "not json")])
+            .await
+            .unwrap_err();
+        match error {
+            ToolError::InvalidToolArguments {
+                tool_name, input, ..
+            } => {
+                assert_eq!(tool_name, "square");
+                assert_eq!(input, "not json");
+            }
+            other => panic!("Expected InvalidToolArguments, got {:?}", other),
+        }
+
+        let error = executor
+            .execute_tools(vec![call("call_1", "cube", "{}")])
+            .await
+            .unwrap_err();
+        assert!(matches!(error, ToolError::NoSuchTool { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_max_concurrency_limits_overlapping_calls() {
+        let peak = Arc::new(AtomicUsize::new(0));
+        let tool = Arc::new(TrackedTool {
+            name: "tracked",
+            parallel_safe: true,
+            running: Arc::new(AtomicUsize::new(0)),
+            peak: peak.clone(),
+        });
+        let executor = ToolExecutor::new(vec![tool])
+            .with_execution_policy(ToolExecutionPolicy::new().with_max_concurrency(2));
+        let calls: Vec<_> = (0..5)
+            .map(|i| call(&format!("call_{}", i), "tracked", "{}"))
+            .collect();
+
+        let results = executor.execute_tools(calls).await.unwrap();
+
+        assert_eq!(peak.load(Ordering::SeqCst), 2);
+        let ids: Vec<_> = results.iter().map(|r| r.tool_call_id.as_str()).collect();
+        assert_eq!(ids, ["call_0", "call_1", "call_2", "call_3", "call_4"]);
+    }
+
+    #[tokio::test]
+    async fn test_tools_that_are_not_parallel_safe_run_alone() {
+        let running = Arc::new(AtomicUsize::new(0));
+        let tracked = |name, parallel_safe| -> Arc<dyn Tool> {
+            Arc::new(TrackedTool {
+                name,
+                parallel_safe,
+                running: running.clone(),
+                peak: Arc::new(AtomicUsize::new(0)),
+            })
+        };
+        let executor =
+            ToolExecutor::new(vec![tracked("shared", true), tracked("exclusive", false)]);
+
+        let results = executor
+            .execute_tools(vec![
+                call("call_1", "shared", "{}"),
+                call("call_2", "shared", "{}"),
+                call("call_3", "exclusive", "{}"),
+                call("call_4", "shared", "{}"),
+            ])
+            .await
+            .unwrap();
+
+        // Each result reports how many calls were running when it started
+        let outputs: Vec<_> = results.iter().map(|r| r.output.clone()).collect();
+        assert_eq!(
+            outputs[2],
+            ToolResultOutput::Json {
+                value: JsonValue::Number(1.into()),
+                provider_metadata: None,
+            }
+        );
+        assert_eq!(
+            outputs[1],
+            ToolResultOutput::Json {
+                value: JsonValue::Number(2.into()),
+                provider_metadata: None,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_tool_execution_limit_applies_to_each_call() {
+        let tool = Arc::new(TrackedTool {
+            name: "tracked",
+            parallel_safe: true,
+            running: Arc::new(AtomicUsize::new(0)),
+            peak: Arc::new(AtomicUsize::new(0)),
+        });
+        let executor = ToolExecutor::new(vec![tool])
+            .with_execution_policy(ToolExecutionPolicy::sequential())
+            .with_timeouts(Timeouts::new().with_tool_execution(Duration::from_millis(50)));
+        // Each call takes 20ms, the four of them more than the limit
+        let calls: Vec<_> = (0..4)
+            .map(|i| call(&format!("call_{}", i), "tracked", "{}"))
+            .collect();
+
+        let results = executor.execute_tools(calls).await.unwrap();
+
+        assert_eq!(results.len(), 4);
+        assert!(results
+            .iter()
+            .all(|r| matches!(r.output, ToolResultOutput::Json { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_panicking_and_hanging_tools_become_error_results() {
+        let executor = ToolExecutor::new(vec![
+            Arc::new(MisbehavingTool),
+            Arc::new(TestTool {
+                name: "test".to_string(),
+                result: "success".to_string(),
+            }),
+        ])
+        .with_execution_policy(
+            ToolExecutionPolicy::new().with_tool_timeout(Duration::from_millis(20)),
+        );
+
+        let results = executor
+            .execute_tools(vec![
+                call("call_1", "misbehave", r#"{"panic":true}"#),
+                call("call_2", "misbehave", "{}"),
+                call("call_3", "test", "{}"),
+            ])
+            .await
+            .unwrap();
+
+        let outputs: Vec<_> = results.iter().map(|r| r.output.clone()).collect();
+        let error_text = |value: &str| ToolResultOutput::ErrorText {
+            value: value.into(),
+            provider_metadata: None,
+        };
+        assert_eq!(
+            outputs,
+            [
+                error_text("Tool 'misbehave' panicked: boom"),
+                error_text("Tool 'misbehave' timed out after 20ms"),
+                ToolResultOutput::Text {
+                    value: "success".into(),
+                    provider_metadata: None,
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_json_error_mode_sends_code_and_details() {
+        let executor = ToolExecutor::new(vec![Arc::new(LookupTool {
+            error_mode: ErrorMode::Json,
+        })]);
+
+        let results = executor
+            .execute_tools(vec![call("call_1", "lookup", "{}")])
+            .await
+            .unwrap();
+
+        match &results[0].output {
+            ToolResultOutput::ErrorJson { value, .. } => {
+                assert_eq!(
+                    serde_json::to_value(value).unwrap(),
+                    serde_json::json!({
+                        "code": "not_found",
+                        "message": "No such record",
+                        "details": { "id": 7 }
+                    })
+                );
+            }
+            other => panic!("Expected ErrorJson output, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_error_mode_none_sends_errors_as_output() {
+        let executor = ToolExecutor::new(vec![Arc::new(LookupTool {
+            error_mode: ErrorMode::None,
+        })]);
+
+        let results = executor
+            .execute_tools(vec![call("call_1", "lookup", "{}")])
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            &results[0].output,
+            ToolResultOutput::Text { value, .. } if value == "No such record"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_error_mode_propagate_fails_with_tool_errors() {
+        let executor = ToolExecutor::new(vec![Arc::new(LookupTool {
+            error_mode: ErrorMode::Propagate,
+        })]);
+
+        let error = executor
+            .execute_tools(vec![call("call_1", "lookup", "{}")])
+            .await
+            .unwrap_err();
+
+        assert_eq!(error.code(), "not_found");
+        assert_eq!(error.to_string(), "No such record");
+    }
 }
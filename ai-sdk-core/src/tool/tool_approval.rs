@@ -0,0 +1,139 @@
+use ai_sdk_provider::language_model::{AssistantContentPart, Message, ToolCallPart};
+use futures::future::BoxFuture;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::Arc;
+
+/// A tool call waiting for a human decision
+///
+/// Returned when a generation pauses because a tool's
+/// [`needs_approval`](super::Tool::needs_approval) is true and no approval
+/// handler is set.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolApprovalRequest {
+    /// ID of the tool call waiting for approval
+    pub tool_call_id: String,
+    /// Name of the tool
+    pub tool_name: String,
+    /// Parsed input the model called the tool with
+    pub input: Value,
+}
+
+/// A decision on a tool call that needs approval
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ToolApprovalResponse {
+    /// ID of the tool call the decision is for
+    pub tool_call_id: String,
+    /// Whether the tool may run
+    pub approved: bool,
+    /// Reason for the decision, sent to the model when the call is denied
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl ToolApprovalResponse {
+    /// Approve the tool call
+    pub fn approve(tool_call_id: impl Into<String>) -> Self {
+        Self {
+            tool_call_id: tool_call_id.into(),
+            approved: true,
+            reason: None,
+        }
+    }
+
+    /// Deny the tool call
+    pub fn deny(tool_call_id: impl Into<String>) -> Self {
+        Self {
+            tool_call_id: tool_call_id.into(),
+            approved: false,
+            reason: None,
+        }
+    }
+
+    /// Set the reason for the decision
+    pub fn with_reason(mut self, reason: impl Into<String>) -> Self {
+        self.reason = Some(reason.into());
+        self
+    }
+}
+
+/// Handler asked to approve tool calls while a generation runs
+pub type ToolApprovalFn =
+    Arc<dyn Fn(ToolApprovalRequest) -> BoxFuture<'static, ToolApprovalResponse> + Send + Sync>;
+
+/// Tool calls of a trailing assistant message that have no results yet
+///
+/// These are the calls a paused generation left waiting for approval.
+pub(crate) fn unresolved_tool_calls(messages: &[Message]) -> Vec<ToolCallPart> {
+    let Some(Message::Assistant { content }) = messages.last() else {
+        return Vec::new();
+    };
+    content
+        .iter()
+        .filter_map(|part| match part {
+            AssistantContentPart::ToolCall(call) if call.provider_executed != Some(true) => {
+                Some(call.clone())
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ai_sdk_provider::language_model::{TextPart, UserContentPart};
+
+    fn tool_call(id: &str) -> ToolCallPart {
+        ToolCallPart {
+            tool_call_id: id.to_string(),
+            tool_name: "delete".to_string(),
+            input: "{}".to_string(),
+            provider_executed: None,
+            dynamic: None,
+            provider_metadata: None,
+        }
+    }
+
+    #[test]
+    fn test_unresolved_tool_calls_of_trailing_assistant_message() {
+        let messages = vec![
+            Message::User {
+                content: vec![UserContentPart::Text {
+                    text: "Clean up".to_string(),
+                }],
+            },
+            Message::Assistant {
+                content: vec![
+                    AssistantContentPart::Text(TextPart {
+                        text: "Deleting".to_string(),
+                        provider_metadata: None,
+                    }),
+                    AssistantContentPart::ToolCall(tool_call("call_1")),
+                ],
+            },
+        ];
+
+        let calls = unresolved_tool_calls(&messages);
+        assert_eq!(calls.len(), 1);
+        assert_eq!(calls[0].tool_call_id, "call_1");
+        assert!(unresolved_tool_calls(&messages[..1]).is_empty());
+    }
+
+    #[test]
+    fn test_approval_response_round_trips_through_json() {
+        let response = ToolApprovalResponse::deny("call_1").with_reason("Not in production");
+
+        let json = serde_json::to_string(&response).unwrap();
+        assert_eq!(
+            json,
+            r#"{"toolCallId":"call_1","approved":false,"reason":"Not in production"}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<ToolApprovalResponse>(&json).unwrap(),
+            response
+        );
+    }
+}
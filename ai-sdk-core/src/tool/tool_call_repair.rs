@@ -0,0 +1,141 @@
+use crate::error::ToolError;
+use ai_sdk_provider::language_model::{
+    CallOptions, Content, FunctionTool, LanguageModel, Message, ResponseFormat, ToolCallPart,
+    UserContentPart,
+};
+use futures::future::BoxFuture;
+use serde_json::Value;
+use std::sync::Arc;
+
+/// A tool call that cannot run as the model made it
+///
+/// Passed to a [`ToolCallRepairFn`] when the call names an unknown tool or
+/// its arguments do not parse or match the tool's input schema.
+#[derive(Debug, Clone)]
+pub struct ToolCallRepairRequest {
+    /// The call as the model made it
+    pub tool_call: ToolCallPart,
+    /// Why the call cannot run: [`ToolError::NoSuchTool`] or
+    /// [`ToolError::InvalidToolArguments`]
+    pub error: ToolError,
+    /// Definitions of the tools available to the model
+    pub tools: Vec<FunctionTool>,
+    /// Conversation up to and including the assistant message that made the
+    /// call
+    pub messages: Vec<Message>,
+}
+
+impl ToolCallRepairRequest {
+    /// Input schema of the tool the call names, if it exists
+    pub fn input_schema(&self) -> Option<&Value> {
+        self.tools
+            .iter()
+            .find(|tool| tool.name == self.tool_call.tool_name)
+            .map(|tool| &tool.input_schema)
+    }
+}
+
+/// Hook that fixes tool calls that cannot run as made
+///
+/// Returns the repaired call, which is checked again before it runs, or
+/// `None` to give up. Repaired calls should keep the original
+/// `tool_call_id`.
+pub type ToolCallRepairFn =
+    Arc<dyn Fn(ToolCallRepairRequest) -> BoxFuture<'static, Option<ToolCallPart>> + Send + Sync>;
+
+/// Repairs invalid tool arguments by asking `model` to rewrite them
+///
+/// The model gets the tool's input schema, the arguments and the validation
+/// error, and must answer with corrected arguments as JSON. Calls of unknown
+/// tools are not repaired.
+pub fn repair_tool_call_with_model(model: Arc<dyn LanguageModel>) -> ToolCallRepairFn {
+    Arc::new(move |request: ToolCallRepairRequest| {
+        let model = model.clone();
+        Box::pin(async move {
+            if !matches!(request.error, ToolError::InvalidToolArguments { .. }) {
+                return None;
+            }
+            let schema = request.input_schema()?.clone();
+            let prompt = format!(
+                "The arguments of a call to the tool \"{}\" are invalid.\n\n\
+                 Input schema:\n{}\n\nArguments:\n{}\n\nError:\n{}\n\n\
+                 Respond with corrected arguments that match the schema, as JSON only.",
+                request.tool_call.tool_name, schema, request.tool_call.input, request.error
+            );
+            let options = CallOptions {
+                prompt: vec![Message::User {
+                    content: vec![UserContentPart::Text { text: prompt }],
+                }],
+                response_format: Some(ResponseFormat::Json {
+                    schema: Some(schema),
+                    name: None,
+                    description: None,
+                }),
+                ..Default::default()
+            };
+            let response = model.do_generate(options).await.ok()?;
+            let text: String = response
+                .content
+                .iter()
+                .filter_map(|content| match content {
+                    Content::Text(part) => Some(part.text.as_str()),
+                    _ => None,
+                })
+                .collect();
+            serde_json::from_str::<Value>(&text).ok()?;
+            Some(ToolCallPart {
+                input: text,
+                ..request.tool_call
+            })
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{text_response, MockLanguageModel};
+
+    #[tokio::test]
+    async fn test_model_rewrites_invalid_arguments() {
+        let model = MockLanguageModel::new().with_generate(Ok(text_response(r#"{"x":3}"#)));
+        let repair = repair_tool_call_with_model(Arc::new(model.clone()));
+        let tool_call = ToolCallPart {
+            tool_call_id: "call_1".to_string(),
+            tool_name: "square".to_string(),
+            input: r#"{"x":"three"}"#.to_string(),
+            provider_executed: None,
+            dynamic: None,
+            provider_metadata: None,
+        };
+        let schema = serde_json::json!({
+            "type": "object",
+            "properties": { "x": { "type": "number" } }
+        });
+
+        let repaired = repair(ToolCallRepairRequest {
+            tool_call: tool_call.clone(),
+            error: ToolError::InvalidToolArguments {
+                tool_name: "square".to_string(),
+                input: tool_call.input.clone(),
+                message: "/x: \"three\" is not of type \"number\"".to_string(),
+            },
+            tools: vec![FunctionTool {
+                name: "square".to_string(),
+                description: None,
+                input_schema: schema.clone(),
+                provider_options: None,
+            }],
+            messages: vec![],
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(repaired.tool_call_id, "call_1");
+        assert_eq!(repaired.input, r#"{"x":3}"#);
+        assert!(matches!(
+            model.calls()[0].response_format,
+            Some(ResponseFormat::Json { schema: Some(ref sent), .. }) if sent == &schema
+        ));
+    }
+}
@@ -0,0 +1,136 @@
+use futures::FutureExt;
+use std::any::Any;
+use std::future::Future;
+use std::panic::AssertUnwindSafe;
+use std::time::Duration;
+
+/// How the tool calls of a step are scheduled and isolated
+///
+/// By default every call of a step runs at once and panics are caught.
+/// Results always come back in the order the model made the calls. Calls of
+/// tools that are not [`parallel_safe`](super::Tool::parallel_safe) run
+/// alone, after the calls before them finish and before the calls after
+/// them start.
+///
+/// A call that panics or runs over its timeout gets an error result the
+/// model can react to, and the other calls of the step carry on. Use
+/// [`Timeouts::with_tool_execution`](crate::Timeouts::with_tool_execution)
+/// instead to fail the whole generation.
+///
+/// # Example
+///
+/// ```
+/// use ai_sdk_core::ToolExecutionPolicy;
+/// use std::time::Duration;
+///
+/// let policy = ToolExecutionPolicy::new()
+///     .with_max_concurrency(4)
+///     .with_tool_timeout(Duration::from_secs(30));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ToolExecutionPolicy {
+    /// Most calls running at once; `None` runs all parallel-safe calls of a
+    /// step together
+    pub max_concurrency: Option<usize>,
+    /// Run every call alone, one after another
+    pub sequential: bool,
+    /// Limit for each call, unless its tool sets its own
+    /// [`timeout`](super::Tool::timeout)
+    pub tool_timeout: Option<Duration>,
+    /// Turn a panicking tool into an error result instead of unwinding
+    pub catch_panics: bool,
+}
+
+impl Default for ToolExecutionPolicy {
+    fn default() -> Self {
+        Self {
+            max_concurrency: None,
+            sequential: false,
+            tool_timeout: None,
+            catch_panics: true,
+        }
+    }
+}
+
+impl ToolExecutionPolicy {
+    /// Creates the default policy
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a policy that runs calls one after another
+    pub fn sequential() -> Self {
+        Self {
+            sequential: true,
+            ..Self::default()
+        }
+    }
+
+    /// Sets the most calls running at once
+    pub fn with_max_concurrency(mut self, max_concurrency: usize) -> Self {
+        self.max_concurrency = Some(max_concurrency);
+        self
+    }
+
+    /// Sets the limit for each call
+    pub fn with_tool_timeout(mut self, timeout: Duration) -> Self {
+        self.tool_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets whether panicking tools become error results
+    pub fn with_catch_panics(mut self, catch_panics: bool) -> Self {
+        self.catch_panics = catch_panics;
+        self
+    }
+
+    /// Number of calls to run at once within a group of parallel-safe calls
+    pub(crate) fn concurrency(&self) -> usize {
+        if self.sequential {
+            1
+        } else {
+            self.max_concurrency.unwrap_or(usize::MAX).max(1)
+        }
+    }
+
+    /// Runs the execution of a call of `tool_name` within its timeout,
+    /// catching panics
+    ///
+    /// Fails with the message for the model when the call panics or times
+    /// out.
+    pub(crate) async fn isolate<F: Future>(
+        &self,
+        tool_name: &str,
+        timeout: Option<Duration>,
+        execution: F,
+    ) -> Result<F::Output, String> {
+        let catch_panics = self.catch_panics;
+        let execution = async move {
+            if catch_panics {
+                AssertUnwindSafe(execution)
+                    .catch_unwind()
+                    .await
+                    .map_err(|panic| {
+                        format!("Tool '{}' panicked: {}", tool_name, panic_message(&panic))
+                    })
+            } else {
+                Ok(execution.await)
+            }
+        };
+        match timeout.or(self.tool_timeout) {
+            Some(timeout) => tokio::time::timeout(timeout, execution)
+                .await
+                .map_err(|_| format!("Tool '{}' timed out after {:?}", tool_name, timeout))?,
+            None => execution.await,
+        }
+    }
+}
+
+/// The message a panic was raised with
+fn panic_message(panic: &Box<dyn Any + Send>) -> &str {
+    panic
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| panic.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown panic")
+}
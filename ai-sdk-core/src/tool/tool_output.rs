@@ -15,15 +15,33 @@ pub enum ToolOutput {
 }
 
 /// Error mode for tool output conversion
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-#[allow(dead_code)]
+///
+/// A tool's [`error_mode`](super::Tool::error_mode) decides how its errors
+/// reach the model.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
 pub enum ErrorMode {
     /// No error, normal output
+    ///
+    /// As a tool's error mode, error messages reach the model as normal
+    /// output, without being marked as errors.
     None,
     /// Convert output to error text
+    ///
+    /// Tool errors become [`ToolResultOutput::ErrorText`] with the error
+    /// message.
+    #[default]
     Text,
     /// Convert output to error JSON
+    ///
+    /// Tool errors become [`ToolResultOutput::ErrorJson`] with the error's
+    /// code, message and details; see [`ToolError::to_json`].
     Json,
+    /// Fail the generation with the error
+    ///
+    /// Only meaningful as a tool's error mode: tool errors are not sent to
+    /// the model and the generation fails with the [`ToolError`] instead.
+    /// [`create_tool_output`] treats it like [`ErrorMode::None`].
+    Propagate,
 }
 
 /// Convert raw tool output to structured ToolResultOutput
@@ -35,7 +53,6 @@ pub enum ErrorMode {
 ///
 /// # Returns
 /// A structured ToolResultOutput enum variant
-#[allow(dead_code)]
 pub fn create_tool_output(
     output: JsonValue,
     error_mode: ErrorMode,
@@ -59,7 +76,7 @@ pub fn create_tool_output(
                 provider_metadata: None,
             };
         }
-        ErrorMode::None => {}
+        ErrorMode::None | ErrorMode::Propagate => {}
     }
 
     // Custom conversion via hook
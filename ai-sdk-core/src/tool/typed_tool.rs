@@ -0,0 +1,304 @@
+use super::tool_output::create_tool_output;
+use super::{ErrorMode, Tool, ToolContext, ToolOutput};
+use crate::error::ToolError;
+use ai_sdk_provider::language_model::ToolResultOutput;
+use ai_sdk_provider::JsonValue;
+use async_trait::async_trait;
+use schemars::gen::SchemaSettings;
+use schemars::JsonSchema;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json::Value;
+use std::time::Duration;
+
+/// A tool with typed input and output
+///
+/// Every `TypedTool` is a [`Tool`]: the input schema is generated from
+/// `Input`, the model's arguments are deserialized into it before `execute`
+/// runs, and the output is serialized back to JSON. Arguments that do not
+/// match `Input` fail with [`ToolError::InvalidInput`].
+///
+/// The [`tool`](crate::tool) attribute implements this trait for an async
+/// function.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// #[derive(Deserialize, JsonSchema)]
+/// struct WeatherQuery {
+///     /// City to look up
+///     city: String,
+/// }
+///
+/// struct WeatherTool;
+///
+/// #[async_trait]
+/// impl TypedTool for WeatherTool {
+///     type Input = WeatherQuery;
+///     type Output = f64;
+///
+///     fn name(&self) -> &str {
+///         "get_weather"
+///     }
+///
+///     fn description(&self) -> &str {
+///         "Get the temperature in a city"
+///     }
+///
+///     async fn execute(&self, input: WeatherQuery, _context: &ToolContext) -> Result<f64, ToolError> {
+///         Ok(21.5)
+///     }
+/// }
+/// ```
+#[async_trait]
+pub trait TypedTool: Send + Sync {
+    /// Input the model calls the tool with
+    type Input: DeserializeOwned + JsonSchema + Send;
+
+    /// Output returned to the model
+    type Output: Serialize + Send;
+
+    /// Name of the tool (used by LLM)
+    fn name(&self) -> &str;
+
+    /// Description of what the tool does
+    fn description(&self) -> &str;
+
+    /// Execute the tool with the deserialized input
+    async fn execute(
+        &self,
+        input: Self::Input,
+        context: &ToolContext,
+    ) -> Result<Self::Output, ToolError>;
+
+    /// Check if this call requires approval before execution
+    fn needs_approval(&self, _input: &Self::Input) -> bool {
+        false
+    }
+
+    /// Whether calls of this tool may overlap with other calls
+    fn parallel_safe(&self) -> bool {
+        true
+    }
+
+    /// Limit for each call of this tool
+    fn timeout(&self) -> Option<Duration> {
+        None
+    }
+
+    /// How errors returned by `execute` reach the model
+    fn error_mode(&self) -> ErrorMode {
+        ErrorMode::Text
+    }
+
+    /// Convert the serialized output for the model
+    fn to_model_output(&self, output: JsonValue) -> ToolResultOutput {
+        create_tool_output(output, ErrorMode::None, None)
+    }
+}
+
+#[async_trait]
+impl<T: TypedTool> Tool for T {
+    fn name(&self) -> &str {
+        TypedTool::name(self)
+    }
+
+    fn description(&self) -> &str {
+        TypedTool::description(self)
+    }
+
+    fn input_schema(&self) -> Value {
+        input_schema_for::<T::Input>()
+    }
+
+    async fn execute(&self, input: Value, context: &ToolContext) -> Result<ToolOutput, ToolError> {
+        let input =
+            serde_json::from_value(input).map_err(|e| ToolError::invalid_input(e.to_string()))?;
+        let output = TypedTool::execute(self, input, context).await?;
+        let output = serde_json::to_value(output)
+            .and_then(serde_json::from_value)
+            .map_err(|e| ToolError::execution(format!("Failed to serialize output: {}", e)))?;
+        Ok(ToolOutput::Value(output))
+    }
+
+    fn needs_approval(&self, input: &Value) -> bool {
+        // Input that does not deserialize fails in `execute` without running
+        serde_json::from_value(input.clone())
+            .map(|input| TypedTool::needs_approval(self, &input))
+            .unwrap_or(false)
+    }
+
+    fn parallel_safe(&self) -> bool {
+        TypedTool::parallel_safe(self)
+    }
+
+    fn timeout(&self) -> Option<Duration> {
+        TypedTool::timeout(self)
+    }
+
+    fn error_mode(&self) -> ErrorMode {
+        TypedTool::error_mode(self)
+    }
+
+    fn to_model_output(&self, output: JsonValue) -> ToolResultOutput {
+        TypedTool::to_model_output(self, output)
+    }
+}
+
+/// JSON Schema for a tool input type, with all definitions inlined
+pub fn input_schema_for<T: JsonSchema>() -> Value {
+    let settings = SchemaSettings::draft07().with(|settings| {
+        settings.inline_subschemas = true;
+        settings.meta_schema = None;
+    });
+    let schema = settings.into_generator().into_root_schema_for::<T>();
+    let mut schema = serde_json::to_value(schema).expect("JSON schemas serialize");
+    if let Some(object) = schema.as_object_mut() {
+        // The Rust type name means nothing to the model
+        object.remove("title");
+    }
+    schema
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tool::ToolExecutor;
+    use ai_sdk_provider::language_model::{ToolCallPart, ToolResultOutput};
+    use serde::Deserialize;
+    use std::sync::Arc;
+
+    #[derive(Deserialize, JsonSchema)]
+    struct AddInput {
+        /// First operand
+        a: i64,
+        /// Second operand
+        b: i64,
+    }
+
+    #[derive(Serialize)]
+    struct AddOutput {
+        sum: i64,
+    }
+
+    struct AddTool;
+
+    #[async_trait]
+    impl TypedTool for AddTool {
+        type Input = AddInput;
+        type Output = AddOutput;
+
+        fn name(&self) -> &str {
+            "add"
+        }
+
+        fn description(&self) -> &str {
+            "Adds two numbers"
+        }
+
+        async fn execute(
+            &self,
+            input: AddInput,
+            _context: &ToolContext,
+        ) -> Result<AddOutput, ToolError> {
+            Ok(AddOutput {
+                sum: input.a + input.b,
+            })
+        }
+    }
+
+    /// Multiplies two numbers
+    #[crate::tool(name = "multiply")]
+    async fn multiply_numbers(input: AddInput, context: &ToolContext) -> Result<i64, ToolError> {
+        assert_eq!(context.tool_call_id, "call_1");
+        Ok(input.a * input.b)
+    }
+
+    fn add_call(input: &str) -> ToolCallPart {
+        ToolCallPart {
+            tool_call_id: "call_1".to_string(),
+            tool_name: "add".to_string(),
+            input: input.to_string(),
+            provider_executed: None,
+            dynamic: None,
+            provider_metadata: None,
+        }
+    }
+
+    #[test]
+    fn test_input_schema_is_generated_from_input_type() {
+        let schema = Tool::input_schema(&AddTool);
+
+        assert_eq!(
+            schema,
+            serde_json::json!({
+                "type": "object",
+                "required": ["a", "b"],
+                "properties": {
+                    "a": { "description": "First operand", "type": "integer", "format": "int64" },
+                    "b": { "description": "Second operand", "type": "integer", "format": "int64" }
+                }
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_typed_tool_runs_with_deserialized_input() {
+        let executor = ToolExecutor::new(vec![Arc::new(AddTool)]);
+
+        let results = executor
+            .execute_tools(vec![add_call(r#"{"a":2,"b":3}"#)])
+            .await
+            .unwrap();
+
+        match &results[0].output {
+            ToolResultOutput::Json { value, .. } => {
+                assert_eq!(
+                    serde_json::to_value(value).unwrap(),
+                    serde_json::json!({ "sum": 5 })
+                );
+            }
+            other => panic!("Expected Json output, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_mismatched_input_is_invalid_input() {
+        let context = ToolContext {
+            tool_call_id: "call_1".to_string(),
+            messages: vec![],
+            step_number: 0,
+            abort_signal: None,
+            user_context: None,
+        };
+
+        let result = Tool::execute(&AddTool, serde_json::json!({ "a": "two" }), &context).await;
+
+        assert!(matches!(result, Err(ToolError::InvalidInput(_))));
+    }
+
+    #[tokio::test]
+    async fn test_tool_attribute_defines_typed_tool() {
+        assert_eq!(Tool::name(&MultiplyNumbers), "multiply");
+        assert_eq!(
+            Tool::description(&MultiplyNumbers),
+            "Multiplies two numbers"
+        );
+        assert_eq!(
+            Tool::input_schema(&MultiplyNumbers),
+            input_schema_for::<AddInput>()
+        );
+
+        let executor = ToolExecutor::new(vec![Arc::new(MultiplyNumbers)]);
+        let mut call = add_call(r#"{"a":4,"b":5}"#);
+        call.tool_name = "multiply".to_string();
+        let results = executor.execute_tools(vec![call]).await.unwrap();
+
+        match &results[0].output {
+            ToolResultOutput::Json { value, .. } => {
+                assert_eq!(value, &ai_sdk_provider::JsonValue::Number(20.into()));
+            }
+            other => panic!("Expected Json output, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,216 @@
+//! # AI SDK Macros
+//!
+//! Procedural macros for `ai-sdk-core`. They are re-exported from there; use
+//! `ai_sdk_core::tool` rather than this crate directly.
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::{format_ident, quote};
+use syn::{
+    parse_macro_input, Expr, ExprLit, FnArg, GenericArgument, Ident, ItemFn, Lit, LitStr, Meta,
+    PathArguments, ReturnType, Type,
+};
+
+/// Turns an async function into a tool
+///
+/// The function takes its input as one parameter whose type implements
+/// `Deserialize` and `JsonSchema`, optionally followed by a `&ToolContext`,
+/// and returns `Result<Output, E>` where `Output: Serialize` and
+/// `E: Into<ToolError>`. The macro keeps the function and adds a unit struct
+/// named after it in PascalCase that implements `TypedTool`, and so `Tool`:
+///
+/// ```rust,ignore
+/// /// Get the current weather for a city
+/// #[tool]
+/// async fn get_weather(query: WeatherQuery) -> Result<Weather, ToolError> {
+///     Ok(lookup(&query.city).await?)
+/// }
+///
+/// let tools: Vec<Arc<dyn Tool>> = vec![Arc::new(GetWeather)];
+/// ```
+///
+/// The doc comment is the tool's description and the function name its name;
+/// `#[tool(name = "weather")]` sets another name.
+#[proc_macro_attribute]
+pub fn tool(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut name = None;
+    let parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("name") {
+            name = Some(meta.value()?.parse::<LitStr>()?);
+            Ok(())
+        } else {
+            Err(meta.error("unsupported #[tool] argument; expected `name = \"...\"`"))
+        }
+    });
+    parse_macro_input!(attr with parser);
+    let function = parse_macro_input!(item as ItemFn);
+
+    match expand(name, function) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+fn expand(name: Option<LitStr>, function: ItemFn) -> syn::Result<proc_macro2::TokenStream> {
+    let signature = &function.sig;
+    if signature.asyncness.is_none() {
+        return Err(syn::Error::new_spanned(
+            signature.fn_token,
+            "#[tool] functions must be async",
+        ));
+    }
+    if !signature.generics.params.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &signature.generics,
+            "#[tool] functions cannot be generic",
+        ));
+    }
+
+    let mut inputs = signature.inputs.iter();
+    let input_type = match inputs.next() {
+        Some(FnArg::Typed(input)) => &input.ty,
+        Some(FnArg::Receiver(receiver)) => {
+            return Err(syn::Error::new_spanned(
+                receiver,
+                "#[tool] functions cannot take self",
+            ))
+        }
+        None => {
+            return Err(syn::Error::new_spanned(
+                &signature.inputs,
+                "#[tool] functions take their input as the first parameter",
+            ))
+        }
+    };
+    let takes_context = match inputs.next() {
+        Some(FnArg::Typed(context)) if matches!(*context.ty, Type::Reference(_)) => true,
+        Some(other) => {
+            return Err(syn::Error::new_spanned(
+                other,
+                "the second parameter of a #[tool] function must be `&ToolContext`",
+            ))
+        }
+        None => false,
+    };
+    if let Some(extra) = inputs.next() {
+        return Err(syn::Error::new_spanned(
+            extra,
+            "#[tool] functions take at most an input and a `&ToolContext`",
+        ));
+    }
+
+    let output_type = result_ok_type(&signature.output)?;
+    let description = doc_comment(&function).ok_or_else(|| {
+        syn::Error::new_spanned(
+            &signature.ident,
+            "#[tool] functions need a doc comment; it is the tool's description",
+        )
+    })?;
+
+    let function_name = &signature.ident;
+    let tool_name =
+        name.unwrap_or_else(|| LitStr::new(&function_name.to_string(), Span::call_site()));
+    let struct_name = pascal_case(function_name);
+    let visibility = &function.vis;
+    let struct_doc = format!("Tool that runs [`{}`]", function_name);
+    let call = if takes_context {
+        quote!(#function_name(input, context))
+    } else {
+        quote!(#function_name(input))
+    };
+
+    Ok(quote! {
+        #function
+
+        #[doc = #struct_doc]
+        #[derive(Debug, Clone, Copy, Default)]
+        #visibility struct #struct_name;
+
+        #[::ai_sdk_core::__private::async_trait]
+        impl ::ai_sdk_core::TypedTool for #struct_name {
+            type Input = #input_type;
+            type Output = #output_type;
+
+            fn name(&self) -> &str {
+                #tool_name
+            }
+
+            fn description(&self) -> &str {
+                #description
+            }
+
+            async fn execute(
+                &self,
+                input: Self::Input,
+                context: &::ai_sdk_core::ToolContext,
+            ) -> ::std::result::Result<Self::Output, ::ai_sdk_core::ToolError> {
+                let _ = context;
+                #call.await.map_err(::std::convert::Into::into)
+            }
+        }
+    })
+}
+
+/// The `T` of a `Result<T, E>` return type
+fn result_ok_type(output: &ReturnType) -> syn::Result<&Type> {
+    let error = || {
+        syn::Error::new_spanned(
+            output,
+            "#[tool] functions must return `Result<Output, Error>`",
+        )
+    };
+    let ReturnType::Type(_, ty) = output else {
+        return Err(error());
+    };
+    let Type::Path(path) = ty.as_ref() else {
+        return Err(error());
+    };
+    let segment = path.path.segments.last().ok_or_else(error)?;
+    if segment.ident != "Result" {
+        return Err(error());
+    }
+    let PathArguments::AngleBracketed(arguments) = &segment.arguments else {
+        return Err(error());
+    };
+    match arguments.args.first() {
+        Some(GenericArgument::Type(ok)) => Ok(ok),
+        _ => Err(error()),
+    }
+}
+
+/// The doc comment of the function, with one leading space per line removed
+fn doc_comment(function: &ItemFn) -> Option<String> {
+    let lines: Vec<String> = function
+        .attrs
+        .iter()
+        .filter_map(|attr| match &attr.meta {
+            Meta::NameValue(meta) if meta.path.is_ident("doc") => match &meta.value {
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(doc), ..
+                }) => Some(doc.value()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .map(|line| line.strip_prefix(' ').unwrap_or(&line).to_string())
+        .collect();
+    let doc = lines.join("\n").trim().to_string();
+    (!doc.is_empty()).then_some(doc)
+}
+
+/// `get_weather` → `GetWeather`
+fn pascal_case(ident: &Ident) -> Ident {
+    let name: String = ident
+        .to_string()
+        .split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect();
+    format_ident!("{}", name, span = ident.span())
+}
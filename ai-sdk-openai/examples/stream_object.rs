@@ -109,6 +109,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             ObjectStreamPart::Error { error } => {
                 eprintln!("Error: {}", error);
             }
+            ObjectStreamPart::Interrupted { error } => {
+                eprintln!("Stream interrupted: {}", error);
+            }
+            ObjectStreamPart::Aborted => {
+                eprintln!("Stream aborted");
+            }
         }
     }
 
@@ -155,7 +155,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         provider_metadata: None,
     };
 
-    let results = executor.execute_tools(vec![tool_call]).await;
+    let results = executor.execute_tools(vec![tool_call]).await?;
     for result in &results {
         println!("   Tool: {}", result.tool_name);
         match &result.output {
@@ -178,7 +178,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         provider_metadata: None,
     };
 
-    let results = executor.execute_tools(vec![tool_call]).await;
+    let results = executor.execute_tools(vec![tool_call]).await?;
     for result in &results {
         println!("   Tool: {}", result.tool_name);
         match &result.output {
@@ -212,7 +212,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         provider_metadata: None,
     };
 
-    let results = executor.execute_tools(vec![tool_call]).await;
+    let results = executor.execute_tools(vec![tool_call]).await?;
     for result in &results {
         println!("   Tool: {}", result.tool_name);
         match &result.output {
@@ -235,7 +235,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         provider_metadata: None,
     };
 
-    let results = executor.execute_tools(vec![tool_call]).await;
+    let results = executor.execute_tools(vec![tool_call]).await?;
     for result in &results {
         println!("   Tool: {}", result.tool_name);
         match &result.output {
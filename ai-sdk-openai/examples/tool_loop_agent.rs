@@ -183,8 +183,8 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     "\n📊 Step finished - Tokens used: {}",
                     step.usage.total_tokens.unwrap_or(0)
                 );
-                if let Some(tool_calls) = &step.tool_calls {
-                    println!("   Tool calls: {}", tool_calls.len());
+                if !step.tool_calls.is_empty() {
+                    println!("   Tool calls: {}", step.tool_calls.len());
                 }
             })
         }))
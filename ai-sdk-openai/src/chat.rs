@@ -1,6 +1,6 @@
 use ai_sdk_provider::language_model::{
-    AssistantContentPart, Message, ResponseInfo, SourcePart, SourceType, StreamError, TextPart,
-    ToolCallPart, UserContentPart,
+    AssistantContentPart, ContentPart, Message, ResponseInfo, SourcePart, SourceType, TextPart,
+    ToolCallPart, ToolResultOutput, UserContentPart,
 };
 use ai_sdk_provider::*;
 use async_stream::stream;
@@ -19,6 +19,7 @@ fn generate_source_id() -> String {
     format!("source-{}", id)
 }
 
+use crate::error::{network_error, response_error, stream_error};
 use crate::model_detection::{
     is_o1_model, is_reasoning_model, is_search_preview_model, supports_flex_processing,
 };
@@ -185,8 +186,17 @@ impl OpenAIChatModel {
                                     },
                                 });
                             }
-                            // Skip other content types for now
-                            _ => {}
+                            AssistantContentPart::Reasoning(_) => {
+                                warn!("Reasoning parts cannot be sent to the OpenAI chat API and were dropped");
+                            }
+                            AssistantContentPart::File(file_part) => {
+                                warn!(
+                                    "Assistant file parts cannot be sent to the OpenAI chat API and were dropped: {}",
+                                    file_part.media_type
+                                );
+                            }
+                            // Results of provider-executed tools are not replayed
+                            AssistantContentPart::ToolResult(_) => {}
                         }
                     }
 
@@ -211,9 +221,7 @@ impl OpenAIChatModel {
                     for tool_result in content {
                         openai_messages.push(crate::api_types::ChatMessage {
                             role: "tool".into(),
-                            content: Some(crate::api_types::ChatMessageContent::Text(
-                                serde_json::to_string(&tool_result.output).unwrap_or_default(),
-                            )),
+                            content: Some(tool_output_to_content(&tool_result.output)),
                             tool_calls: None,
                             tool_call_id: Some(tool_result.tool_call_id.clone()),
                             annotations: None,
@@ -435,6 +443,66 @@ struct OpenAIOptions {
     safety_identifier: Option<String>,
 }
 
+/// Converts a tool result to the content of a tool message
+///
+/// Text and JSON results are sent as they are, multi-part results as text
+/// and image parts.
+fn tool_output_to_content(output: &ToolResultOutput) -> crate::api_types::ChatMessageContent {
+    use crate::api_types::ChatMessageContent;
+    use crate::multimodal::{ImageUrl, OpenAIContentPart};
+
+    match output {
+        ToolResultOutput::Text { value, .. } | ToolResultOutput::ErrorText { value, .. } => {
+            ChatMessageContent::Text(value.clone())
+        }
+        ToolResultOutput::Json { value, .. } | ToolResultOutput::ErrorJson { value, .. } => {
+            ChatMessageContent::Text(serde_json::to_string(value).unwrap_or_default())
+        }
+        ToolResultOutput::ExecutionDenied { reason, .. } => ChatMessageContent::Text(
+            reason
+                .clone()
+                .unwrap_or_else(|| "Tool execution denied.".to_string()),
+        ),
+        ToolResultOutput::Content { value, .. } => {
+            let image = |url: String| OpenAIContentPart::ImageUrl {
+                image_url: ImageUrl { url, detail: None },
+            };
+            let parts = value
+                .iter()
+                .filter_map(|part| match part {
+                    ContentPart::Text { text, .. } => {
+                        Some(OpenAIContentPart::Text { text: text.clone() })
+                    }
+                    ContentPart::ImageUrl { url, .. } => Some(image(url.clone())),
+                    ContentPart::ImageData {
+                        data, media_type, ..
+                    } => Some(image(format!("data:{};base64,{}", media_type, data))),
+                    ContentPart::FileData {
+                        data, media_type, ..
+                    } if media_type.starts_with("image/") => {
+                        Some(image(format!("data:{};base64,{}", media_type, data)))
+                    }
+                    ContentPart::FileData { media_type, .. } => {
+                        warn!(
+                            "Unsupported media type in tool result for the OpenAI chat API: {}",
+                            media_type
+                        );
+                        None
+                    }
+                    ContentPart::FileUrl { url, .. } => {
+                        warn!(
+                            "File URLs in tool results are not supported by the OpenAI chat API: {}",
+                            url
+                        );
+                        None
+                    }
+                })
+                .collect();
+            ChatMessageContent::Parts(parts)
+        }
+    }
+}
+
 #[async_trait]
 impl LanguageModel for OpenAIChatModel {
     fn provider(&self) -> &str {
@@ -482,6 +550,10 @@ impl LanguageModel for OpenAIChatModel {
             (options.max_output_tokens, openai_opts.max_completion_tokens)
         };
 
+        if options.top_k.is_some() {
+            warn!("topK is not supported by the OpenAI chat API and will be ignored");
+        }
+
         // Validate service_tier for flex processing
         let service_tier = match &openai_opts.service_tier {
             Some(tier) if tier == "flex" && !supports_flex_processing(&self.model_id) => {
@@ -499,6 +571,11 @@ impl LanguageModel for OpenAIChatModel {
             messages: self.convert_prompt_to_messages(&options.prompt),
             temperature,
             max_tokens,
+            top_p: options.top_p,
+            frequency_penalty: options.frequency_penalty,
+            presence_penalty: options.presence_penalty,
+            stop: options.stop_sequences.clone(),
+            seed: options.seed,
             stream: Some(false),
             tools: options.tools.as_ref().map(|t| self.convert_tools(t)),
             tool_choice: options
@@ -529,9 +606,10 @@ impl LanguageModel for OpenAIChatModel {
         };
 
         // Build request with custom headers
+        let url = format!("{}/chat/completions", self.base_url);
         let mut request_builder = self
             .client
-            .post(format!("{}/chat/completions", self.base_url))
+            .post(&url)
             .header("Authorization", format!("Bearer {}", self.api_key))
             .header("Content-Type", "application/json")
             .json(&request);
@@ -543,13 +621,14 @@ impl LanguageModel for OpenAIChatModel {
             }
         }
 
-        let response = request_builder.send().await?;
+        let abort_signal = options.abort_signal.clone();
+        let response = abortable(abort_signal.as_ref(), request_builder.send())
+            .await?
+            .map_err(|e| network_error(&url, e))?;
 
         if !response.status().is_success() {
-            return Err(Box::new(crate::error::OpenAIError::ApiError {
-                message: format!("API returned status {}", response.status()),
-                status_code: Some(response.status().as_u16()),
-            }));
+            let error = abortable(abort_signal.as_ref(), response_error(&url, response)).await?;
+            return Err(Box::new(error));
         }
 
         // Capture headers before consuming response
@@ -559,7 +638,8 @@ impl LanguageModel for OpenAIChatModel {
             .map(|(k, v)| (k.as_str().to_string(), v.to_str().unwrap_or("").to_string()))
             .collect();
 
-        let api_response: crate::api_types::ChatCompletionResponse = response.json().await?;
+        let api_response: crate::api_types::ChatCompletionResponse =
+            abortable(abort_signal.as_ref(), response.json()).await??;
 
         let choice = &api_response.choices[0];
 
@@ -746,6 +826,10 @@ impl LanguageModel for OpenAIChatModel {
             (options.max_output_tokens, openai_opts.max_completion_tokens)
         };
 
+        if options.top_k.is_some() {
+            warn!("topK is not supported by the OpenAI chat API and will be ignored");
+        }
+
         // Validate service_tier for flex processing
         let service_tier = match &openai_opts.service_tier {
             Some(tier) if tier == "flex" && !supports_flex_processing(&self.model_id) => {
@@ -763,6 +847,11 @@ impl LanguageModel for OpenAIChatModel {
             messages: self.convert_prompt_to_messages(&options.prompt),
             temperature,
             max_tokens,
+            top_p: options.top_p,
+            frequency_penalty: options.frequency_penalty,
+            presence_penalty: options.presence_penalty,
+            stop: options.stop_sequences.clone(),
+            seed: options.seed,
             stream: Some(true),
             tools: options.tools.as_ref().map(|t| self.convert_tools(t)),
             tool_choice: options
@@ -795,9 +884,10 @@ impl LanguageModel for OpenAIChatModel {
         };
 
         // Build request with custom headers
+        let url = format!("{}/chat/completions", self.base_url);
         let mut request_builder = self
             .client
-            .post(format!("{}/chat/completions", self.base_url))
+            .post(&url)
             .header("Authorization", format!("Bearer {}", self.api_key))
             .header("Content-Type", "application/json")
             .json(&request);
@@ -809,14 +899,14 @@ impl LanguageModel for OpenAIChatModel {
             }
         }
 
-        let response = request_builder.send().await?;
+        let abort_signal = options.abort_signal.clone();
+        let response = abortable(abort_signal.as_ref(), request_builder.send())
+            .await?
+            .map_err(|e| network_error(&url, e))?;
 
-        let status = response.status();
-        if !status.is_success() {
-            return Err(Box::new(crate::error::OpenAIError::ApiError {
-                message: format!("API returned status {}", status),
-                status_code: Some(status.as_u16()),
-            }));
+        if !response.status().is_success() {
+            let error = abortable(abort_signal.as_ref(), response_error(&url, response)).await?;
+            return Err(Box::new(error));
         }
 
         let stream_impl = stream! {
@@ -826,7 +916,16 @@ impl LanguageModel for OpenAIChatModel {
             let mut accumulated_usage: Option<Usage> = None;
             let mut last_finish_reason: Option<FinishReason> = None;
 
-            while let Some(chunk_result) = byte_stream.next().await {
+            loop {
+                let chunk_result = match abortable(abort_signal.as_ref(), byte_stream.next()).await {
+                    Ok(Some(chunk_result)) => chunk_result,
+                    Ok(None) => break,
+                    Err(e) => {
+                        yield Err(e.into());
+                        return;
+                    }
+                };
+
                 match chunk_result {
                     Ok(bytes) => {
                         buffer.push_str(&String::from_utf8_lossy(&bytes));
@@ -967,7 +1066,7 @@ impl LanguageModel for OpenAIChatModel {
                         }
                     }
                     Err(e) => {
-                        yield Err(StreamError::Other(format!("Stream error: {}", e)));
+                        yield Err(stream_error(&url, e));
                         break;
                     }
                 }
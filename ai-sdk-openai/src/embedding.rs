@@ -3,6 +3,8 @@ use async_trait::async_trait;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 
+use crate::error::{network_error, response_error};
+
 /// OpenAI implementation of embedding model.
 pub struct OpenAIEmbeddingModel {
     model_id: String,
@@ -147,7 +149,7 @@ impl EmbeddingModel<String> for OpenAIEmbeddingModel {
             }
         }
 
-        let response = request.send().await?;
+        let response = request.send().await.map_err(|e| network_error(&url, e))?;
 
         let status = response.status();
         let response_headers: std::collections::HashMap<String, String> = response
@@ -157,8 +159,7 @@ impl EmbeddingModel<String> for OpenAIEmbeddingModel {
             .collect();
 
         if !status.is_success() {
-            let error_text = response.text().await?;
-            return Err(format!("API error {}: {}", status, error_text).into());
+            return Err(Box::new(response_error(&url, response).await));
         }
 
         let response_body = response.text().await?;
@@ -1,6 +1,14 @@
+use ai_sdk_provider::language_model::StreamError;
+use ai_sdk_provider::{ApiCallError, SharedHeaders};
+use serde::Deserialize;
+use std::time::Duration;
 use thiserror::Error;
 
 /// Errors that can occur when using the OpenAI provider.
+///
+/// Errors caused by a failed API response carry the underlying
+/// [`ApiCallError`] as their source, so generic code can inspect them with
+/// [`ApiCallError::find`].
 #[derive(Error, Debug)]
 pub enum OpenAIError {
     /// API error from OpenAI service.
@@ -10,17 +18,74 @@ pub enum OpenAIError {
         message: String,
         /// HTTP status code if available.
         status_code: Option<u16>,
+        /// Error type from the response body (e.g. `invalid_request_error`).
+        error_type: Option<String>,
+        /// Error code from the response body (e.g. `model_not_found`).
+        code: Option<String>,
+        /// Request parameter the error relates to, if any.
+        param: Option<String>,
+        /// The failed API call.
+        #[source]
+        source: Option<ApiCallError>,
     },
 
-    /// Authentication failed with the provided API key.
-    #[error("Authentication failed")]
-    AuthenticationError,
+    /// Authentication failed with the provided API key (401) or the key
+    /// lacks permission for the resource (403).
+    #[error("Authentication failed: {message}")]
+    AuthenticationError {
+        /// Error message from the API.
+        message: String,
+        /// HTTP status code (401 or 403).
+        status_code: u16,
+        /// Error type from the response body (e.g. `invalid_request_error`).
+        error_type: Option<String>,
+        /// Error code from the response body (e.g. `invalid_api_key`).
+        code: Option<String>,
+        /// Request parameter the error relates to, if any.
+        param: Option<String>,
+        /// The failed API call.
+        #[source]
+        source: ApiCallError,
+    },
 
     /// Rate limit exceeded for the API.
-    #[error("Rate limit exceeded")]
+    #[error("Rate limit exceeded: {message}")]
     RateLimitError {
-        /// Number of seconds to wait before retrying.
-        retry_after: Option<u64>,
+        /// Error message from the API.
+        message: String,
+        /// How long to wait before retrying, from the `retry-after-ms` or
+        /// `retry-after` header.
+        retry_after: Option<Duration>,
+        /// Rate limit state from the `x-ratelimit-*` headers.
+        rate_limit: RateLimitInfo,
+        /// Error type from the response body (e.g. `requests`).
+        error_type: Option<String>,
+        /// Error code from the response body (e.g. `rate_limit_exceeded` or
+        /// `insufficient_quota`).
+        code: Option<String>,
+        /// Request parameter the error relates to, if any.
+        param: Option<String>,
+        /// The failed API call.
+        #[source]
+        source: ApiCallError,
+    },
+
+    /// OpenAI failed to process a valid request (5xx).
+    #[error("Server error: {message}")]
+    ServerError {
+        /// Error message from the API.
+        message: String,
+        /// HTTP status code.
+        status_code: u16,
+        /// Error type from the response body.
+        error_type: Option<String>,
+        /// Error code from the response body.
+        code: Option<String>,
+        /// Request parameter the error relates to, if any.
+        param: Option<String>,
+        /// The failed API call.
+        #[source]
+        source: ApiCallError,
     },
 
     /// Network error occurred during request.
@@ -35,3 +100,343 @@ pub enum OpenAIError {
     #[error("Invalid response")]
     InvalidResponse,
 }
+
+/// Rate limit state reported in the `x-ratelimit-*` response headers.
+///
+/// Reset values are kept as sent by the API (e.g. `"1s"` or `"6m0s"`).
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RateLimitInfo {
+    /// Maximum number of requests allowed in the current window.
+    pub limit_requests: Option<u64>,
+    /// Maximum number of tokens allowed in the current window.
+    pub limit_tokens: Option<u64>,
+    /// Remaining requests in the current window.
+    pub remaining_requests: Option<u64>,
+    /// Remaining tokens in the current window.
+    pub remaining_tokens: Option<u64>,
+    /// Time until the request limit resets.
+    pub reset_requests: Option<String>,
+    /// Time until the token limit resets.
+    pub reset_tokens: Option<String>,
+}
+
+impl RateLimitInfo {
+    fn from_api_call_error(error: &ApiCallError) -> Self {
+        let number = |name: &str| error.header(name).and_then(|v| v.trim().parse().ok());
+        let text = |name: &str| error.header(name).map(str::to_string);
+
+        Self {
+            limit_requests: number("x-ratelimit-limit-requests"),
+            limit_tokens: number("x-ratelimit-limit-tokens"),
+            remaining_requests: number("x-ratelimit-remaining-requests"),
+            remaining_tokens: number("x-ratelimit-remaining-tokens"),
+            reset_requests: text("x-ratelimit-reset-requests"),
+            reset_tokens: text("x-ratelimit-reset-tokens"),
+        }
+    }
+}
+
+impl OpenAIError {
+    /// Returns the failed API call behind this error, if any
+    pub fn api_call_error(&self) -> Option<&ApiCallError> {
+        match self {
+            OpenAIError::ApiError { source, .. } => source.as_ref(),
+            OpenAIError::AuthenticationError { source, .. }
+            | OpenAIError::RateLimitError { source, .. }
+            | OpenAIError::ServerError { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+
+    /// Returns the error code from the response body, if any
+    ///
+    /// Tells apart failures that share a status code, such as
+    /// `insufficient_quota` and `rate_limit_exceeded` on a 429.
+    pub fn code(&self) -> Option<&str> {
+        match self {
+            OpenAIError::ApiError { code, .. }
+            | OpenAIError::AuthenticationError { code, .. }
+            | OpenAIError::RateLimitError { code, .. }
+            | OpenAIError::ServerError { code, .. } => code.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Classifies a failed API call by status code, using the message, type,
+    /// code and param from the `{"error": {...}}` response body when present
+    fn from_api_call_error(mut error: ApiCallError) -> Self {
+        let body = error
+            .response_body
+            .as_deref()
+            .and_then(|body| serde_json::from_str::<ErrorResponse>(body).ok())
+            .map(|response| response.error);
+
+        let message = body
+            .as_ref()
+            .map(|body| body.message.clone())
+            .filter(|message| !message.is_empty())
+            .unwrap_or_else(|| error.message.clone());
+        error.message = message.clone();
+
+        let body = body.unwrap_or_default();
+        let error_type = body.error_type;
+        let code = body.code.and_then(json_to_string);
+        let param = body.param;
+
+        match error.status_code {
+            Some(status_code @ (401 | 403)) => OpenAIError::AuthenticationError {
+                message,
+                status_code,
+                error_type,
+                code,
+                param,
+                source: error,
+            },
+            Some(429) => OpenAIError::RateLimitError {
+                message,
+                retry_after: error.retry_after(),
+                rate_limit: RateLimitInfo::from_api_call_error(&error),
+                error_type,
+                code,
+                param,
+                source: error,
+            },
+            Some(status_code) if status_code >= 500 => OpenAIError::ServerError {
+                message,
+                status_code,
+                error_type,
+                code,
+                param,
+                source: error,
+            },
+            status_code => OpenAIError::ApiError {
+                message,
+                status_code,
+                error_type,
+                code,
+                param,
+                source: Some(error),
+            },
+        }
+    }
+}
+
+/// `{"error": {...}}` body returned by all OpenAI endpoints
+#[derive(Debug, Deserialize)]
+struct ErrorResponse {
+    error: ErrorBody,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ErrorBody {
+    #[serde(default)]
+    message: String,
+    #[serde(rename = "type", default)]
+    error_type: Option<String>,
+    #[serde(default)]
+    param: Option<String>,
+    /// Usually a string, but some endpoints send a number
+    #[serde(default)]
+    code: Option<serde_json::Value>,
+}
+
+fn json_to_string(value: serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::Null => None,
+        serde_json::Value::String(s) => Some(s),
+        other => Some(other.to_string()),
+    }
+}
+
+/// Collects response headers into a map
+pub(crate) fn response_headers(response: &reqwest::Response) -> SharedHeaders {
+    response
+        .headers()
+        .iter()
+        .map(|(k, v)| (k.as_str().to_string(), v.to_str().unwrap_or("").to_string()))
+        .collect()
+}
+
+/// Builds an [`OpenAIError`] from a non-success response, consuming its body
+pub(crate) async fn response_error(url: &str, response: reqwest::Response) -> OpenAIError {
+    let status = response.status();
+    let headers = response_headers(&response);
+    let body = response.text().await.unwrap_or_default();
+
+    let error = ApiCallError::new(format!("API returned status {}", status), url)
+        .with_status_code(status.as_u16())
+        .with_response_headers(headers)
+        .with_response_body(body);
+
+    OpenAIError::from_api_call_error(error)
+}
+
+/// Builds an [`ApiCallError`] for a request that failed before a response
+/// was received
+///
+/// Connection failures and timeouts are retryable.
+pub(crate) fn network_error(url: &str, error: reqwest::Error) -> ApiCallError {
+    let is_retryable = error.is_connect() || error.is_timeout();
+    ApiCallError::new(format!("Network error: {}", error), url).with_retryable(is_retryable)
+}
+
+/// Builds a [`StreamError`] for a response body that failed while streaming
+///
+/// A body that breaks off mid-stream is usually a dropped connection, so it
+/// is flagged as retryable.
+pub(crate) fn stream_error(url: &str, error: reqwest::Error) -> StreamError {
+    let is_retryable = error.is_body() || error.is_connect() || error.is_timeout();
+    StreamError::ApiCall(ApiCallError::new(error.to_string(), url).with_retryable(is_retryable))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn failed_call(status_code: u16, headers: &[(&str, &str)], body: &str) -> ApiCallError {
+        ApiCallError::new(
+            "API returned status",
+            "https://api.openai.com/v1/chat/completions",
+        )
+        .with_status_code(status_code)
+        .with_response_headers(
+            headers
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect::<HashMap<_, _>>(),
+        )
+        .with_response_body(body)
+    }
+
+    #[test]
+    fn test_invalid_request_error() {
+        let body = r#"{"error":{"message":"Invalid model","type":"invalid_request_error","param":"model","code":"model_not_found"}}"#;
+        let error = OpenAIError::from_api_call_error(failed_call(404, &[], body));
+
+        match &error {
+            OpenAIError::ApiError {
+                message,
+                status_code,
+                error_type,
+                code,
+                param,
+                ..
+            } => {
+                assert_eq!(message, "Invalid model");
+                assert_eq!(*status_code, Some(404));
+                assert_eq!(error_type.as_deref(), Some("invalid_request_error"));
+                assert_eq!(code.as_deref(), Some("model_not_found"));
+                assert_eq!(param.as_deref(), Some("model"));
+            }
+            other => panic!("Expected ApiError, got {:?}", other),
+        }
+        assert_eq!(error.to_string(), "API error: Invalid model");
+        assert!(!error.api_call_error().unwrap().is_retryable);
+    }
+
+    #[test]
+    fn test_authentication_error() {
+        let body = r#"{"error":{"message":"Incorrect API key provided","type":"invalid_request_error","code":"invalid_api_key"}}"#;
+        for status in [401, 403] {
+            let error = OpenAIError::from_api_call_error(failed_call(status, &[], body));
+            assert!(matches!(
+                error,
+                OpenAIError::AuthenticationError { status_code, .. } if status_code == status
+            ));
+            assert_eq!(
+                error.to_string(),
+                "Authentication failed: Incorrect API key provided"
+            );
+        }
+    }
+
+    #[test]
+    fn test_rate_limit_error() {
+        let headers = [
+            ("retry-after", "20"),
+            ("x-ratelimit-limit-requests", "500"),
+            ("x-ratelimit-remaining-requests", "0"),
+            ("x-ratelimit-reset-requests", "120ms"),
+            ("x-ratelimit-limit-tokens", "30000"),
+            ("x-ratelimit-remaining-tokens", "29000"),
+            ("x-ratelimit-reset-tokens", "2s"),
+        ];
+        let body = r#"{"error":{"message":"Rate limit reached","type":"requests","code":"rate_limit_exceeded"}}"#;
+        let error = OpenAIError::from_api_call_error(failed_call(429, &headers, body));
+
+        match &error {
+            OpenAIError::RateLimitError {
+                retry_after,
+                rate_limit,
+                source,
+                ..
+            } => {
+                assert_eq!(*retry_after, Some(Duration::from_secs(20)));
+                assert_eq!(
+                    *rate_limit,
+                    RateLimitInfo {
+                        limit_requests: Some(500),
+                        limit_tokens: Some(30000),
+                        remaining_requests: Some(0),
+                        remaining_tokens: Some(29000),
+                        reset_requests: Some("120ms".into()),
+                        reset_tokens: Some("2s".into()),
+                    }
+                );
+                assert!(source.is_retryable);
+                assert_eq!(source.message, "Rate limit reached");
+            }
+            other => panic!("Expected RateLimitError, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_insufficient_quota_error() {
+        let body = r#"{"error":{"message":"You exceeded your current quota","type":"insufficient_quota","param":null,"code":"insufficient_quota"}}"#;
+        let error = OpenAIError::from_api_call_error(failed_call(429, &[], body));
+
+        match &error {
+            OpenAIError::RateLimitError {
+                error_type,
+                code,
+                param,
+                ..
+            } => {
+                assert_eq!(error_type.as_deref(), Some("insufficient_quota"));
+                assert_eq!(code.as_deref(), Some("insufficient_quota"));
+                assert_eq!(*param, None);
+            }
+            other => panic!("Expected RateLimitError, got {:?}", other),
+        }
+        assert_eq!(error.code(), Some("insufficient_quota"));
+    }
+
+    #[test]
+    fn test_retry_after_ms_takes_precedence() {
+        let headers = [("retry-after-ms", "1500"), ("retry-after", "2")];
+        let error = OpenAIError::from_api_call_error(failed_call(429, &headers, ""));
+        assert!(matches!(
+            error,
+            OpenAIError::RateLimitError { retry_after: Some(d), .. } if d == Duration::from_millis(1500)
+        ));
+    }
+
+    #[test]
+    fn test_server_error_without_json_body() {
+        let error = OpenAIError::from_api_call_error(failed_call(502, &[], "Bad Gateway"));
+
+        assert!(matches!(
+            error,
+            OpenAIError::ServerError {
+                status_code: 502,
+                ..
+            }
+        ));
+        assert_eq!(error.to_string(), "Server error: API returned status");
+        assert_eq!(
+            error.api_call_error().unwrap().response_body.as_deref(),
+            Some("Bad Gateway")
+        );
+    }
+}
@@ -4,6 +4,8 @@ use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use crate::error::{network_error, response_error};
+
 /// OpenAI implementation of image model.
 pub struct OpenAIImageModel {
     model_id: String,
@@ -144,7 +146,7 @@ impl ImageModel for OpenAIImageModel {
             }
         }
 
-        let response = request.send().await?;
+        let response = request.send().await.map_err(|e| network_error(&url, e))?;
 
         let status = response.status();
         let response_headers: HashMap<String, String> = response
@@ -154,8 +156,7 @@ impl ImageModel for OpenAIImageModel {
             .collect();
 
         if !status.is_success() {
-            let error_text = response.text().await?;
-            return Err(format!("API error {}: {}", status, error_text).into());
+            return Err(Box::new(response_error(&url, response).await));
         }
 
         let api_response: ImageApiResponse = response.json().await?;
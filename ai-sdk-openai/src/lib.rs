@@ -7,6 +7,8 @@
 //! ## Features
 //!
 //! - **GPT Models** - GPT-4, GPT-3.5 Turbo chat completion
+//! - **Responses API** - Reasoning and built-in tools (web search, file search,
+//!   code interpreter, image generation, local shell, MCP)
 //! - **Embeddings** - text-embedding-3-small, text-embedding-3-large
 //! - **Image Generation** - DALL-E 2, DALL-E 3
 //! - **Speech Synthesis** - TTS-1, TTS-1-HD
@@ -52,15 +54,19 @@ mod image;
 pub mod model_detection;
 mod multimodal;
 mod provider;
+mod responses;
+mod responses_api_types;
 mod speech;
+pub mod tools;
 mod transcription;
 
 pub use chat::OpenAIChatModel;
 pub use embedding::OpenAIEmbeddingModel;
-pub use error::OpenAIError;
+pub use error::{OpenAIError, RateLimitInfo};
 pub use image::OpenAIImageModel;
 pub use multimodal::{convert_audio_part, convert_image_part, MultimodalError, OpenAIContentPart};
 pub use provider::OpenAIProvider;
+pub use responses::OpenAIResponsesModel;
 pub use speech::OpenAISpeechModel;
 pub use transcription::OpenAITranscriptionModel;
 
@@ -71,6 +77,14 @@ pub fn openai(model_id: impl Into<String>, api_key: impl Into<String>) -> OpenAI
     OpenAIChatModel::new(model_id, api_key)
 }
 
+/// Creates a new OpenAI Responses API model with the specified model ID and API key.
+pub fn openai_responses(
+    model_id: impl Into<String>,
+    api_key: impl Into<String>,
+) -> OpenAIResponsesModel {
+    OpenAIResponsesModel::new(model_id, api_key)
+}
+
 /// Creates a new OpenAI embedding model with the specified model ID and API key.
 pub fn openai_embedding(
     model_id: impl Into<String>,
@@ -0,0 +1,1359 @@
+//! OpenAI Responses API (`/v1/responses`) language model.
+//!
+//! Unlike the chat completions endpoint, the Responses API returns a list of
+//! typed output items (messages, reasoning, function calls and built-in tool
+//! calls such as web search or code interpreter). Built-in tools run on
+//! OpenAI's side, so their calls and results are surfaced together with
+//! `provider_executed: Some(true)`. See [`crate::tools`] for typed
+//! constructors and result types for these tools.
+
+use ai_sdk_provider::json_value::JsonValue;
+use ai_sdk_provider::language_model::{
+    AssistantContentPart, CallWarning, FileData, Message, ReasoningPart, RequestInfo, ResponseInfo,
+    ResponseMetadata, SourcePart, SourceType, TextPart, ToolCallPart, ToolResultOutput,
+    ToolResultPart, UserContentPart,
+};
+use ai_sdk_provider::*;
+use async_stream::stream;
+use async_trait::async_trait;
+use futures::stream::StreamExt;
+use reqwest::Client;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use crate::error::{network_error, response_error, stream_error};
+use crate::model_detection::is_reasoning_model;
+use crate::responses_api_types::{
+    ResponsesAnnotation, ResponsesInputContentPart, ResponsesInputItem, ResponsesInputMessage,
+    ResponsesInputMessageContent, ResponsesInputSummaryPart, ResponsesInputTypedItem,
+    ResponsesOutputContent, ResponsesOutputItem, ResponsesReasoningConfig, ResponsesRequest,
+    ResponsesResponse, ResponsesStreamEvent, ResponsesTextConfig, ResponsesTextFormat,
+    ResponsesUsage,
+};
+use crate::tools::{
+    CodeInterpreterInput, CodeInterpreterResult, FileSearchResult, ImageGenerationResult,
+    LocalShellInput, McpCallResult, WebSearchResult,
+};
+
+// Simple ID generator for source parts
+static SOURCE_ID_COUNTER: AtomicU64 = AtomicU64::new(0);
+
+fn generate_source_id() -> String {
+    let id = SOURCE_ID_COUNTER.fetch_add(1, Ordering::SeqCst);
+    format!("source-{}", id)
+}
+
+/// Tool types that OpenAI executes itself and that can be forced via `tool_choice`.
+const BUILT_IN_TOOL_TYPES: &[&str] = &[
+    "web_search",
+    "web_search_preview",
+    "file_search",
+    "code_interpreter",
+    "image_generation",
+    "local_shell",
+    "mcp",
+];
+
+/// OpenAI implementation of the Responses API language model.
+pub struct OpenAIResponsesModel {
+    model_id: String,
+    api_key: String,
+    client: Client,
+    base_url: String,
+}
+
+impl OpenAIResponsesModel {
+    /// Creates a new Responses API model with the specified model ID and API key.
+    pub fn new(model_id: impl Into<String>, api_key: impl Into<String>) -> Self {
+        Self {
+            model_id: model_id.into(),
+            api_key: api_key.into(),
+            client: Client::new(),
+            base_url: "https://api.openai.com/v1".into(),
+        }
+    }
+
+    /// Configures a custom base URL for the API endpoint.
+    ///
+    /// This is primarily useful for testing with mock servers.
+    ///
+    /// # Example
+    /// ```rust
+    /// # use ai_sdk_openai::OpenAIResponsesModel;
+    /// let model = OpenAIResponsesModel::new("gpt-5", "api-key")
+    ///     .with_base_url("http://localhost:8080");
+    /// ```
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into();
+        self
+    }
+
+    fn build_request(
+        &self,
+        options: &CallOptions,
+        stream: bool,
+    ) -> (ResponsesRequest, Vec<CallWarning>) {
+        let mut warnings = Vec::new();
+        let openai_opts = extract_responses_options(&options.provider_options);
+        let reasoning_model = is_reasoning_model(&self.model_id);
+
+        // Stored responses can be referenced by item id instead of replayed
+        let store = openai_opts.store != Some(false);
+        let input = self.convert_prompt_to_input(&options.prompt, store, &mut warnings);
+
+        let (temperature, top_p) = if reasoning_model {
+            if options.temperature.is_some() {
+                warnings.push(unsupported_setting("temperature", "reasoning models"));
+            }
+            if options.top_p.is_some() {
+                warnings.push(unsupported_setting("topP", "reasoning models"));
+            }
+            (None, None)
+        } else {
+            (options.temperature, options.top_p)
+        };
+
+        for (setting, is_set) in [
+            ("topK", options.top_k.is_some()),
+            ("seed", options.seed.is_some()),
+            ("presencePenalty", options.presence_penalty.is_some()),
+            ("frequencyPenalty", options.frequency_penalty.is_some()),
+            ("stopSequences", options.stop_sequences.is_some()),
+        ] {
+            if is_set {
+                warnings.push(unsupported_setting(setting, "the Responses API"));
+            }
+        }
+
+        let format = options.response_format.as_ref().map(|rf| match rf {
+            language_model::ResponseFormat::Text => ResponsesTextFormat::Text,
+            language_model::ResponseFormat::Json {
+                schema: Some(schema),
+                name,
+                description,
+            } => ResponsesTextFormat::JsonSchema {
+                name: name.clone().unwrap_or_else(|| "response".to_string()),
+                description: description.clone(),
+                schema: schema.clone(),
+                strict: Some(openai_opts.strict_json_schema.unwrap_or(true)),
+            },
+            language_model::ResponseFormat::Json { schema: None, .. } => {
+                ResponsesTextFormat::JsonObject
+            }
+        });
+        let text = if format.is_some() || openai_opts.text_verbosity.is_some() {
+            Some(ResponsesTextConfig {
+                format,
+                verbosity: openai_opts.text_verbosity.clone(),
+            })
+        } else {
+            None
+        };
+
+        let reasoning =
+            if openai_opts.reasoning_effort.is_some() || openai_opts.reasoning_summary.is_some() {
+                Some(ResponsesReasoningConfig {
+                    effort: openai_opts.reasoning_effort.clone(),
+                    summary: openai_opts.reasoning_summary.clone(),
+                })
+            } else {
+                None
+            };
+
+        let tools = options
+            .tools
+            .as_ref()
+            .map(|tools| convert_tools(tools, &mut warnings));
+
+        let request = ResponsesRequest {
+            model: self.model_id.clone(),
+            input,
+            instructions: openai_opts.instructions,
+            temperature,
+            top_p,
+            max_output_tokens: options.max_output_tokens,
+            stream: if stream { Some(true) } else { None },
+            tools,
+            tool_choice: options.tool_choice.as_ref().map(convert_tool_choice),
+            text,
+            reasoning,
+            parallel_tool_calls: openai_opts.parallel_tool_calls,
+            previous_response_id: openai_opts.previous_response_id,
+            store: openai_opts.store,
+            user: openai_opts.user,
+            metadata: openai_opts.metadata,
+            service_tier: openai_opts.service_tier,
+            include: openai_opts.include,
+            max_tool_calls: openai_opts.max_tool_calls,
+            truncation: openai_opts.truncation,
+            prompt_cache_key: openai_opts.prompt_cache_key,
+            safety_identifier: openai_opts.safety_identifier,
+        };
+
+        (request, warnings)
+    }
+
+    fn convert_prompt_to_input(
+        &self,
+        prompt: &[Message],
+        store: bool,
+        warnings: &mut Vec<CallWarning>,
+    ) -> Vec<ResponsesInputItem> {
+        let mut input = Vec::new();
+
+        for msg in prompt {
+            match msg {
+                Message::System { content } => {
+                    // Reasoning models expect developer messages instead of system messages
+                    let role = if is_reasoning_model(&self.model_id) {
+                        "developer"
+                    } else {
+                        "system"
+                    };
+                    input.push(ResponsesInputItem::Message(ResponsesInputMessage {
+                        role: role.into(),
+                        content: ResponsesInputMessageContent::Text(content.clone()),
+                    }));
+                }
+                Message::User { content } => {
+                    let mut parts = Vec::new();
+
+                    for (index, part) in content.iter().enumerate() {
+                        match part {
+                            UserContentPart::Text { text } => {
+                                parts.push(ResponsesInputContentPart::InputText {
+                                    text: text.clone(),
+                                });
+                            }
+                            UserContentPart::File { data, media_type } => {
+                                if media_type.starts_with("image/") {
+                                    parts.push(ResponsesInputContentPart::InputImage {
+                                        image_url: file_data_to_url(data, media_type),
+                                    });
+                                } else if media_type == "application/pdf" {
+                                    parts.push(match data {
+                                        FileData::Url(url) => {
+                                            ResponsesInputContentPart::InputFile {
+                                                filename: None,
+                                                file_data: None,
+                                                file_url: Some(url.clone()),
+                                            }
+                                        }
+                                        FileData::Binary(_) => {
+                                            ResponsesInputContentPart::InputFile {
+                                                filename: Some(format!("part-{}.pdf", index)),
+                                                file_data: Some(file_data_to_url(data, media_type)),
+                                                file_url: None,
+                                            }
+                                        }
+                                    });
+                                } else {
+                                    warnings.push(CallWarning {
+                                        message: format!(
+                                            "Unsupported media type for the Responses API: {}",
+                                            media_type
+                                        ),
+                                    });
+                                }
+                            }
+                        }
+                    }
+
+                    input.push(ResponsesInputItem::Message(ResponsesInputMessage {
+                        role: "user".into(),
+                        content: ResponsesInputMessageContent::Parts(parts),
+                    }));
+                }
+                Message::Assistant { content } => {
+                    // Summary parts of one reasoning item share its item id
+                    let mut reasoning_items: HashMap<String, usize> = HashMap::new();
+
+                    for part in content {
+                        match part {
+                            AssistantContentPart::Text(text_part) => {
+                                input.push(ResponsesInputItem::Message(ResponsesInputMessage {
+                                    role: "assistant".into(),
+                                    content: ResponsesInputMessageContent::Parts(vec![
+                                        ResponsesInputContentPart::OutputText {
+                                            text: text_part.text.clone(),
+                                        },
+                                    ]),
+                                }));
+                            }
+                            AssistantContentPart::ToolCall(tool_call) => {
+                                // Built-in tool calls already live on OpenAI's side
+                                if tool_call.provider_executed == Some(true) {
+                                    continue;
+                                }
+
+                                if tool_call.tool_name == "local_shell" {
+                                    let action =
+                                        serde_json::from_str::<serde_json::Value>(&tool_call.input)
+                                            .ok()
+                                            .and_then(|v| v.get("action").cloned())
+                                            .unwrap_or(serde_json::Value::Null);
+                                    let id = item_id_from_metadata(&tool_call.provider_metadata)
+                                        .unwrap_or_else(|| tool_call.tool_call_id.clone());
+                                    input.push(ResponsesInputItem::Item(
+                                        ResponsesInputTypedItem::LocalShellCall {
+                                            id,
+                                            call_id: tool_call.tool_call_id.clone(),
+                                            action,
+                                        },
+                                    ));
+                                } else {
+                                    input.push(ResponsesInputItem::Item(
+                                        ResponsesInputTypedItem::FunctionCall {
+                                            call_id: tool_call.tool_call_id.clone(),
+                                            name: tool_call.tool_name.clone(),
+                                            arguments: tool_call.input.clone(),
+                                        },
+                                    ));
+                                }
+                            }
+                            AssistantContentPart::Reasoning(reasoning_part) => {
+                                let Some(id) =
+                                    item_id_from_metadata(&reasoning_part.provider_metadata)
+                                else {
+                                    warnings.push(CallWarning {
+                                        message: "Reasoning without an item id cannot be replayed"
+                                            .into(),
+                                    });
+                                    continue;
+                                };
+                                let summary = (!reasoning_part.reasoning.is_empty()).then(|| {
+                                    ResponsesInputSummaryPart::SummaryText {
+                                        text: reasoning_part.reasoning.clone(),
+                                    }
+                                });
+                                let encrypted = reasoning_encrypted_content_from_metadata(
+                                    &reasoning_part.provider_metadata,
+                                );
+
+                                if let Some(&index) = reasoning_items.get(&id) {
+                                    if let ResponsesInputItem::Item(
+                                        ResponsesInputTypedItem::Reasoning {
+                                            encrypted_content,
+                                            summary: parts,
+                                            ..
+                                        },
+                                    ) = &mut input[index]
+                                    {
+                                        parts.extend(summary);
+                                        if encrypted_content.is_none() {
+                                            *encrypted_content = encrypted;
+                                        }
+                                    }
+                                    continue;
+                                }
+
+                                reasoning_items.insert(id.clone(), input.len());
+                                let item = if store {
+                                    ResponsesInputTypedItem::ItemReference { id }
+                                } else {
+                                    ResponsesInputTypedItem::Reasoning {
+                                        id,
+                                        encrypted_content: encrypted,
+                                        summary: summary.into_iter().collect(),
+                                    }
+                                };
+                                input.push(ResponsesInputItem::Item(item));
+                            }
+                            // Files and provider tool results cannot be replayed as input items
+                            _ => {}
+                        }
+                    }
+                }
+                Message::Tool { content } => {
+                    for tool_result in content {
+                        let output = tool_output_to_string(&tool_result.output);
+                        let item = if tool_result.tool_name == "local_shell" {
+                            ResponsesInputTypedItem::LocalShellCallOutput {
+                                call_id: tool_result.tool_call_id.clone(),
+                                output,
+                            }
+                        } else {
+                            ResponsesInputTypedItem::FunctionCallOutput {
+                                call_id: tool_result.tool_call_id.clone(),
+                                output,
+                            }
+                        };
+                        input.push(ResponsesInputItem::Item(item));
+                    }
+                }
+            }
+        }
+
+        input
+    }
+
+    async fn send_request(
+        &self,
+        request: &ResponsesRequest,
+        options: &CallOptions,
+    ) -> Result<reqwest::Response, Box<dyn std::error::Error + Send + Sync>> {
+        let url = format!("{}/responses", self.base_url);
+        let mut request_builder = self
+            .client
+            .post(&url)
+            .header("Authorization", format!("Bearer {}", self.api_key))
+            .header("Content-Type", "application/json")
+            .json(request);
+
+        // Add custom headers if provided
+        if let Some(headers) = &options.headers {
+            for (key, value) in headers {
+                request_builder = request_builder.header(key, value);
+            }
+        }
+
+        let abort_signal = options.abort_signal.as_ref();
+        let response = abortable(abort_signal, request_builder.send())
+            .await?
+            .map_err(|e| network_error(&url, e))?;
+
+        if !response.status().is_success() {
+            let error = abortable(abort_signal, response_error(&url, response)).await?;
+            return Err(Box::new(error));
+        }
+
+        Ok(response)
+    }
+}
+
+#[async_trait]
+impl LanguageModel for OpenAIResponsesModel {
+    fn provider(&self) -> &str {
+        "openai"
+    }
+
+    fn model_id(&self) -> &str {
+        &self.model_id
+    }
+
+    async fn supported_urls(&self) -> HashMap<String, Vec<String>> {
+        let mut urls = HashMap::new();
+        urls.insert("image/*".into(), vec![r"^https?://.*$".into()]);
+        urls.insert("application/pdf".into(), vec![r"^https?://.*$".into()]);
+        urls
+    }
+
+    async fn do_generate(
+        &self,
+        options: CallOptions,
+    ) -> Result<GenerateResponse, Box<dyn std::error::Error + Send + Sync>> {
+        let (request, warnings) = self.build_request(&options, false);
+        let request_body = serde_json::to_value(&request).ok();
+
+        let response = self.send_request(&request, &options).await?;
+
+        // Capture headers before consuming response
+        let headers: HashMap<String, String> = response
+            .headers()
+            .iter()
+            .map(|(k, v)| (k.as_str().to_string(), v.to_str().unwrap_or("").to_string()))
+            .collect();
+
+        let body: serde_json::Value =
+            abortable(options.abort_signal.as_ref(), response.json()).await??;
+        let api_response: ResponsesResponse = serde_json::from_value(body.clone())?;
+
+        let mut has_function_call = false;
+        let content = api_response
+            .output
+            .iter()
+            .flat_map(|item| convert_output_item(item, &mut has_function_call))
+            .collect();
+
+        let finish_reason = map_finish_reason(
+            api_response
+                .incomplete_details
+                .as_ref()
+                .and_then(|d| d.reason.as_deref()),
+            has_function_call,
+        );
+
+        let metadata = response_metadata(&api_response);
+
+        Ok(GenerateResponse {
+            content,
+            finish_reason,
+            usage: api_response
+                .usage
+                .as_ref()
+                .map(convert_usage)
+                .unwrap_or_default(),
+            provider_metadata: Some(finish_metadata(&api_response.id)),
+            request: Some(RequestInfo { body: request_body }),
+            response: Some(ResponseInfo {
+                headers: Some(headers),
+                body: Some(body),
+                id: metadata.id,
+                timestamp: metadata.timestamp,
+                model_id: metadata.model_id,
+            }),
+            warnings,
+        })
+    }
+
+    async fn do_stream(
+        &self,
+        options: CallOptions,
+    ) -> Result<StreamResponse, Box<dyn std::error::Error + Send + Sync + 'static>> {
+        let (request, warnings) = self.build_request(&options, true);
+        let request_body = serde_json::to_value(&request).ok();
+        let include_raw_chunks = options.include_raw_chunks.unwrap_or(false);
+
+        let response = self.send_request(&request, &options).await?;
+        let url = response.url().to_string();
+        let abort_signal = options.abort_signal.clone();
+
+        let headers: HashMap<String, String> = response
+            .headers()
+            .iter()
+            .map(|(k, v)| (k.as_str().to_string(), v.to_str().unwrap_or("").to_string()))
+            .collect();
+
+        let stream_impl = stream! {
+            yield Ok(StreamPart::StreamStart { warnings });
+
+            let mut byte_stream = response.bytes_stream();
+            let mut buffer = String::new();
+            let mut response_id: Option<String> = None;
+            let mut usage = Usage::default();
+            let mut finish_reason = FinishReason::Unknown;
+            let mut has_function_call = false;
+            // Function call ids by output index, for argument deltas
+            let mut function_calls: HashMap<usize, String> = HashMap::new();
+            // Number of reasoning summary parts started per reasoning item
+            let mut reasoning_parts: HashMap<String, u32> = HashMap::new();
+
+            'outer: loop {
+                let chunk_result = match abortable(abort_signal.as_ref(), byte_stream.next()).await {
+                    Ok(Some(chunk_result)) => chunk_result,
+                    Ok(None) => break,
+                    Err(e) => {
+                        yield Err(e.into());
+                        return;
+                    }
+                };
+                let bytes = match chunk_result {
+                    Ok(bytes) => bytes,
+                    Err(e) => {
+                        yield Err(stream_error(&url, e));
+                        break;
+                    }
+                };
+                buffer.push_str(&String::from_utf8_lossy(&bytes));
+
+                // Process SSE lines
+                while let Some(line_end) = buffer.find('\n') {
+                    let line = buffer[..line_end].trim().to_string();
+                    buffer.drain(..line_end + 1);
+
+                    let Some(data) = line.strip_prefix("data: ") else {
+                        continue;
+                    };
+                    if data == "[DONE]" {
+                        break 'outer;
+                    }
+
+                    let Ok(raw_value) = serde_json::from_str::<serde_json::Value>(data) else {
+                        continue;
+                    };
+                    if include_raw_chunks {
+                        yield Ok(StreamPart::Raw { raw_value: raw_value.clone() });
+                    }
+                    let Ok(event) = serde_json::from_value::<ResponsesStreamEvent>(raw_value) else {
+                        continue;
+                    };
+
+                    match event {
+                        ResponsesStreamEvent::ResponseCreated { response } => {
+                            response_id = Some(response.id.clone());
+                            yield Ok(StreamPart::ResponseMetadata {
+                                metadata: response_metadata(&response),
+                            });
+                        }
+                        ResponsesStreamEvent::ResponseCompleted { response }
+                        | ResponsesStreamEvent::ResponseIncomplete { response } => {
+                            if let Some(u) = &response.usage {
+                                usage = convert_usage(u);
+                            }
+                            finish_reason = map_finish_reason(
+                                response
+                                    .incomplete_details
+                                    .as_ref()
+                                    .and_then(|d| d.reason.as_deref()),
+                                has_function_call,
+                            );
+                        }
+                        ResponsesStreamEvent::ResponseFailed { response } => {
+                            finish_reason = FinishReason::Error;
+                            let message = response
+                                .pointer("/error/message")
+                                .and_then(|m| m.as_str())
+                                .unwrap_or("Response failed")
+                                .to_string();
+                            yield Ok(StreamPart::Error { error: message });
+                        }
+                        ResponsesStreamEvent::Error { message } => {
+                            finish_reason = FinishReason::Error;
+                            yield Ok(StreamPart::Error { error: message });
+                        }
+                        ResponsesStreamEvent::OutputItemAdded { output_index, item } => {
+                            match item {
+                                ResponsesOutputItem::Message { id, .. } => {
+                                    yield Ok(StreamPart::TextStart {
+                                        provider_metadata: Some(item_metadata(&id, None)),
+                                        id,
+                                    });
+                                }
+                                ResponsesOutputItem::Reasoning { id, .. } => {
+                                    yield Ok(StreamPart::ReasoningStart {
+                                        id: format!("{}:0", id),
+                                        provider_metadata: Some(item_metadata(&id, None)),
+                                    });
+                                    reasoning_parts.insert(id, 1);
+                                }
+                                ResponsesOutputItem::FunctionCall { call_id, name, .. } => {
+                                    function_calls.insert(output_index, call_id.clone());
+                                    yield Ok(StreamPart::ToolInputStart {
+                                        id: call_id,
+                                        tool_name: name,
+                                        provider_metadata: None,
+                                        provider_executed: None,
+                                        dynamic: None,
+                                        title: None,
+                                    });
+                                }
+                                ResponsesOutputItem::CodeInterpreterCall { id, .. } => {
+                                    yield Ok(StreamPart::ToolInputStart {
+                                        id,
+                                        tool_name: "code_interpreter".into(),
+                                        provider_metadata: None,
+                                        provider_executed: Some(true),
+                                        dynamic: None,
+                                        title: None,
+                                    });
+                                }
+                                _ => {}
+                            }
+                        }
+                        ResponsesStreamEvent::OutputTextDelta { item_id, delta } => {
+                            yield Ok(StreamPart::TextDelta {
+                                id: item_id,
+                                delta,
+                                provider_metadata: None,
+                            });
+                        }
+                        ResponsesStreamEvent::OutputTextAnnotationAdded { annotation, .. } => {
+                            if let Some(source) = convert_annotation(&annotation) {
+                                yield Ok(StreamPart::Source(source));
+                            }
+                        }
+                        // The first summary part is opened together with the reasoning item
+                        ResponsesStreamEvent::ReasoningSummaryPartAdded { item_id, summary_index }
+                            if summary_index > 0 =>
+                        {
+                            reasoning_parts.insert(item_id.clone(), summary_index + 1);
+                            yield Ok(StreamPart::ReasoningStart {
+                                id: format!("{}:{}", item_id, summary_index),
+                                provider_metadata: Some(item_metadata(&item_id, None)),
+                            });
+                        }
+                        ResponsesStreamEvent::ReasoningSummaryTextDelta { item_id, summary_index, delta } => {
+                            yield Ok(StreamPart::ReasoningDelta {
+                                id: format!("{}:{}", item_id, summary_index),
+                                delta,
+                                provider_metadata: None,
+                            });
+                        }
+                        ResponsesStreamEvent::FunctionCallArgumentsDelta { output_index, delta } => {
+                            if let Some(call_id) = function_calls.get(&output_index) {
+                                yield Ok(StreamPart::ToolInputDelta {
+                                    id: call_id.clone(),
+                                    delta,
+                                    provider_metadata: None,
+                                });
+                            }
+                        }
+                        ResponsesStreamEvent::CodeInterpreterCallCodeDelta { item_id, delta } => {
+                            yield Ok(StreamPart::ToolInputDelta {
+                                id: item_id,
+                                delta,
+                                provider_metadata: None,
+                            });
+                        }
+                        ResponsesStreamEvent::CodeInterpreterCallCodeDone { item_id } => {
+                            yield Ok(StreamPart::ToolInputEnd {
+                                id: item_id,
+                                provider_metadata: None,
+                            });
+                        }
+                        ResponsesStreamEvent::OutputItemDone { item, .. } => {
+                            match &item {
+                                ResponsesOutputItem::Message { id, .. } => {
+                                    yield Ok(StreamPart::TextEnd {
+                                        id: id.clone(),
+                                        provider_metadata: None,
+                                    });
+                                }
+                                ResponsesOutputItem::Reasoning { id, encrypted_content, .. } => {
+                                    let parts = reasoning_parts.remove(id).unwrap_or(1);
+                                    for index in 0..parts {
+                                        yield Ok(StreamPart::ReasoningEnd {
+                                            id: format!("{}:{}", id, index),
+                                            provider_metadata: Some(item_metadata(
+                                                id,
+                                                encrypted_content.as_deref(),
+                                            )),
+                                        });
+                                    }
+                                }
+                                ResponsesOutputItem::FunctionCall { call_id, .. } => {
+                                    yield Ok(StreamPart::ToolInputEnd {
+                                        id: call_id.clone(),
+                                        provider_metadata: None,
+                                    });
+                                }
+                                _ => {}
+                            }
+
+                            // Text and reasoning were already streamed as deltas
+                            if !matches!(
+                                item,
+                                ResponsesOutputItem::Message { .. }
+                                    | ResponsesOutputItem::Reasoning { .. }
+                            ) {
+                                for content in convert_output_item(&item, &mut has_function_call) {
+                                    match content {
+                                        Content::ToolCall(call) => yield Ok(StreamPart::ToolCall(call)),
+                                        Content::ToolResult(result) => {
+                                            yield Ok(StreamPart::ToolResult(result))
+                                        }
+                                        Content::File(file) => yield Ok(StreamPart::File(file)),
+                                        Content::Source(source) => yield Ok(StreamPart::Source(source)),
+                                        Content::Text(_) | Content::Reasoning(_) => {}
+                                    }
+                                }
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+
+            yield Ok(StreamPart::Finish {
+                usage,
+                finish_reason,
+                provider_metadata: response_id.as_deref().map(finish_metadata),
+            });
+        };
+
+        Ok(StreamResponse {
+            stream: Box::pin(stream_impl),
+            request: Some(RequestInfo { body: request_body }),
+            response: Some(ResponseInfo {
+                headers: Some(headers),
+                body: None,
+                id: None,
+                timestamp: None,
+                model_id: None,
+            }),
+        })
+    }
+}
+
+/// Converts a single Responses API output item into language model content.
+fn convert_output_item(item: &ResponsesOutputItem, has_function_call: &mut bool) -> Vec<Content> {
+    let mut content = Vec::new();
+
+    match item {
+        ResponsesOutputItem::Message { id, content: parts } => {
+            for part in parts {
+                match part {
+                    ResponsesOutputContent::OutputText { text, annotations } => {
+                        content.push(Content::Text(TextPart {
+                            text: text.clone(),
+                            provider_metadata: Some(item_metadata(id, None)),
+                        }));
+                        content.extend(
+                            annotations
+                                .iter()
+                                .filter_map(convert_annotation)
+                                .map(Content::Source),
+                        );
+                    }
+                    ResponsesOutputContent::Refusal { refusal } => {
+                        content.push(Content::Text(TextPart {
+                            text: refusal.clone(),
+                            provider_metadata: Some(item_metadata(id, None)),
+                        }));
+                    }
+                    ResponsesOutputContent::Unknown => {}
+                }
+            }
+        }
+        ResponsesOutputItem::Reasoning {
+            id,
+            summary,
+            encrypted_content,
+        } => {
+            let provider_metadata = Some(item_metadata(id, encrypted_content.as_deref()));
+            if summary.is_empty() {
+                // Keep an empty part so the item id survives for later turns
+                content.push(Content::Reasoning(ReasoningPart {
+                    reasoning: String::new(),
+                    provider_metadata,
+                }));
+            } else {
+                content.extend(summary.iter().map(|part| {
+                    Content::Reasoning(ReasoningPart {
+                        reasoning: part.text.clone(),
+                        provider_metadata: provider_metadata.clone(),
+                    })
+                }));
+            }
+        }
+        ResponsesOutputItem::FunctionCall {
+            id,
+            call_id,
+            name,
+            arguments,
+        } => {
+            *has_function_call = true;
+            content.push(Content::ToolCall(ToolCallPart {
+                tool_call_id: call_id.clone(),
+                tool_name: name.clone(),
+                input: arguments.clone(),
+                provider_executed: None,
+                dynamic: None,
+                provider_metadata: id.as_deref().map(|id| item_metadata(id, None)),
+            }));
+        }
+        ResponsesOutputItem::LocalShellCall {
+            id,
+            call_id,
+            action,
+        } => {
+            // Local shell commands are executed by the client, like function calls
+            *has_function_call = true;
+            let input = LocalShellInput {
+                action: action.clone(),
+            };
+            content.push(Content::ToolCall(ToolCallPart {
+                tool_call_id: call_id.clone(),
+                tool_name: "local_shell".into(),
+                input: serde_json::to_string(&input).unwrap_or_default(),
+                provider_executed: None,
+                dynamic: None,
+                provider_metadata: Some(item_metadata(id, None)),
+            }));
+        }
+        ResponsesOutputItem::WebSearchCall { id, status, action } => {
+            push_provider_tool(
+                &mut content,
+                id,
+                "web_search",
+                "{}".into(),
+                &WebSearchResult {
+                    status: status.clone(),
+                    action: action.clone(),
+                },
+            );
+        }
+        ResponsesOutputItem::FileSearchCall {
+            id,
+            queries,
+            results,
+        } => {
+            push_provider_tool(
+                &mut content,
+                id,
+                "file_search",
+                "{}".into(),
+                &FileSearchResult {
+                    queries: queries.clone(),
+                    results: results.clone(),
+                },
+            );
+        }
+        ResponsesOutputItem::CodeInterpreterCall {
+            id,
+            code,
+            container_id,
+            outputs,
+        } => {
+            let input = CodeInterpreterInput {
+                code: code.clone(),
+                container_id: container_id.clone(),
+            };
+            push_provider_tool(
+                &mut content,
+                id,
+                "code_interpreter",
+                serde_json::to_string(&input).unwrap_or_default(),
+                &CodeInterpreterResult {
+                    outputs: outputs.clone(),
+                },
+            );
+        }
+        ResponsesOutputItem::ImageGenerationCall { id, result } => {
+            push_provider_tool(
+                &mut content,
+                id,
+                "image_generation",
+                "{}".into(),
+                &ImageGenerationResult {
+                    result: result.clone(),
+                },
+            );
+        }
+        ResponsesOutputItem::McpCall {
+            id,
+            name,
+            arguments,
+            server_label,
+            output,
+            error,
+        } => {
+            push_provider_tool(
+                &mut content,
+                id,
+                "mcp",
+                arguments.clone(),
+                &McpCallResult {
+                    server_label: server_label.clone(),
+                    name: name.clone(),
+                    arguments: arguments.clone(),
+                    output: output.clone(),
+                    error: error.clone(),
+                },
+            );
+        }
+        ResponsesOutputItem::Unknown => {}
+    }
+
+    content
+}
+
+/// Pushes the call and result of a tool executed on OpenAI's side.
+fn push_provider_tool(
+    content: &mut Vec<Content>,
+    id: &str,
+    tool_name: &str,
+    input: String,
+    result: &impl serde::Serialize,
+) {
+    content.push(Content::ToolCall(ToolCallPart {
+        tool_call_id: id.to_string(),
+        tool_name: tool_name.to_string(),
+        input,
+        provider_executed: Some(true),
+        dynamic: None,
+        provider_metadata: None,
+    }));
+    content.push(Content::ToolResult(ToolResultPart {
+        tool_call_id: id.to_string(),
+        tool_name: tool_name.to_string(),
+        output: ToolResultOutput::Json {
+            value: serde_json::to_value(result)
+                .map(to_json_value)
+                .unwrap_or(JsonValue::Null),
+            provider_metadata: None,
+        },
+        provider_executed: Some(true),
+        preliminary: None,
+        provider_metadata: None,
+    }));
+}
+
+fn convert_annotation(annotation: &ResponsesAnnotation) -> Option<SourcePart> {
+    match annotation {
+        ResponsesAnnotation::UrlCitation { url, title } => Some(SourcePart {
+            source_type: SourceType::Url,
+            id: generate_source_id(),
+            url: Some(url.clone()),
+            title: title.clone(),
+            provider_metadata: None,
+        }),
+        ResponsesAnnotation::FileCitation { file_id, filename } => Some(SourcePart {
+            source_type: SourceType::Document,
+            id: generate_source_id(),
+            url: None,
+            title: Some(filename.clone().unwrap_or_else(|| file_id.clone())),
+            provider_metadata: Some(openai_metadata([(
+                "fileId",
+                JsonValue::String(file_id.clone()),
+            )])),
+        }),
+        ResponsesAnnotation::ContainerFileCitation {
+            container_id,
+            file_id,
+            filename,
+        } => Some(SourcePart {
+            source_type: SourceType::Document,
+            id: generate_source_id(),
+            url: None,
+            title: Some(filename.clone().unwrap_or_else(|| file_id.clone())),
+            provider_metadata: Some(openai_metadata([
+                ("fileId", JsonValue::String(file_id.clone())),
+                ("containerId", JsonValue::String(container_id.clone())),
+            ])),
+        }),
+        ResponsesAnnotation::Unknown => None,
+    }
+}
+
+fn convert_tools(
+    tools: &[language_model::Tool],
+    warnings: &mut Vec<CallWarning>,
+) -> Vec<serde_json::Value> {
+    tools
+        .iter()
+        .filter_map(|tool| match tool {
+            language_model::Tool::Function(function_tool) => {
+                let mut value = serde_json::json!({
+                    "type": "function",
+                    "name": function_tool.name,
+                    "parameters": function_tool.input_schema,
+                });
+                if let Some(description) = &function_tool.description {
+                    value["description"] = serde_json::Value::String(description.clone());
+                }
+                Some(value)
+            }
+            language_model::Tool::ProviderDefined(provider_tool) => {
+                match provider_tool.id.strip_prefix("openai.") {
+                    Some(tool_type) => {
+                        let mut args = provider_tool.args.clone();
+                        args.insert("type".into(), serde_json::Value::String(tool_type.into()));
+                        Some(serde_json::Value::Object(args))
+                    }
+                    None => {
+                        warnings.push(CallWarning {
+                            message: format!(
+                                "Unsupported provider-defined tool: {}",
+                                provider_tool.id
+                            ),
+                        });
+                        None
+                    }
+                }
+            }
+        })
+        .collect()
+}
+
+fn convert_tool_choice(tool_choice: &language_model::ToolChoice) -> serde_json::Value {
+    match tool_choice {
+        language_model::ToolChoice::Auto => serde_json::json!("auto"),
+        language_model::ToolChoice::None => serde_json::json!("none"),
+        language_model::ToolChoice::Required => serde_json::json!("required"),
+        language_model::ToolChoice::Tool { tool_name } => {
+            if BUILT_IN_TOOL_TYPES.contains(&tool_name.as_str()) {
+                serde_json::json!({ "type": tool_name })
+            } else {
+                serde_json::json!({ "type": "function", "name": tool_name })
+            }
+        }
+    }
+}
+
+fn map_finish_reason(incomplete_reason: Option<&str>, has_function_call: bool) -> FinishReason {
+    match incomplete_reason {
+        None if has_function_call => FinishReason::ToolCalls,
+        None => FinishReason::Stop,
+        Some("max_output_tokens") => FinishReason::Length,
+        Some("content_filter") => FinishReason::ContentFilter,
+        Some(_) if has_function_call => FinishReason::ToolCalls,
+        Some(_) => FinishReason::Unknown,
+    }
+}
+
+fn convert_usage(usage: &ResponsesUsage) -> Usage {
+    Usage {
+        input_tokens: Some(usage.input_tokens),
+        output_tokens: Some(usage.output_tokens),
+        total_tokens: Some(
+            usage
+                .total_tokens
+                .unwrap_or(usage.input_tokens + usage.output_tokens),
+        ),
+        reasoning_tokens: usage
+            .output_tokens_details
+            .as_ref()
+            .and_then(|d| d.reasoning_tokens),
+        cached_input_tokens: usage
+            .input_tokens_details
+            .as_ref()
+            .and_then(|d| d.cached_tokens),
+    }
+}
+
+fn response_metadata(response: &ResponsesResponse) -> ResponseMetadata {
+    ResponseMetadata {
+        id: Some(response.id.clone()),
+        timestamp: response.created_at.map(unix_to_iso8601),
+        model_id: response.model.clone(),
+    }
+}
+
+/// Formats a Unix timestamp (seconds) as an ISO 8601 UTC string.
+fn unix_to_iso8601(secs: i64) -> String {
+    let days = secs.div_euclid(86_400);
+    let time = secs.rem_euclid(86_400);
+
+    // Civil-from-days conversion (Howard Hinnant's algorithm)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        time / 3600,
+        (time % 3600) / 60,
+        time % 60
+    )
+}
+
+fn tool_output_to_string(output: &ToolResultOutput) -> String {
+    match output {
+        ToolResultOutput::Text { value, .. } | ToolResultOutput::ErrorText { value, .. } => {
+            value.clone()
+        }
+        ToolResultOutput::Json { value, .. } | ToolResultOutput::ErrorJson { value, .. } => {
+            serde_json::to_string(value).unwrap_or_default()
+        }
+        ToolResultOutput::ExecutionDenied { reason, .. } => reason
+            .clone()
+            .unwrap_or_else(|| "Tool execution denied.".to_string()),
+        ToolResultOutput::Content { value, .. } => serde_json::to_string(value).unwrap_or_default(),
+    }
+}
+
+fn file_data_to_url(data: &FileData, media_type: &str) -> String {
+    match data {
+        FileData::Url(url) => url.clone(),
+        FileData::Binary(bytes) => format!(
+            "data:{};base64,{}",
+            media_type,
+            ai_sdk_core::util::encode_base64(bytes)
+        ),
+    }
+}
+
+fn unsupported_setting(setting: &str, target: &str) -> CallWarning {
+    CallWarning {
+        message: format!(
+            "{} is not supported for {} and was ignored",
+            setting, target
+        ),
+    }
+}
+
+fn to_json_value(value: serde_json::Value) -> JsonValue {
+    serde_json::from_value(value).unwrap_or(JsonValue::Null)
+}
+
+fn openai_metadata<const N: usize>(entries: [(&str, JsonValue); N]) -> SharedProviderMetadata {
+    let openai: json_value::JsonObject = entries
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v))
+        .collect();
+    let mut metadata = HashMap::new();
+    metadata.insert("openai".to_string(), openai);
+    metadata
+}
+
+fn item_metadata(item_id: &str, encrypted_content: Option<&str>) -> SharedProviderMetadata {
+    let mut metadata = openai_metadata([("itemId", JsonValue::String(item_id.to_string()))]);
+    if let Some(encrypted) = encrypted_content {
+        if let Some(openai) = metadata.get_mut("openai") {
+            openai.insert(
+                "reasoningEncryptedContent".to_string(),
+                JsonValue::String(encrypted.to_string()),
+            );
+        }
+    }
+    metadata
+}
+
+fn item_id_from_metadata(metadata: &Option<SharedProviderMetadata>) -> Option<String> {
+    match metadata.as_ref()?.get("openai")?.get("itemId")? {
+        JsonValue::String(id) => Some(id.clone()),
+        _ => None,
+    }
+}
+
+fn reasoning_encrypted_content_from_metadata(
+    metadata: &Option<SharedProviderMetadata>,
+) -> Option<String> {
+    match metadata
+        .as_ref()?
+        .get("openai")?
+        .get("reasoningEncryptedContent")?
+    {
+        JsonValue::String(content) => Some(content.clone()),
+        _ => None,
+    }
+}
+
+fn finish_metadata(response_id: &str) -> SharedProviderMetadata {
+    openai_metadata([("responseId", JsonValue::String(response_id.to_string()))])
+}
+
+/// Struct to hold extracted Responses API options
+#[derive(Default)]
+struct ResponsesOptions {
+    parallel_tool_calls: Option<bool>,
+    previous_response_id: Option<String>,
+    store: Option<bool>,
+    user: Option<String>,
+    metadata: Option<HashMap<String, String>>,
+    instructions: Option<String>,
+    reasoning_effort: Option<String>,
+    reasoning_summary: Option<String>,
+    service_tier: Option<String>,
+    text_verbosity: Option<String>,
+    include: Option<Vec<String>>,
+    max_tool_calls: Option<u32>,
+    truncation: Option<String>,
+    strict_json_schema: Option<bool>,
+    prompt_cache_key: Option<String>,
+    safety_identifier: Option<String>,
+}
+
+/// Extract Responses API options from provider_options["openai"]
+fn extract_responses_options(
+    provider_options: &Option<HashMap<String, json_value::JsonObject>>,
+) -> ResponsesOptions {
+    let mut opts = ResponsesOptions::default();
+
+    let Some(openai_opts) = provider_options.as_ref().and_then(|p| p.get("openai")) else {
+        return opts;
+    };
+
+    let string = |key: &str| match openai_opts.get(key) {
+        Some(JsonValue::String(s)) => Some(s.clone()),
+        _ => None,
+    };
+    let boolean = |key: &str| match openai_opts.get(key) {
+        Some(JsonValue::Bool(b)) => Some(*b),
+        _ => None,
+    };
+
+    opts.parallel_tool_calls = boolean("parallelToolCalls");
+    opts.previous_response_id = string("previousResponseId");
+    opts.store = boolean("store");
+    opts.user = string("user");
+    opts.instructions = string("instructions");
+    opts.reasoning_effort = string("reasoningEffort");
+    opts.reasoning_summary = string("reasoningSummary");
+    opts.service_tier = string("serviceTier");
+    opts.text_verbosity = string("textVerbosity");
+    opts.truncation = string("truncation");
+    opts.strict_json_schema = boolean("strictJsonSchema");
+    opts.prompt_cache_key = string("promptCacheKey");
+    opts.safety_identifier = string("safetyIdentifier");
+
+    // metadata: HashMap<String, String>
+    if let Some(JsonValue::Object(metadata)) = openai_opts.get("metadata") {
+        let meta_map: HashMap<String, String> = metadata
+            .iter()
+            .filter_map(|(k, v)| match v {
+                JsonValue::String(s) => Some((k.clone(), s.clone())),
+                _ => None,
+            })
+            .collect();
+        if !meta_map.is_empty() {
+            opts.metadata = Some(meta_map);
+        }
+    }
+
+    // include: Vec<String>
+    if let Some(JsonValue::Array(values)) = openai_opts.get("include") {
+        opts.include = Some(
+            values
+                .iter()
+                .filter_map(|v| match v {
+                    JsonValue::String(s) => Some(s.clone()),
+                    _ => None,
+                })
+                .collect(),
+        );
+    }
+
+    // maxToolCalls: u32
+    if let Some(JsonValue::Number(n)) = openai_opts.get("maxToolCalls") {
+        opts.max_tool_calls = n.as_u64().map(|u| u as u32);
+    }
+
+    opts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unix_to_iso8601() {
+        assert_eq!(unix_to_iso8601(0), "1970-01-01T00:00:00Z");
+        assert_eq!(unix_to_iso8601(1741630255), "2025-03-10T18:10:55Z");
+    }
+
+    #[test]
+    fn test_map_finish_reason() {
+        assert_eq!(map_finish_reason(None, false), FinishReason::Stop);
+        assert_eq!(map_finish_reason(None, true), FinishReason::ToolCalls);
+        assert_eq!(
+            map_finish_reason(Some("max_output_tokens"), false),
+            FinishReason::Length
+        );
+        assert_eq!(
+            map_finish_reason(Some("content_filter"), false),
+            FinishReason::ContentFilter
+        );
+    }
+
+    #[test]
+    fn test_tool_choice_for_built_in_tool() {
+        let choice = convert_tool_choice(&language_model::ToolChoice::Tool {
+            tool_name: "web_search".into(),
+        });
+        assert_eq!(choice, serde_json::json!({ "type": "web_search" }));
+
+        let choice = convert_tool_choice(&language_model::ToolChoice::Tool {
+            tool_name: "get_weather".into(),
+        });
+        assert_eq!(
+            choice,
+            serde_json::json!({ "type": "function", "name": "get_weather" })
+        );
+    }
+
+    #[test]
+    fn test_tool_output_to_string() {
+        let text = ToolResultOutput::Text {
+            value: "sunny".into(),
+            provider_metadata: None,
+        };
+        assert_eq!(tool_output_to_string(&text), "sunny");
+
+        let denied = ToolResultOutput::ExecutionDenied {
+            reason: None,
+            provider_metadata: None,
+        };
+        assert_eq!(tool_output_to_string(&denied), "Tool execution denied.");
+    }
+}
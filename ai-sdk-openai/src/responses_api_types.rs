@@ -0,0 +1,381 @@
+//! Wire types for the OpenAI Responses API (`/v1/responses`).
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use std::collections::HashMap;
+
+use crate::tools::{
+    CodeInterpreterOutput, FileSearchResultItem, LocalShellAction, WebSearchAction,
+};
+
+#[derive(Debug, Serialize)]
+pub struct ResponsesRequest {
+    pub model: String,
+    pub input: Vec<ResponsesInputItem>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instructions: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_output_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tools: Option<Vec<JsonValue>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool_choice: Option<JsonValue>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub text: Option<ResponsesTextConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reasoning: Option<ResponsesReasoningConfig>,
+
+    // OpenAI-specific options
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parallel_tool_calls: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_response_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub store: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<HashMap<String, String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub service_tier: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_tool_calls: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub truncation: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prompt_cache_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub safety_identifier: Option<String>,
+}
+
+/// Text output configuration (format and verbosity)
+#[derive(Debug, Serialize, Default)]
+pub struct ResponsesTextConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<ResponsesTextFormat>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verbosity: Option<String>,
+}
+
+/// Text output format
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ResponsesTextFormat {
+    /// Plain text output (default)
+    Text,
+    /// JSON object output (unvalidated)
+    JsonObject,
+    /// JSON schema output (structured)
+    JsonSchema {
+        name: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<String>,
+        schema: JsonValue,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        strict: Option<bool>,
+    },
+}
+
+/// Reasoning configuration for reasoning models
+#[derive(Debug, Serialize)]
+pub struct ResponsesReasoningConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub effort: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+}
+
+/// A single item of the request `input` array
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum ResponsesInputItem {
+    /// A role-based message (system, developer, user or assistant)
+    Message(ResponsesInputMessage),
+    /// A typed item such as a function call or function call output
+    Item(ResponsesInputTypedItem),
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct ResponsesInputMessage {
+    pub role: String,
+    pub content: ResponsesInputMessageContent,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum ResponsesInputMessageContent {
+    Text(String),
+    Parts(Vec<ResponsesInputContentPart>),
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ResponsesInputContentPart {
+    InputText {
+        text: String,
+    },
+    InputImage {
+        image_url: String,
+    },
+    InputFile {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        filename: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        file_data: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        file_url: Option<String>,
+    },
+    OutputText {
+        text: String,
+    },
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ResponsesInputTypedItem {
+    FunctionCall {
+        call_id: String,
+        name: String,
+        arguments: String,
+    },
+    FunctionCallOutput {
+        call_id: String,
+        output: String,
+    },
+    LocalShellCall {
+        id: String,
+        call_id: String,
+        action: JsonValue,
+    },
+    LocalShellCallOutput {
+        call_id: String,
+        output: String,
+    },
+    /// Reasoning replayed from an earlier response when it is not stored
+    Reasoning {
+        id: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        encrypted_content: Option<String>,
+        summary: Vec<ResponsesInputSummaryPart>,
+    },
+    /// An item of an earlier stored response, referenced by id
+    ItemReference {
+        id: String,
+    },
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ResponsesInputSummaryPart {
+    SummaryText { text: String },
+}
+
+/// Non-streaming response body
+#[derive(Debug, Deserialize, Clone)]
+pub struct ResponsesResponse {
+    pub id: String,
+    #[serde(default)]
+    pub created_at: Option<i64>,
+    #[serde(default)]
+    pub model: Option<String>,
+    #[serde(default)]
+    pub output: Vec<ResponsesOutputItem>,
+    #[serde(default)]
+    pub incomplete_details: Option<ResponsesIncompleteDetails>,
+    #[serde(default)]
+    pub usage: Option<ResponsesUsage>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct ResponsesIncompleteDetails {
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct ResponsesUsage {
+    pub input_tokens: u32,
+    #[serde(default)]
+    pub input_tokens_details: Option<ResponsesInputTokensDetails>,
+    pub output_tokens: u32,
+    #[serde(default)]
+    pub output_tokens_details: Option<ResponsesOutputTokensDetails>,
+    #[serde(default)]
+    pub total_tokens: Option<u32>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct ResponsesInputTokensDetails {
+    pub cached_tokens: Option<u32>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct ResponsesOutputTokensDetails {
+    pub reasoning_tokens: Option<u32>,
+}
+
+/// An item of the response `output` array
+#[derive(Debug, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ResponsesOutputItem {
+    Message {
+        id: String,
+        #[serde(default)]
+        content: Vec<ResponsesOutputContent>,
+    },
+    Reasoning {
+        id: String,
+        #[serde(default)]
+        summary: Vec<ResponsesSummaryPart>,
+        #[serde(default)]
+        encrypted_content: Option<String>,
+    },
+    FunctionCall {
+        #[serde(default)]
+        id: Option<String>,
+        call_id: String,
+        name: String,
+        #[serde(default)]
+        arguments: String,
+    },
+    WebSearchCall {
+        id: String,
+        #[serde(default)]
+        status: Option<String>,
+        #[serde(default)]
+        action: Option<WebSearchAction>,
+    },
+    FileSearchCall {
+        id: String,
+        #[serde(default)]
+        queries: Vec<String>,
+        #[serde(default)]
+        results: Option<Vec<FileSearchResultItem>>,
+    },
+    CodeInterpreterCall {
+        id: String,
+        #[serde(default)]
+        code: Option<String>,
+        container_id: String,
+        #[serde(default)]
+        outputs: Option<Vec<CodeInterpreterOutput>>,
+    },
+    ImageGenerationCall {
+        id: String,
+        #[serde(default)]
+        result: Option<String>,
+    },
+    LocalShellCall {
+        id: String,
+        call_id: String,
+        action: LocalShellAction,
+    },
+    McpCall {
+        id: String,
+        name: String,
+        #[serde(default)]
+        arguments: String,
+        server_label: String,
+        #[serde(default)]
+        output: Option<String>,
+        #[serde(default)]
+        error: Option<JsonValue>,
+    },
+    /// Output item types without content (e.g. `mcp_list_tools`)
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ResponsesOutputContent {
+    OutputText {
+        text: String,
+        #[serde(default)]
+        annotations: Vec<ResponsesAnnotation>,
+    },
+    Refusal {
+        refusal: String,
+    },
+    #[serde(other)]
+    Unknown,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct ResponsesSummaryPart {
+    pub text: String,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ResponsesAnnotation {
+    UrlCitation {
+        url: String,
+        #[serde(default)]
+        title: Option<String>,
+    },
+    FileCitation {
+        file_id: String,
+        #[serde(default)]
+        filename: Option<String>,
+    },
+    ContainerFileCitation {
+        container_id: String,
+        file_id: String,
+        #[serde(default)]
+        filename: Option<String>,
+    },
+    #[serde(other)]
+    Unknown,
+}
+
+/// Server-sent event from a streaming response
+#[derive(Debug, Deserialize, Clone)]
+#[serde(tag = "type")]
+pub enum ResponsesStreamEvent {
+    #[serde(rename = "response.created")]
+    ResponseCreated { response: ResponsesResponse },
+    #[serde(rename = "response.completed")]
+    ResponseCompleted { response: ResponsesResponse },
+    #[serde(rename = "response.incomplete")]
+    ResponseIncomplete { response: ResponsesResponse },
+    #[serde(rename = "response.failed")]
+    ResponseFailed { response: JsonValue },
+    #[serde(rename = "response.output_item.added")]
+    OutputItemAdded {
+        output_index: usize,
+        item: ResponsesOutputItem,
+    },
+    #[serde(rename = "response.output_item.done")]
+    OutputItemDone { item: ResponsesOutputItem },
+    #[serde(rename = "response.output_text.delta")]
+    OutputTextDelta { item_id: String, delta: String },
+    #[serde(rename = "response.output_text.annotation.added")]
+    OutputTextAnnotationAdded { annotation: ResponsesAnnotation },
+    #[serde(rename = "response.reasoning_summary_part.added")]
+    ReasoningSummaryPartAdded { item_id: String, summary_index: u32 },
+    #[serde(rename = "response.reasoning_summary_text.delta")]
+    ReasoningSummaryTextDelta {
+        item_id: String,
+        summary_index: u32,
+        delta: String,
+    },
+    #[serde(rename = "response.function_call_arguments.delta")]
+    FunctionCallArgumentsDelta { output_index: usize, delta: String },
+    #[serde(rename = "response.code_interpreter_call_code.delta")]
+    CodeInterpreterCallCodeDelta { item_id: String, delta: String },
+    #[serde(rename = "response.code_interpreter_call_code.done")]
+    CodeInterpreterCallCodeDone { item_id: String },
+    #[serde(rename = "error")]
+    Error { message: String },
+    /// Lifecycle events that carry no content (in_progress, searching, ...)
+    #[serde(other)]
+    Unknown,
+}
@@ -5,6 +5,8 @@ use serde::Serialize;
 use std::collections::HashMap;
 use std::time::SystemTime;
 
+use crate::error::{network_error, response_error};
+
 /// OpenAI implementation of speech model.
 pub struct OpenAISpeechModel {
     model_id: String,
@@ -128,7 +130,10 @@ impl SpeechModel for OpenAISpeechModel {
             }
         }
 
-        let response = request.send().await?;
+        let abort_signal = options.abort_signal.as_ref();
+        let response = abortable(abort_signal, request.send())
+            .await?
+            .map_err(|e| network_error(&url, e))?;
 
         let status = response.status();
         let response_headers: HashMap<String, String> = response
@@ -138,12 +143,12 @@ impl SpeechModel for OpenAISpeechModel {
             .collect();
 
         if !status.is_success() {
-            let error_text = response.text().await?;
-            return Err(format!("API error {}: {}", status, error_text).into());
+            let error = abortable(abort_signal, response_error(&url, response)).await?;
+            return Err(Box::new(error));
         }
 
         // Get audio as binary
-        let audio_bytes = response.bytes().await?.to_vec();
+        let audio_bytes = abortable(abort_signal, response.bytes()).await??.to_vec();
 
         Ok(SpeechGenerateResponse {
             audio: AudioData::Binary(audio_bytes),
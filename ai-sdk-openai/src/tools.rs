@@ -0,0 +1,624 @@
+//! OpenAI built-in tools for the Responses API.
+//!
+//! The constructors in this module produce [`ProviderDefinedTool`] values with
+//! the wire shape OpenAI expects, so they can be passed straight to
+//! [`OpenAIResponsesModel`](crate::OpenAIResponsesModel) through
+//! `CallOptions::tools`:
+//!
+//! ```rust
+//! use ai_sdk_openai::tools::{self, SearchContextSize, WebSearchArgs};
+//! use ai_sdk_provider::language_model::Tool;
+//!
+//! let tool = Tool::ProviderDefined(tools::web_search(WebSearchArgs {
+//!     search_context_size: Some(SearchContextSize::High),
+//!     ..Default::default()
+//! }));
+//! ```
+//!
+//! Calls to these tools come back as `ToolCallPart`/`ToolResultPart` pairs with
+//! `provider_executed: Some(true)` (except `local_shell`, which the client runs).
+//! Their JSON payloads deserialize into the typed results defined here, see
+//! [`parse_tool_input`] and [`parse_tool_output`].
+
+use ai_sdk_provider::language_model::{ProviderDefinedTool, ToolResultOutput};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Creates a provider-defined tool from serializable arguments.
+fn provider_tool(name: &str, args: impl Serialize) -> ProviderDefinedTool {
+    let args = match serde_json::to_value(args) {
+        Ok(serde_json::Value::Object(map)) => map,
+        _ => serde_json::Map::new(),
+    };
+
+    ProviderDefinedTool {
+        id: format!("openai.{}", name),
+        name: name.to_string(),
+        args,
+    }
+}
+
+/// Creates the `web_search` tool, which lets the model search the web.
+pub fn web_search(args: WebSearchArgs) -> ProviderDefinedTool {
+    provider_tool("web_search", args)
+}
+
+/// Creates the `file_search` tool, which searches the given vector stores.
+pub fn file_search(args: FileSearchArgs) -> ProviderDefinedTool {
+    provider_tool("file_search", args)
+}
+
+/// Creates the `code_interpreter` tool, which runs Python in a sandboxed container.
+pub fn code_interpreter(args: CodeInterpreterArgs) -> ProviderDefinedTool {
+    provider_tool("code_interpreter", args)
+}
+
+/// Creates the `image_generation` tool, which generates or edits images.
+pub fn image_generation(args: ImageGenerationArgs) -> ProviderDefinedTool {
+    provider_tool("image_generation", args)
+}
+
+/// Creates the `local_shell` tool.
+///
+/// The model only proposes commands; the client executes them and sends the
+/// output back as a tool result.
+pub fn local_shell() -> ProviderDefinedTool {
+    provider_tool("local_shell", serde_json::json!({}))
+}
+
+/// Creates the `mcp` tool, which connects the model to a remote MCP server.
+pub fn mcp(args: McpArgs) -> ProviderDefinedTool {
+    provider_tool("mcp", args)
+}
+
+/// Parses the input of a built-in tool call (e.g. [`CodeInterpreterInput`]).
+pub fn parse_tool_input<T: DeserializeOwned>(input: &str) -> Result<T, serde_json::Error> {
+    serde_json::from_str(input)
+}
+
+/// Parses the output of a built-in tool result (e.g. [`WebSearchResult`]).
+///
+/// Only JSON outputs can be parsed; other output kinds yield `None`.
+pub fn parse_tool_output<T: DeserializeOwned>(output: &ToolResultOutput) -> Option<T> {
+    match output {
+        ToolResultOutput::Json { value, .. } => serde_json::to_value(value)
+            .ok()
+            .and_then(|v| serde_json::from_value(v).ok()),
+        _ => None,
+    }
+}
+
+// Web search
+
+/// Arguments for the `web_search` tool.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WebSearchArgs {
+    /// How much context to retrieve from the web
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub search_context_size: Option<SearchContextSize>,
+    /// Approximate user location used to localize results
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_location: Option<UserLocation>,
+    /// Restricts the search to specific domains
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filters: Option<WebSearchFilters>,
+}
+
+/// Amount of context retrieved by web search.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchContextSize {
+    /// Least context, lowest latency
+    Low,
+    /// Balanced context and latency (default)
+    Medium,
+    /// Most context, highest latency
+    High,
+}
+
+/// Approximate user location for web search.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UserLocation {
+    /// Location type, always `approximate`
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// Two-letter ISO country code
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>,
+    /// City name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub city: Option<String>,
+    /// Region or state
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub region: Option<String>,
+    /// IANA timezone
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+}
+
+impl Default for UserLocation {
+    fn default() -> Self {
+        Self {
+            kind: "approximate".into(),
+            country: None,
+            city: None,
+            region: None,
+            timezone: None,
+        }
+    }
+}
+
+/// Domain filters for web search.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct WebSearchFilters {
+    /// Domains the search is restricted to
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_domains: Vec<String>,
+}
+
+/// Result of a `web_search` call.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WebSearchResult {
+    /// Call status (e.g. `completed`)
+    #[serde(default)]
+    pub status: Option<String>,
+    /// The action the model performed
+    #[serde(default)]
+    pub action: Option<WebSearchAction>,
+}
+
+/// Action performed during a web search call.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WebSearchAction {
+    /// A search query
+    Search {
+        /// The query, when reported
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        query: Option<String>,
+        /// Sources consulted by the search
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        sources: Vec<WebSearchSource>,
+    },
+    /// Opening a page
+    OpenPage {
+        /// URL of the opened page
+        #[serde(default)]
+        url: Option<String>,
+    },
+    /// Searching within a page
+    Find {
+        /// URL of the searched page
+        #[serde(default)]
+        url: Option<String>,
+        /// Pattern searched for
+        #[serde(default)]
+        pattern: Option<String>,
+    },
+    /// An action type not modelled by this crate
+    #[serde(other)]
+    Other,
+}
+
+/// A source consulted by web search.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WebSearchSource {
+    /// A web page
+    Url {
+        /// URL of the page
+        url: String,
+    },
+    /// A named source such as a data provider
+    Api {
+        /// Name of the source
+        name: String,
+    },
+    /// A source type not modelled by this crate
+    #[serde(other)]
+    Other,
+}
+
+// File search
+
+/// Arguments for the `file_search` tool.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FileSearchArgs {
+    /// Vector stores to search
+    pub vector_store_ids: Vec<String>,
+    /// Maximum number of results to return
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_num_results: Option<u32>,
+    /// Ranking options for the search
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ranking_options: Option<FileSearchRankingOptions>,
+    /// Attribute filters (comparison or compound filter objects)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filters: Option<serde_json::Value>,
+}
+
+/// Ranking options for file search.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FileSearchRankingOptions {
+    /// Ranker to use (e.g. `auto`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ranker: Option<String>,
+    /// Minimum score between 0 and 1
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score_threshold: Option<f64>,
+}
+
+/// Result of a `file_search` call.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FileSearchResult {
+    /// Queries the model issued
+    #[serde(default)]
+    pub queries: Vec<String>,
+    /// Matching chunks, only present when requested via
+    /// `include: ["file_search_call.results"]`
+    #[serde(default)]
+    pub results: Option<Vec<FileSearchResultItem>>,
+}
+
+/// A chunk returned by file search.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FileSearchResultItem {
+    /// ID of the file
+    pub file_id: String,
+    /// Name of the file
+    pub filename: String,
+    /// Relevance score
+    pub score: f64,
+    /// Matching text
+    pub text: String,
+    /// File attributes
+    #[serde(default)]
+    pub attributes: Option<serde_json::Value>,
+}
+
+// Code interpreter
+
+/// Arguments for the `code_interpreter` tool.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CodeInterpreterArgs {
+    /// Container to run code in
+    pub container: CodeInterpreterContainer,
+}
+
+/// Container used by the code interpreter.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum CodeInterpreterContainer {
+    /// An existing container ID
+    Id(String),
+    /// A container created automatically, optionally seeded with files
+    Auto(AutoContainer),
+}
+
+impl Default for CodeInterpreterContainer {
+    fn default() -> Self {
+        Self::Auto(AutoContainer::default())
+    }
+}
+
+/// Automatically created code interpreter container.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename = "auto")]
+pub struct AutoContainer {
+    /// Files made available in the container
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub file_ids: Vec<String>,
+}
+
+/// Input of a `code_interpreter` call.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CodeInterpreterInput {
+    /// Code the model ran
+    #[serde(default)]
+    pub code: Option<String>,
+    /// Container the code ran in
+    pub container_id: String,
+}
+
+/// Result of a `code_interpreter` call.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CodeInterpreterResult {
+    /// Outputs produced by the code
+    #[serde(default)]
+    pub outputs: Option<Vec<CodeInterpreterOutput>>,
+}
+
+/// An output produced by the code interpreter.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum CodeInterpreterOutput {
+    /// Console output
+    Logs {
+        /// The logged text
+        logs: String,
+    },
+    /// A generated image
+    Image {
+        /// URL of the image
+        url: String,
+    },
+    /// An output type not modelled by this crate
+    #[serde(other)]
+    Other,
+}
+
+// Image generation
+
+/// Arguments for the `image_generation` tool.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ImageGenerationArgs {
+    /// Background type: `transparent`, `opaque` or `auto`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub background: Option<String>,
+    /// Fidelity to input images: `high` or `low`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input_fidelity: Option<String>,
+    /// Image generation model (e.g. `gpt-image-1`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    /// Moderation level: `auto` or `low`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub moderation: Option<String>,
+    /// Compression level (0-100) for jpeg and webp output
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_compression: Option<u32>,
+    /// Output format: `png`, `jpeg` or `webp`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_format: Option<String>,
+    /// Number of partial images to stream (0-3)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub partial_images: Option<u32>,
+    /// Quality: `low`, `medium`, `high` or `auto`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quality: Option<String>,
+    /// Size (e.g. `1024x1024`) or `auto`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<String>,
+}
+
+/// Result of an `image_generation` call.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImageGenerationResult {
+    /// Base64-encoded image
+    #[serde(default)]
+    pub result: Option<String>,
+}
+
+// Local shell
+
+/// Input of a `local_shell` call.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LocalShellInput {
+    /// The command the model wants to run
+    pub action: LocalShellAction,
+}
+
+/// A command proposed by the `local_shell` tool.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LocalShellAction {
+    /// Action type, always `exec`
+    #[serde(rename = "type")]
+    pub kind: String,
+    /// Command and arguments
+    pub command: Vec<String>,
+    /// Environment variables to set
+    #[serde(default)]
+    pub env: HashMap<String, String>,
+    /// Working directory
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub working_directory: Option<String>,
+    /// Timeout in milliseconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_ms: Option<u64>,
+    /// User to run the command as
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+}
+
+// MCP
+
+/// Arguments for the `mcp` tool.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct McpArgs {
+    /// Label identifying the server in tool calls
+    pub server_label: String,
+    /// URL of the MCP server
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub server_url: Option<String>,
+    /// ID of an OpenAI connector, as an alternative to `server_url`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub connector_id: Option<String>,
+    /// Description of the server
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub server_description: Option<String>,
+    /// OAuth access token for the server
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authorization: Option<String>,
+    /// Restricts which of the server's tools may be called
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allowed_tools: Option<Vec<String>>,
+    /// Headers sent to the server
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub headers: Option<HashMap<String, String>>,
+    /// Whether tool calls need approval
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub require_approval: Option<McpApproval>,
+}
+
+/// Approval requirement for MCP tool calls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum McpApproval {
+    /// Every call needs approval
+    Always,
+    /// No call needs approval
+    Never,
+}
+
+/// Result of an `mcp` call.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct McpCallResult {
+    /// Label of the server that handled the call
+    pub server_label: String,
+    /// Name of the MCP tool that was called
+    pub name: String,
+    /// JSON-encoded arguments
+    #[serde(default)]
+    pub arguments: String,
+    /// Tool output, if the call succeeded
+    #[serde(default)]
+    pub output: Option<String>,
+    /// Error, if the call failed
+    #[serde(default)]
+    pub error: Option<serde_json::Value>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn args_json(tool: &ProviderDefinedTool) -> serde_json::Value {
+        serde_json::Value::Object(tool.args.clone())
+    }
+
+    #[test]
+    fn test_web_search_tool() {
+        let tool = web_search(WebSearchArgs {
+            search_context_size: Some(SearchContextSize::Medium),
+            user_location: Some(UserLocation {
+                country: Some("US".into()),
+                ..Default::default()
+            }),
+            filters: None,
+        });
+
+        assert_eq!(tool.id, "openai.web_search");
+        assert_eq!(tool.name, "web_search");
+        assert_eq!(
+            args_json(&tool),
+            json!({
+                "search_context_size": "medium",
+                "user_location": { "type": "approximate", "country": "US" }
+            })
+        );
+    }
+
+    #[test]
+    fn test_file_search_tool() {
+        let tool = file_search(FileSearchArgs {
+            vector_store_ids: vec!["vs_123".into()],
+            max_num_results: Some(20),
+            ranking_options: Some(FileSearchRankingOptions {
+                ranker: Some("auto".into()),
+                score_threshold: Some(0.0),
+            }),
+            filters: None,
+        });
+
+        assert_eq!(
+            args_json(&tool),
+            json!({
+                "vector_store_ids": ["vs_123"],
+                "max_num_results": 20,
+                "ranking_options": { "ranker": "auto", "score_threshold": 0.0 }
+            })
+        );
+    }
+
+    #[test]
+    fn test_code_interpreter_container() {
+        let tool = code_interpreter(CodeInterpreterArgs::default());
+        assert_eq!(args_json(&tool), json!({ "container": { "type": "auto" } }));
+
+        let tool = code_interpreter(CodeInterpreterArgs {
+            container: CodeInterpreterContainer::Id("cntr_123".into()),
+        });
+        assert_eq!(args_json(&tool), json!({ "container": "cntr_123" }));
+
+        let tool = code_interpreter(CodeInterpreterArgs {
+            container: CodeInterpreterContainer::Auto(AutoContainer {
+                file_ids: vec!["file_1".into()],
+            }),
+        });
+        assert_eq!(
+            args_json(&tool),
+            json!({ "container": { "type": "auto", "file_ids": ["file_1"] } })
+        );
+    }
+
+    #[test]
+    fn test_image_generation_and_local_shell_tools() {
+        let tool = image_generation(ImageGenerationArgs {
+            output_format: Some("webp".into()),
+            quality: Some("low".into()),
+            ..Default::default()
+        });
+        assert_eq!(
+            args_json(&tool),
+            json!({ "output_format": "webp", "quality": "low" })
+        );
+
+        let tool = local_shell();
+        assert_eq!(tool.id, "openai.local_shell");
+        assert!(tool.args.is_empty());
+    }
+
+    #[test]
+    fn test_mcp_tool() {
+        let tool = mcp(McpArgs {
+            server_label: "dmcp".into(),
+            server_url: Some("https://mcp.exa.ai/mcp".into()),
+            require_approval: Some(McpApproval::Never),
+            ..Default::default()
+        });
+
+        assert_eq!(
+            args_json(&tool),
+            json!({
+                "server_label": "dmcp",
+                "server_url": "https://mcp.exa.ai/mcp",
+                "require_approval": "never"
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_tool_output() {
+        let output = ToolResultOutput::Json {
+            value: serde_json::from_value(json!({
+                "status": "completed",
+                "action": {
+                    "type": "search",
+                    "query": "rust",
+                    "sources": [{ "type": "url", "url": "https://www.rust-lang.org" }]
+                }
+            }))
+            .unwrap(),
+            provider_metadata: None,
+        };
+
+        let result: WebSearchResult = parse_tool_output(&output).unwrap();
+        assert_eq!(
+            result.action,
+            Some(WebSearchAction::Search {
+                query: Some("rust".into()),
+                sources: vec![WebSearchSource::Url {
+                    url: "https://www.rust-lang.org".into()
+                }],
+            })
+        );
+
+        let text = ToolResultOutput::Text {
+            value: "hello".into(),
+            provider_metadata: None,
+        };
+        assert!(parse_tool_output::<WebSearchResult>(&text).is_none());
+    }
+}
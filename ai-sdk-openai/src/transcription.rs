@@ -3,6 +3,8 @@ use async_trait::async_trait;
 use reqwest::{multipart, Client};
 use serde::Deserialize;
 use std::collections::HashMap;
+
+use crate::error::{network_error, response_error};
 use std::time::SystemTime;
 
 /// OpenAI implementation of transcription model.
@@ -223,7 +225,10 @@ impl TranscriptionModel for OpenAITranscriptionModel {
             }
         }
 
-        let response = request.send().await?;
+        let abort_signal = options.abort_signal.as_ref();
+        let response = abortable(abort_signal, request.send())
+            .await?
+            .map_err(|e| network_error(&url, e))?;
 
         let status = response.status();
         let response_headers: HashMap<String, String> = response
@@ -233,11 +238,12 @@ impl TranscriptionModel for OpenAITranscriptionModel {
             .collect();
 
         if !status.is_success() {
-            let error_text = response.text().await?;
-            return Err(format!("API error {}: {}", status, error_text).into());
+            let error = abortable(abort_signal, response_error(&url, response)).await?;
+            return Err(Box::new(error));
         }
 
-        let api_response: TranscriptionApiResponse = response.json().await?;
+        let api_response: TranscriptionApiResponse =
+            abortable(abort_signal, response.json()).await??;
 
         // Convert segments or words to our format
         let segments = if let Some(segs) = api_response.segments {
@@ -584,3 +584,136 @@ async fn test_expose_raw_response_headers() {
     assert_eq!(headers.get("test-header").unwrap(), "test-value");
     assert!(headers.contains_key("content-type"));
 }
+
+#[tokio::test]
+async fn test_api_error_details() {
+    use wiremock::{Mock, ResponseTemplate};
+
+    let test_server = TestServer::new().await;
+    let error_body = serde_json::json!({
+        "error": {
+            "message": "Invalid value for 'temperature'",
+            "type": "invalid_request_error",
+            "param": "temperature",
+            "code": null
+        }
+    });
+
+    Mock::given(wiremock::matchers::method("POST"))
+        .and(wiremock::matchers::path("/v1/chat/completions"))
+        .respond_with(
+            ResponseTemplate::new(400)
+                .set_body_json(error_body)
+                .insert_header("x-request-id", "req_123"),
+        )
+        .mount(&test_server.server)
+        .await;
+
+    let model = OpenAIChatModel::new("gpt-4", "test-key")
+        .with_base_url(format!("{}/v1", test_server.base_url));
+    let options = CallOptions {
+        prompt: vec![Message::User {
+            content: vec![UserContentPart::Text {
+                text: "Hello".to_string(),
+            }],
+        }],
+        ..Default::default()
+    };
+
+    let error = model.do_generate(options.clone()).await.unwrap_err();
+    match error.downcast_ref::<OpenAIError>() {
+        Some(OpenAIError::ApiError {
+            message,
+            error_type,
+            param,
+            code,
+            ..
+        }) => {
+            assert_eq!(message, "Invalid value for 'temperature'");
+            assert_eq!(error_type.as_deref(), Some("invalid_request_error"));
+            assert_eq!(param.as_deref(), Some("temperature"));
+            assert_eq!(*code, None);
+        }
+        other => panic!("Expected OpenAIError::ApiError, got {:?}", other),
+    }
+
+    let api_error = ApiCallError::find(error.as_ref()).expect("ApiCallError");
+    assert_eq!(api_error.status_code, Some(400));
+    assert!(!api_error.is_retryable);
+    assert_eq!(
+        api_error.url,
+        format!("{}/v1/chat/completions", test_server.base_url)
+    );
+    assert_eq!(api_error.header("x-request-id"), Some("req_123"));
+    assert!(api_error
+        .response_body
+        .as_deref()
+        .unwrap()
+        .contains("invalid_request_error"));
+
+    // Streaming reports the same error before any part is produced
+    let error = match model.do_stream(options).await {
+        Ok(_) => panic!("Stream should fail"),
+        Err(error) => error,
+    };
+    assert_eq!(
+        ApiCallError::find(error.as_ref()).unwrap().status_code,
+        Some(400)
+    );
+}
+
+#[tokio::test]
+async fn test_network_error_is_retryable() {
+    // Nothing listens on port 9 (discard) on the loopback interface
+    let model = OpenAIChatModel::new("gpt-4", "test-key").with_base_url("http://127.0.0.1:9/v1");
+
+    let error = model
+        .do_generate(CallOptions {
+            prompt: vec![Message::User {
+                content: vec![UserContentPart::Text {
+                    text: "Hello".to_string(),
+                }],
+            }],
+            ..Default::default()
+        })
+        .await
+        .unwrap_err();
+
+    let api_error = ApiCallError::find(error.as_ref()).expect("ApiCallError");
+    assert_eq!(api_error.status_code, None);
+    assert!(api_error.is_retryable);
+}
+
+#[tokio::test]
+async fn test_abort_cancels_in_flight_request() {
+    let test_server = TestServer::new().await;
+    test_server
+        .mock_delayed_json_response(
+            "/v1/chat/completions",
+            load_json_fixture("chat-completion-simple-1"),
+            std::time::Duration::from_secs(30),
+        )
+        .await;
+
+    let model = OpenAIChatModel::new("gpt-4", "test-key")
+        .with_base_url(format!("{}/v1", test_server.base_url));
+    let controller = AbortController::new();
+    let options = CallOptions {
+        prompt: vec![Message::User {
+            content: vec![UserContentPart::Text {
+                text: "Hello".to_string(),
+            }],
+        }],
+        abort_signal: Some(controller.signal()),
+        ..Default::default()
+    };
+
+    let started = std::time::Instant::now();
+    let request = tokio::spawn(async move { model.do_generate(options).await });
+    tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+    controller.abort();
+
+    let error = request.await.unwrap().unwrap_err();
+    assert!(AbortError::is_abort(error.as_ref()));
+    assert!(started.elapsed() < std::time::Duration::from_secs(5));
+}
@@ -124,6 +124,65 @@ async fn test_pass_settings() {
         .expect("Generate should succeed");
 }
 
+#[tokio::test]
+async fn test_pass_sampling_settings() {
+    let test_server = TestServer::new().await;
+
+    let response_json = json!({
+        "id": "chatcmpl-test",
+        "object": "chat.completion",
+        "created": 1711115037,
+        "model": "gpt-3.5-turbo",
+        "choices": [{
+            "index": 0,
+            "message": {
+                "role": "assistant",
+                "content": ""
+            },
+            "finish_reason": "stop"
+        }],
+        "usage": {
+            "prompt_tokens": 4,
+            "completion_tokens": 1,
+            "total_tokens": 5
+        }
+    });
+
+    test_server
+        .mock_json_response("/v1/chat/completions", response_json)
+        .await;
+
+    let model = OpenAIChatModel::new("gpt-3.5-turbo", "test-key")
+        .with_base_url(format!("{}/v1", test_server.base_url));
+
+    let _ = model
+        .do_generate(CallOptions {
+            prompt: vec![Message::User {
+                content: vec![UserContentPart::Text {
+                    text: "Hello".to_string(),
+                }],
+            }],
+            top_p: Some(0.5),
+            frequency_penalty: Some(0.25),
+            presence_penalty: Some(-0.5),
+            stop_sequences: Some(vec!["END".to_string()]),
+            seed: Some(42),
+            top_k: Some(10),
+            ..Default::default()
+        })
+        .await
+        .expect("Generate should succeed");
+
+    let request = test_server.last_request_body().await.unwrap();
+    assert_eq!(request["top_p"], json!(0.5));
+    assert_eq!(request["frequency_penalty"], json!(0.25));
+    assert_eq!(request["presence_penalty"], json!(-0.5));
+    assert_eq!(request["stop"], json!(["END"]));
+    assert_eq!(request["seed"], json!(42));
+    // Chat completions has no top_k parameter
+    assert!(request.get("top_k").is_none());
+}
+
 #[tokio::test]
 async fn test_pass_reasoning_effort_from_provider_metadata() {
     // TypeScript reference: line 486
@@ -1,6 +1,9 @@
 use crate::common::{load_chunks_fixture, load_json_fixture, TestServer};
 use ai_sdk_openai::OpenAIChatModel;
-use ai_sdk_provider::language_model::{FunctionTool, Message, Tool, ToolChoice, UserContentPart};
+use ai_sdk_provider::language_model::{
+    AssistantContentPart, ContentPart, FunctionTool, Message, ReasoningPart, Tool, ToolChoice,
+    ToolResultOutput, ToolResultPart, UserContentPart,
+};
 use ai_sdk_provider::{CallOptions, Content, FinishReason, LanguageModel, StreamPart};
 use futures::stream::StreamExt;
 
@@ -352,3 +355,95 @@ async fn test_no_tool_calls_when_not_needed_with_fixture() {
         panic!("Expected text content in response");
     }
 }
+
+#[tokio::test]
+async fn test_tool_results_sent_as_their_content() {
+    let test_server = TestServer::new().await;
+    test_server
+        .mock_json_response(
+            "/v1/chat/completions",
+            load_json_fixture("chat-no-tool-call-1"),
+        )
+        .await;
+
+    let model = OpenAIChatModel::new("gpt-4", "test-key")
+        .with_base_url(format!("{}/v1", test_server.base_url));
+
+    let tool_result = |id: &str, output: ToolResultOutput| ToolResultPart {
+        tool_call_id: id.to_string(),
+        tool_name: "get_weather".to_string(),
+        output,
+        provider_executed: None,
+        preliminary: None,
+        provider_metadata: None,
+    };
+
+    let options = CallOptions {
+        prompt: vec![
+            Message::Assistant {
+                content: vec![AssistantContentPart::Reasoning(ReasoningPart {
+                    reasoning: "I should check the weather".to_string(),
+                    provider_metadata: None,
+                })],
+            },
+            Message::Tool {
+                content: vec![
+                    tool_result(
+                        "call_1",
+                        ToolResultOutput::Json {
+                            value: serde_json::from_value(serde_json::json!({ "temp": 20 }))
+                                .unwrap(),
+                            provider_metadata: None,
+                        },
+                    ),
+                    tool_result(
+                        "call_2",
+                        ToolResultOutput::ExecutionDenied {
+                            reason: None,
+                            provider_metadata: None,
+                        },
+                    ),
+                    tool_result(
+                        "call_3",
+                        ToolResultOutput::Content {
+                            value: vec![
+                                ContentPart::Text {
+                                    text: "Radar".to_string(),
+                                    provider_metadata: None,
+                                },
+                                ContentPart::ImageData {
+                                    data: "iVBORw0KGgo=".to_string(),
+                                    media_type: "image/png".to_string(),
+                                    provider_metadata: None,
+                                },
+                            ],
+                            provider_metadata: None,
+                        },
+                    ),
+                ],
+            },
+        ],
+        ..Default::default()
+    };
+
+    model
+        .do_generate(options)
+        .await
+        .expect("Failed to generate response");
+
+    let request = test_server.last_request_body().await.unwrap();
+    let messages = request["messages"].as_array().unwrap();
+    assert_eq!(messages[0]["role"], "assistant");
+    assert_eq!(messages[1]["content"], r#"{"temp":20}"#);
+    assert_eq!(messages[2]["content"], "Tool execution denied.");
+    assert_eq!(
+        messages[3]["content"],
+        serde_json::json!([
+            { "type": "text", "text": "Radar" },
+            {
+                "type": "image_url",
+                "image_url": { "url": "data:image/png;base64,iVBORw0KGgo=" }
+            }
+        ])
+    );
+}
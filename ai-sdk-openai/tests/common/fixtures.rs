@@ -1,15 +1,30 @@
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-/// Loads a JSON fixture from the chat fixtures directory
+/// Fixture directories, searched in order
+///
+/// Chat completions fixtures live in `tests/chat/fixtures`, Responses API
+/// fixtures in `tests/fixtures`.
+const FIXTURE_DIRS: [&str; 2] = ["tests/chat/fixtures", "tests/fixtures"];
+
+/// Resolves a fixture file name against the fixture directories
+fn fixture_path(file_name: &str) -> PathBuf {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+
+    FIXTURE_DIRS
+        .iter()
+        .map(|dir| root.join(dir).join(file_name))
+        .find(|path| path.exists())
+        .unwrap_or_else(|| root.join(FIXTURE_DIRS[0]).join(file_name))
+}
+
+/// Loads a JSON fixture from the fixture directories
 ///
 /// # Arguments
 /// * `filename` - The fixture name without extension (e.g., "chat-completion-simple-1")
 #[allow(dead_code)]
 pub fn load_json_fixture(filename: &str) -> serde_json::Value {
-    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
-        .join("tests/chat/fixtures")
-        .join(format!("{}.json", filename));
+    let path = fixture_path(&format!("{}.json", filename));
 
     let content = fs::read_to_string(&path)
         .unwrap_or_else(|_| panic!("Failed to read fixture: {}", path.display()));
@@ -18,15 +33,13 @@ pub fn load_json_fixture(filename: &str) -> serde_json::Value {
         .unwrap_or_else(|e| panic!("Failed to parse JSON fixture at {}: {}", path.display(), e))
 }
 
-/// Loads a chunks fixture (streaming responses) from the chat fixtures directory
+/// Loads a chunks fixture (streaming responses) from the fixture directories
 ///
 /// # Arguments
 /// * `filename` - The fixture name without extension (e.g., "chat-completion-simple-1")
 #[allow(dead_code)]
 pub fn load_chunks_fixture(filename: &str) -> Vec<String> {
-    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
-        .join("tests/chat/fixtures")
-        .join(format!("{}-chunks.txt", filename));
+    let path = fixture_path(&format!("{}-chunks.txt", filename));
 
     let content = fs::read_to_string(&path)
         .unwrap_or_else(|_| panic!("Failed to read chunks fixture: {}", path.display()));
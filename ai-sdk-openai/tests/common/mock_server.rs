@@ -35,6 +35,31 @@ impl TestServer {
             .await;
     }
 
+    /// Mocks a JSON response that is only sent after `delay`
+    ///
+    /// # Arguments
+    /// * `endpoint` - The API endpoint path (e.g., "/v1/chat/completions")
+    /// * `response_body` - The JSON response body
+    /// * `delay` - How long the server waits before responding
+    #[allow(dead_code)]
+    pub async fn mock_delayed_json_response(
+        &self,
+        endpoint: &str,
+        response_body: serde_json::Value,
+        delay: std::time::Duration,
+    ) {
+        Mock::given(method("POST"))
+            .and(path(endpoint))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(response_body)
+                    .insert_header("content-type", "application/json")
+                    .set_delay(delay),
+            )
+            .mount(&self.server)
+            .await;
+    }
+
     /// Mocks a streaming SSE response for a POST endpoint
     ///
     /// # Arguments
@@ -1,9 +1,12 @@
 mod common;
 
-use ai_sdk_openai::OpenAIEmbeddingModel;
-use ai_sdk_provider::{EmbedOptions, EmbeddingModel};
+use ai_sdk_openai::{OpenAIEmbeddingModel, OpenAIError};
+use ai_sdk_provider::{ApiCallError, EmbedOptions, EmbeddingModel};
 use common::TestServer;
 use serde_json::json;
+use std::time::Duration;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, ResponseTemplate};
 
 // Dummy embeddings for testing (like TypeScript version)
 const DUMMY_EMBEDDINGS: [[f32; 5]; 2] = [[0.1, 0.2, 0.3, 0.4, 0.5], [0.6, 0.7, 0.8, 0.9, 1.0]];
@@ -204,3 +207,50 @@ async fn test_openai_embedding_pass_model_and_values() {
     assert_eq!(request_body["input"], json!(test_values));
     assert_eq!(request_body["encoding_format"], "float");
 }
+
+#[tokio::test]
+async fn test_embedding_api_error() {
+    let test_server = TestServer::new().await;
+    Mock::given(method("POST"))
+        .and(path("/v1/embeddings"))
+        .respond_with(
+            ResponseTemplate::new(429)
+                .set_body_json(json!({
+                    "error": { "message": "Rate limit reached", "type": "requests" }
+                }))
+                .insert_header("retry-after", "3")
+                .insert_header("x-ratelimit-remaining-requests", "0"),
+        )
+        .mount(&test_server.server)
+        .await;
+
+    let model = OpenAIEmbeddingModel::new("text-embedding-3-small", "test-key")
+        .with_base_url(format!("{}/v1", test_server.base_url));
+
+    let error = model
+        .do_embed(EmbedOptions {
+            values: vec!["hello".into()],
+            provider_options: None,
+            headers: None,
+        })
+        .await
+        .unwrap_err();
+
+    match error.downcast_ref::<OpenAIError>() {
+        Some(OpenAIError::RateLimitError {
+            message,
+            retry_after,
+            rate_limit,
+            ..
+        }) => {
+            assert_eq!(message, "Rate limit reached");
+            assert_eq!(*retry_after, Some(Duration::from_secs(3)));
+            assert_eq!(rate_limit.remaining_requests, Some(0));
+        }
+        other => panic!("Expected OpenAIError::RateLimitError, got {:?}", other),
+    }
+
+    let api_error = ApiCallError::find(error.as_ref()).expect("ApiCallError");
+    assert_eq!(api_error.status_code, Some(429));
+    assert!(api_error.is_retryable);
+}
@@ -1,6 +1,6 @@
 mod common;
 
-use ai_sdk_openai::OpenAIImageModel;
+use ai_sdk_openai::{OpenAIError, OpenAIImageModel};
 use ai_sdk_provider::{ImageData, ImageGenerateOptions, ImageModel};
 use common::TestServer;
 use serde_json::json;
@@ -130,3 +130,50 @@ async fn test_openai_image_pass_model_and_settings() {
 
     assert_eq!(request_body, expected);
 }
+
+#[tokio::test]
+async fn test_openai_image_authentication_error() {
+    let test_server = TestServer::new().await;
+    test_server
+        .mock_error_response(
+            "/v1/images/generations",
+            401,
+            Some(json!({
+                "error": {
+                    "message": "Incorrect API key provided: test-key.",
+                    "type": "invalid_request_error",
+                    "param": null,
+                    "code": "invalid_api_key"
+                }
+            })),
+        )
+        .await;
+
+    let model = OpenAIImageModel::new("dall-e-3", "test-key")
+        .with_base_url(format!("{}/v1", test_server.base_url));
+
+    let error = model
+        .do_generate(ImageGenerateOptions {
+            prompt: "A cute baby sea otter".into(),
+            n: 1,
+            size: None,
+            aspect_ratio: None,
+            seed: None,
+            provider_options: None,
+            headers: None,
+        })
+        .await
+        .unwrap_err();
+
+    assert!(matches!(
+        error.downcast_ref::<OpenAIError>(),
+        Some(OpenAIError::AuthenticationError {
+            status_code: 401,
+            ..
+        })
+    ));
+    assert_eq!(
+        error.to_string(),
+        "Authentication failed: Incorrect API key provided: test-key."
+    );
+}
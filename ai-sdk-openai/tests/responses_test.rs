@@ -0,0 +1,940 @@
+mod common;
+
+use ai_sdk_openai::tools::{
+    self, CodeInterpreterArgs, CodeInterpreterInput, CodeInterpreterOutput, CodeInterpreterResult,
+    FileSearchArgs, FileSearchResult, ImageGenerationResult, LocalShellInput, McpApproval, McpArgs,
+    McpCallResult, SearchContextSize, WebSearchAction, WebSearchArgs, WebSearchResult,
+};
+use ai_sdk_openai::OpenAIResponsesModel;
+use ai_sdk_provider::language_model::{
+    AssistantContentPart, FunctionTool, Message, ReasoningPart, SourceType, TextPart, Tool,
+    ToolCallPart, ToolResultOutput, ToolResultPart, UserContentPart,
+};
+use ai_sdk_provider::*;
+use common::{load_chunks_fixture, load_json_fixture, TestServer};
+use serde_json::json;
+use tokio_stream::StreamExt;
+
+fn create_model(test_server: &TestServer, model_id: &str) -> OpenAIResponsesModel {
+    OpenAIResponsesModel::new(model_id, "test-key")
+        .with_base_url(format!("{}/v1", test_server.base_url))
+}
+
+fn user_prompt(text: &str) -> Vec<Message> {
+    vec![Message::User {
+        content: vec![UserContentPart::Text { text: text.into() }],
+    }]
+}
+
+fn tool_calls(content: &[Content]) -> Vec<&ToolCallPart> {
+    content
+        .iter()
+        .filter_map(|c| match c {
+            Content::ToolCall(call) => Some(call),
+            _ => None,
+        })
+        .collect()
+}
+
+fn tool_results(content: &[Content]) -> Vec<&ToolResultPart> {
+    content
+        .iter()
+        .filter_map(|c| match c {
+            Content::ToolResult(result) => Some(result),
+            _ => None,
+        })
+        .collect()
+}
+
+async fn collect_stream(model: &OpenAIResponsesModel, options: CallOptions) -> Vec<StreamPart> {
+    let mut response = model.do_stream(options).await.expect("Stream should start");
+
+    let mut parts = vec![];
+    while let Some(part) = response.stream.next().await {
+        parts.push(part.expect("Stream part should be ok"));
+    }
+    parts
+}
+
+fn streamed_text(parts: &[StreamPart]) -> String {
+    parts
+        .iter()
+        .filter_map(|part| match part {
+            StreamPart::TextDelta { delta, .. } => Some(delta.as_str()),
+            _ => None,
+        })
+        .collect()
+}
+
+fn finish_part(parts: &[StreamPart]) -> (&Usage, &FinishReason) {
+    match parts.last() {
+        Some(StreamPart::Finish {
+            usage,
+            finish_reason,
+            ..
+        }) => (usage, finish_reason),
+        other => panic!("Expected finish part, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_responses_model_trait() {
+    let model = OpenAIResponsesModel::new("gpt-5", "test-key");
+
+    assert_eq!(model.provider(), "openai");
+    assert_eq!(model.model_id(), "gpt-5");
+}
+
+#[tokio::test]
+async fn test_generate_web_search() {
+    let test_server = TestServer::new().await;
+    test_server
+        .mock_json_response(
+            "/v1/responses",
+            load_json_fixture("openai-web-search-tool-1"),
+        )
+        .await;
+
+    let model = create_model(&test_server, "gpt-4o-mini");
+    let response = model
+        .do_generate(CallOptions {
+            prompt: user_prompt("What happened in San Francisco last week?"),
+            tools: Some(vec![Tool::ProviderDefined(tools::web_search(
+                WebSearchArgs {
+                    search_context_size: Some(SearchContextSize::Medium),
+                    ..Default::default()
+                },
+            ))]),
+            ..Default::default()
+        })
+        .await
+        .expect("Request should succeed");
+
+    // Built-in tool calls and results are provider executed
+    let calls = tool_calls(&response.content);
+    assert_eq!(calls.len(), 2);
+    assert!(calls
+        .iter()
+        .all(|c| c.tool_name == "web_search" && c.provider_executed == Some(true)));
+    assert_eq!(calls[0].tool_call_id, "ws_67cf2b3051e88190b006770db6fdb13d");
+
+    let results = tool_results(&response.content);
+    assert_eq!(results.len(), 2);
+    assert!(results.iter().all(|r| r.provider_executed == Some(true)));
+    let result: WebSearchResult = tools::parse_tool_output(&results[0].output).unwrap();
+    assert_eq!(result.status.as_deref(), Some("completed"));
+    match result.action {
+        Some(WebSearchAction::Search { query, sources }) => {
+            assert_eq!(
+                query.as_deref(),
+                Some("Vercel AI SDK next version features")
+            );
+            assert_eq!(sources.len(), 2);
+        }
+        other => panic!("Expected search action, got {:?}", other),
+    }
+
+    // Text and url citations
+    let text: String = response
+        .content
+        .iter()
+        .filter_map(|c| match c {
+            Content::Text(t) => Some(t.text.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert!(text.starts_with("Last week in San Francisco"));
+
+    let sources: Vec<_> = response
+        .content
+        .iter()
+        .filter_map(|c| match c {
+            Content::Source(s) => Some(s),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(sources.len(), 5);
+    assert_eq!(sources[0].source_type, SourceType::Url);
+    assert_eq!(
+        sources[0].title.as_deref(),
+        Some("Bruce Lee statue to be installed in SF Chinatown")
+    );
+
+    assert_eq!(response.finish_reason, FinishReason::Stop);
+    assert_eq!(response.usage.input_tokens, Some(327));
+    assert_eq!(response.usage.output_tokens, Some(770));
+    assert_eq!(response.usage.total_tokens, Some(1097));
+
+    let response_info = response.response.expect("Response info should be set");
+    assert_eq!(
+        response_info.id.as_deref(),
+        Some("resp_67cf2b2f6bd081909be2c8054ddef0eb")
+    );
+    assert_eq!(
+        response_info.timestamp.as_deref(),
+        Some("2025-03-10T18:10:55Z")
+    );
+    assert_eq!(response_info.model_id.as_deref(), Some("gpt-4o-2024-07-18"));
+
+    // Provider-defined tool args are sent as-is with the tool type
+    let request = test_server.last_request_body().await.unwrap();
+    assert_eq!(
+        request["tools"],
+        json!([{ "type": "web_search", "search_context_size": "medium" }])
+    );
+    assert_eq!(
+        request["input"],
+        json!([{
+            "role": "user",
+            "content": [{ "type": "input_text", "text": "What happened in San Francisco last week?" }]
+        }])
+    );
+}
+
+#[tokio::test]
+async fn test_generate_code_interpreter() {
+    let test_server = TestServer::new().await;
+    test_server
+        .mock_json_response(
+            "/v1/responses",
+            load_json_fixture("openai-code-interpreter-tool-1"),
+        )
+        .await;
+
+    let model = create_model(&test_server, "gpt-5-nano");
+    let response = model
+        .do_generate(CallOptions {
+            prompt: user_prompt("Simulate rolling two dice 10000 times."),
+            tools: Some(vec![Tool::ProviderDefined(tools::code_interpreter(
+                CodeInterpreterArgs::default(),
+            ))]),
+            ..Default::default()
+        })
+        .await
+        .expect("Request should succeed");
+
+    let reasoning_count = response
+        .content
+        .iter()
+        .filter(|c| matches!(c, Content::Reasoning(_)))
+        .count();
+    assert_eq!(reasoning_count, 4);
+
+    let calls = tool_calls(&response.content);
+    assert_eq!(calls.len(), 3);
+    let input: CodeInterpreterInput = tools::parse_tool_input(&calls[0].input).unwrap();
+    assert_eq!(
+        input.container_id,
+        "cntr_6903bf2c0470819090b2b1e63e0b66800c139a5d654a42ec"
+    );
+    assert!(input.code.unwrap().starts_with("import random"));
+
+    let results = tool_results(&response.content);
+    let result: CodeInterpreterResult = tools::parse_tool_output(&results[0].output).unwrap();
+    assert_eq!(
+        result.outputs,
+        Some(vec![CodeInterpreterOutput::Logs {
+            logs: "(10000, 70024)".into()
+        }])
+    );
+
+    let request = test_server.last_request_body().await.unwrap();
+    assert_eq!(
+        request["tools"],
+        json!([{ "type": "code_interpreter", "container": { "type": "auto" } }])
+    );
+
+    // Container file citations become document sources
+    let source = response
+        .content
+        .iter()
+        .find_map(|c| match c {
+            Content::Source(s) => Some(s),
+            _ => None,
+        })
+        .expect("Should have a source");
+    assert_eq!(source.source_type, SourceType::Document);
+    assert_eq!(source.title.as_deref(), Some("two_dice_sums_10000.txt"));
+
+    assert_eq!(response.usage.reasoning_tokens, Some(1792));
+}
+
+#[tokio::test]
+async fn test_generate_file_search() {
+    let test_server = TestServer::new().await;
+    test_server
+        .mock_json_response(
+            "/v1/responses",
+            load_json_fixture("openai-file-search-tool-1"),
+        )
+        .await;
+
+    let model = create_model(&test_server, "gpt-5-mini");
+    let response = model
+        .do_generate(CallOptions {
+            prompt: user_prompt("What is an embedding model?"),
+            tools: Some(vec![Tool::ProviderDefined(tools::file_search(
+                FileSearchArgs {
+                    vector_store_ids: vec!["vs_68caad8bd5d88191ab766cf043d89a18".into()],
+                    max_num_results: Some(20),
+                    ..Default::default()
+                },
+            ))]),
+            ..Default::default()
+        })
+        .await
+        .expect("Request should succeed");
+
+    let calls = tool_calls(&response.content);
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].tool_name, "file_search");
+
+    let result: FileSearchResult =
+        tools::parse_tool_output(&tool_results(&response.content)[0].output).unwrap();
+    assert_eq!(result.queries.len(), 4);
+    assert_eq!(result.results, None);
+
+    let source = response
+        .content
+        .iter()
+        .find_map(|c| match c {
+            Content::Source(s) => Some(s),
+            _ => None,
+        })
+        .expect("Should have a source");
+    assert_eq!(source.source_type, SourceType::Document);
+    assert_eq!(source.title.as_deref(), Some("ai.pdf"));
+}
+
+#[tokio::test]
+async fn test_generate_image_generation() {
+    let test_server = TestServer::new().await;
+    test_server
+        .mock_json_response(
+            "/v1/responses",
+            load_json_fixture("openai-image-generation-tool-1"),
+        )
+        .await;
+
+    let model = create_model(&test_server, "gpt-5");
+    let response = model
+        .do_generate(CallOptions {
+            prompt: user_prompt("Generate an image of a cat."),
+            ..Default::default()
+        })
+        .await
+        .expect("Request should succeed");
+
+    let calls = tool_calls(&response.content);
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].tool_name, "image_generation");
+
+    let result: ImageGenerationResult =
+        tools::parse_tool_output(&tool_results(&response.content)[0].output).unwrap();
+    assert!(result.result.unwrap().starts_with("UklGR"));
+}
+
+#[tokio::test]
+async fn test_generate_local_shell() {
+    let test_server = TestServer::new().await;
+    test_server
+        .mock_json_response(
+            "/v1/responses",
+            load_json_fixture("openai-local-shell-tool-1"),
+        )
+        .await;
+
+    let model = create_model(&test_server, "codex-mini-latest");
+    let response = model
+        .do_generate(CallOptions {
+            prompt: user_prompt("List the files in my home directory."),
+            ..Default::default()
+        })
+        .await
+        .expect("Request should succeed");
+
+    // Local shell commands are executed by the client
+    let calls = tool_calls(&response.content);
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].tool_name, "local_shell");
+    assert_eq!(calls[0].tool_call_id, "call_XWgeTylovOiS8xLNz2TONOgO");
+    assert_eq!(calls[0].provider_executed, None);
+
+    let input: LocalShellInput = tools::parse_tool_input(&calls[0].input).unwrap();
+    assert_eq!(input.action.command, vec!["ls".to_string()]);
+    assert_eq!(input.action.working_directory.as_deref(), Some("/root"));
+    assert!(tool_results(&response.content).is_empty());
+
+    assert_eq!(response.finish_reason, FinishReason::ToolCalls);
+}
+
+#[tokio::test]
+async fn test_generate_mcp() {
+    let test_server = TestServer::new().await;
+    test_server
+        .mock_json_response("/v1/responses", load_json_fixture("openai-mcp-tool-1"))
+        .await;
+
+    let model = create_model(&test_server, "gpt-5");
+    let response = model
+        .do_generate(CallOptions {
+            prompt: user_prompt("Who won the NYC mayoral election?"),
+            tools: Some(vec![Tool::ProviderDefined(tools::mcp(McpArgs {
+                server_label: "dmcp".into(),
+                server_url: Some("https://mcp.exa.ai/mcp".into()),
+                require_approval: Some(McpApproval::Never),
+                ..Default::default()
+            }))]),
+            ..Default::default()
+        })
+        .await
+        .expect("Request should succeed");
+
+    let calls = tool_calls(&response.content);
+    assert_eq!(calls.len(), 1);
+    assert_eq!(calls[0].tool_name, "mcp");
+    assert_eq!(calls[0].provider_executed, Some(true));
+
+    let result: McpCallResult =
+        tools::parse_tool_output(&tool_results(&response.content)[0].output).unwrap();
+    assert_eq!(result.server_label, "dmcp");
+    assert_eq!(result.name, "web_search_exa");
+    assert!(result.output.is_some());
+    assert_eq!(result.error, None);
+
+    let request = test_server.last_request_body().await.unwrap();
+    assert_eq!(
+        request["tools"],
+        json!([{
+            "type": "mcp",
+            "server_label": "dmcp",
+            "server_url": "https://mcp.exa.ai/mcp",
+            "require_approval": "never"
+        }])
+    );
+    assert_eq!(response.finish_reason, FinishReason::Stop);
+}
+
+#[tokio::test]
+async fn test_generate_converts_tool_round_trip() {
+    let test_server = TestServer::new().await;
+    test_server
+        .mock_json_response(
+            "/v1/responses",
+            load_json_fixture("openai-web-search-tool-1"),
+        )
+        .await;
+
+    let model = create_model(&test_server, "gpt-4o");
+    model
+        .do_generate(CallOptions {
+            prompt: vec![
+                Message::System {
+                    content: "You are helpful.".into(),
+                },
+                Message::User {
+                    content: vec![UserContentPart::Text {
+                        text: "Weather in Berlin?".into(),
+                    }],
+                },
+                Message::Assistant {
+                    content: vec![
+                        AssistantContentPart::Text(TextPart {
+                            text: "Let me check.".into(),
+                            provider_metadata: None,
+                        }),
+                        AssistantContentPart::ToolCall(ToolCallPart {
+                            tool_call_id: "call_1".into(),
+                            tool_name: "get_weather".into(),
+                            input: r#"{"city":"Berlin"}"#.into(),
+                            provider_executed: None,
+                            dynamic: None,
+                            provider_metadata: None,
+                        }),
+                    ],
+                },
+                Message::Tool {
+                    content: vec![ToolResultPart {
+                        tool_call_id: "call_1".into(),
+                        tool_name: "get_weather".into(),
+                        output: ToolResultOutput::Text {
+                            value: "Sunny, 22°C".into(),
+                            provider_metadata: None,
+                        },
+                        provider_executed: None,
+                        preliminary: None,
+                        provider_metadata: None,
+                    }],
+                },
+            ],
+            tools: Some(vec![Tool::Function(FunctionTool {
+                name: "get_weather".into(),
+                description: Some("Get the weather".into()),
+                input_schema: json!({ "type": "object" }),
+                provider_options: None,
+            })]),
+            tool_choice: Some(language_model::ToolChoice::Tool {
+                tool_name: "get_weather".into(),
+            }),
+            ..Default::default()
+        })
+        .await
+        .expect("Request should succeed");
+
+    let request = test_server.last_request_body().await.unwrap();
+    assert_eq!(
+        request["input"],
+        json!([
+            { "role": "system", "content": "You are helpful." },
+            { "role": "user", "content": [{ "type": "input_text", "text": "Weather in Berlin?" }] },
+            { "role": "assistant", "content": [{ "type": "output_text", "text": "Let me check." }] },
+            {
+                "type": "function_call",
+                "call_id": "call_1",
+                "name": "get_weather",
+                "arguments": "{\"city\":\"Berlin\"}"
+            },
+            { "type": "function_call_output", "call_id": "call_1", "output": "Sunny, 22°C" }
+        ])
+    );
+    assert_eq!(
+        request["tools"],
+        json!([{
+            "type": "function",
+            "name": "get_weather",
+            "description": "Get the weather",
+            "parameters": { "type": "object" }
+        }])
+    );
+    assert_eq!(
+        request["tool_choice"],
+        json!({ "type": "function", "name": "get_weather" })
+    );
+}
+
+fn reasoning_part(text: &str, item_id: Option<&str>, encrypted: Option<&str>) -> ReasoningPart {
+    let mut openai = json_value::JsonObject::new();
+    if let Some(item_id) = item_id {
+        openai.insert(
+            "itemId".into(),
+            json_value::JsonValue::String(item_id.into()),
+        );
+    }
+    if let Some(encrypted) = encrypted {
+        openai.insert(
+            "reasoningEncryptedContent".into(),
+            json_value::JsonValue::String(encrypted.into()),
+        );
+    }
+    ReasoningPart {
+        reasoning: text.into(),
+        provider_metadata: Some([("openai".to_string(), openai)].into()),
+    }
+}
+
+fn reasoning_prompt() -> Vec<Message> {
+    vec![
+        Message::User {
+            content: vec![UserContentPart::Text {
+                text: "Weather in Berlin?".into(),
+            }],
+        },
+        Message::Assistant {
+            content: vec![
+                AssistantContentPart::Reasoning(reasoning_part(
+                    "Need the weather",
+                    Some("rs_1"),
+                    None,
+                )),
+                AssistantContentPart::Reasoning(reasoning_part(
+                    "Call the tool",
+                    Some("rs_1"),
+                    Some("enc_1"),
+                )),
+                AssistantContentPart::Reasoning(reasoning_part(
+                    "From another provider",
+                    None,
+                    None,
+                )),
+                AssistantContentPart::Text(TextPart {
+                    text: "Let me check.".into(),
+                    provider_metadata: None,
+                }),
+            ],
+        },
+    ]
+}
+
+#[tokio::test]
+async fn test_generate_converts_reasoning_parts() {
+    let test_server = TestServer::new().await;
+    test_server
+        .mock_json_response(
+            "/v1/responses",
+            load_json_fixture("openai-web-search-tool-1"),
+        )
+        .await;
+    let model = create_model(&test_server, "o3-mini");
+
+    // Stored reasoning is referenced by its item id
+    let response = model
+        .do_generate(CallOptions {
+            prompt: reasoning_prompt(),
+            ..Default::default()
+        })
+        .await
+        .expect("Request should succeed");
+
+    let request = test_server.last_request_body().await.unwrap();
+    assert_eq!(
+        request["input"],
+        json!([
+            { "role": "user", "content": [{ "type": "input_text", "text": "Weather in Berlin?" }] },
+            { "type": "item_reference", "id": "rs_1" },
+            { "role": "assistant", "content": [{ "type": "output_text", "text": "Let me check." }] }
+        ])
+    );
+    assert_eq!(response.warnings.len(), 1);
+
+    // Without storage the reasoning is replayed with its encrypted content
+    let mut openai_options = json_value::JsonObject::new();
+    openai_options.insert("store".into(), json_value::JsonValue::Bool(false));
+    openai_options.insert(
+        "include".into(),
+        json_value::JsonValue::Array(vec![json_value::JsonValue::String(
+            "reasoning.encrypted_content".into(),
+        )]),
+    );
+    model
+        .do_generate(CallOptions {
+            prompt: reasoning_prompt(),
+            provider_options: Some([("openai".to_string(), openai_options)].into()),
+            ..Default::default()
+        })
+        .await
+        .expect("Request should succeed");
+
+    let request = test_server.last_request_body().await.unwrap();
+    assert_eq!(request["include"], json!(["reasoning.encrypted_content"]));
+    assert_eq!(
+        request["input"][1],
+        json!({
+            "type": "reasoning",
+            "id": "rs_1",
+            "encrypted_content": "enc_1",
+            "summary": [
+                { "type": "summary_text", "text": "Need the weather" },
+                { "type": "summary_text", "text": "Call the tool" }
+            ]
+        })
+    );
+}
+
+#[tokio::test]
+async fn test_generate_provider_options_and_warnings() {
+    let test_server = TestServer::new().await;
+    test_server
+        .mock_json_response(
+            "/v1/responses",
+            load_json_fixture("openai-local-shell-tool-1"),
+        )
+        .await;
+
+    let mut openai_options = json_value::JsonObject::new();
+    openai_options.insert(
+        "reasoningEffort".into(),
+        json_value::JsonValue::String("low".into()),
+    );
+    openai_options.insert(
+        "previousResponseId".into(),
+        json_value::JsonValue::String("resp_123".into()),
+    );
+    openai_options.insert("store".into(), json_value::JsonValue::Bool(false));
+
+    let model = create_model(&test_server, "o3-mini");
+    let response = model
+        .do_generate(CallOptions {
+            prompt: user_prompt("Hello"),
+            temperature: Some(0.5),
+            seed: Some(42),
+            provider_options: Some([("openai".to_string(), openai_options)].into()),
+            ..Default::default()
+        })
+        .await
+        .expect("Request should succeed");
+
+    let request = test_server.last_request_body().await.unwrap();
+    assert_eq!(request["reasoning"], json!({ "effort": "low" }));
+    assert_eq!(request["previous_response_id"], "resp_123");
+    assert_eq!(request["store"], false);
+    assert!(request.get("temperature").is_none());
+    assert!(request.get("seed").is_none());
+
+    // Reasoning models drop temperature; seed is not supported by the endpoint
+    assert_eq!(response.warnings.len(), 2);
+}
+
+#[tokio::test]
+async fn test_generate_api_error() {
+    let test_server = TestServer::new().await;
+    test_server
+        .mock_error_response("/v1/responses", 500, None)
+        .await;
+
+    let model = create_model(&test_server, "gpt-5");
+    let result = model
+        .do_generate(CallOptions {
+            prompt: user_prompt("Hello"),
+            ..Default::default()
+        })
+        .await;
+
+    let error = result.unwrap_err();
+    let api_error = ApiCallError::find(error.as_ref()).expect("ApiCallError");
+    assert_eq!(api_error.status_code, Some(500));
+    assert!(api_error.is_retryable);
+    assert!(api_error.url.ends_with("/v1/responses"));
+}
+
+#[tokio::test]
+async fn test_stream_web_search() {
+    let test_server = TestServer::new().await;
+    test_server
+        .mock_streaming_response(
+            "/v1/responses",
+            load_chunks_fixture("openai-web-search-tool-1"),
+        )
+        .await;
+
+    let model = create_model(&test_server, "gpt-5-nano");
+    let parts = collect_stream(
+        &model,
+        CallOptions {
+            prompt: user_prompt("What's happening in Berlin today?"),
+            ..Default::default()
+        },
+    )
+    .await;
+
+    assert!(matches!(parts[0], StreamPart::StreamStart { .. }));
+    assert!(matches!(
+        &parts[1],
+        StreamPart::ResponseMetadata { metadata }
+            if metadata.id.as_deref() == Some("resp_68c187cc09508192aa225af9734e2ed905ca09a4773fcd25")
+    ));
+
+    let text = streamed_text(&parts);
+    assert!(text.starts_with("Here’s what’s notable in Berlin today"));
+    assert_eq!(text.chars().count(), 2135);
+
+    let calls: Vec<_> = parts
+        .iter()
+        .filter_map(|p| match p {
+            StreamPart::ToolCall(call) => Some(call),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(calls.len(), 4);
+    assert!(calls
+        .iter()
+        .all(|c| c.tool_name == "web_search" && c.provider_executed == Some(true)));
+
+    let results = parts
+        .iter()
+        .filter(|p| matches!(p, StreamPart::ToolResult(_)))
+        .count();
+    assert_eq!(results, 4);
+
+    let sources = parts
+        .iter()
+        .filter(|p| matches!(p, StreamPart::Source(_)))
+        .count();
+    assert_eq!(sources, 6);
+
+    // Every reasoning item is opened and closed
+    let reasoning_starts = parts
+        .iter()
+        .filter(|p| matches!(p, StreamPart::ReasoningStart { .. }))
+        .count();
+    let reasoning_ends = parts
+        .iter()
+        .filter(|p| matches!(p, StreamPart::ReasoningEnd { .. }))
+        .count();
+    assert_eq!(reasoning_starts, 5);
+    assert_eq!(reasoning_starts, reasoning_ends);
+
+    let (usage, finish_reason) = finish_part(&parts);
+    assert_eq!(usage.input_tokens, Some(60093));
+    assert_eq!(usage.output_tokens, Some(4080));
+    assert_eq!(*finish_reason, FinishReason::Stop);
+}
+
+#[tokio::test]
+async fn test_stream_code_interpreter_input() {
+    let test_server = TestServer::new().await;
+    test_server
+        .mock_streaming_response(
+            "/v1/responses",
+            load_chunks_fixture("openai-code-interpreter-tool-1"),
+        )
+        .await;
+
+    let model = create_model(&test_server, "gpt-5-nano");
+    let parts = collect_stream(
+        &model,
+        CallOptions {
+            prompt: user_prompt("Simulate rolling two dice."),
+            ..Default::default()
+        },
+    )
+    .await;
+
+    let input_starts: Vec<_> = parts
+        .iter()
+        .filter_map(|p| match p {
+            StreamPart::ToolInputStart {
+                id,
+                tool_name,
+                provider_executed,
+                ..
+            } => Some((id.clone(), tool_name.clone(), *provider_executed)),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(input_starts.len(), 3);
+    assert_eq!(input_starts[0].1, "code_interpreter");
+    assert_eq!(input_starts[0].2, Some(true));
+
+    // The streamed code matches the code reported with the tool call
+    let first_id = &input_starts[0].0;
+    let streamed_code: String = parts
+        .iter()
+        .filter_map(|p| match p {
+            StreamPart::ToolInputDelta { id, delta, .. } if id == first_id => Some(delta.as_str()),
+            _ => None,
+        })
+        .collect();
+    let call = parts
+        .iter()
+        .find_map(|p| match p {
+            StreamPart::ToolCall(call) if &call.tool_call_id == first_id => Some(call),
+            _ => None,
+        })
+        .expect("Should have the tool call");
+    let input: serde_json::Value = serde_json::from_str(&call.input).unwrap();
+    assert_eq!(input["code"].as_str().unwrap(), streamed_code);
+
+    let input_ends = parts
+        .iter()
+        .filter(|p| matches!(p, StreamPart::ToolInputEnd { .. }))
+        .count();
+    assert_eq!(input_ends, 3);
+
+    assert!(streamed_text(&parts).starts_with("Here’s a simulation of rolling two fair"));
+}
+
+#[tokio::test]
+async fn test_stream_local_shell() {
+    let test_server = TestServer::new().await;
+    test_server
+        .mock_streaming_response(
+            "/v1/responses",
+            load_chunks_fixture("openai-local-shell-tool-1"),
+        )
+        .await;
+
+    let model = create_model(&test_server, "codex-mini-latest");
+    let parts = collect_stream(
+        &model,
+        CallOptions {
+            prompt: user_prompt("List the files in my home directory."),
+            ..Default::default()
+        },
+    )
+    .await;
+
+    let call = parts
+        .iter()
+        .find_map(|p| match p {
+            StreamPart::ToolCall(call) => Some(call),
+            _ => None,
+        })
+        .expect("Should have a tool call");
+    assert_eq!(call.tool_name, "local_shell");
+    assert_eq!(call.provider_executed, None);
+
+    let (usage, finish_reason) = finish_part(&parts);
+    assert_eq!(usage.reasoning_tokens, Some(128));
+    assert_eq!(*finish_reason, FinishReason::ToolCalls);
+}
+
+#[tokio::test]
+async fn test_stream_image_generation_and_mcp() {
+    for (fixture, tool_name) in [
+        ("openai-image-generation-tool-1", "image_generation"),
+        ("openai-mcp-tool-1", "mcp"),
+        ("openai-file-search-tool-1", "file_search"),
+    ] {
+        let test_server = TestServer::new().await;
+        test_server
+            .mock_streaming_response("/v1/responses", load_chunks_fixture(fixture))
+            .await;
+
+        let model = create_model(&test_server, "gpt-5");
+        let parts = collect_stream(
+            &model,
+            CallOptions {
+                prompt: user_prompt("Hello"),
+                ..Default::default()
+            },
+        )
+        .await;
+
+        let call = parts
+            .iter()
+            .find_map(|p| match p {
+                StreamPart::ToolCall(call) => Some(call),
+                _ => None,
+            })
+            .unwrap_or_else(|| panic!("{} should have a tool call", fixture));
+        assert_eq!(call.tool_name, tool_name);
+        assert_eq!(call.provider_executed, Some(true));
+        assert!(parts
+            .iter()
+            .any(|p| matches!(p, StreamPart::ToolResult(r) if r.tool_name == tool_name)));
+
+        let (_, finish_reason) = finish_part(&parts);
+        assert_eq!(*finish_reason, FinishReason::Stop);
+    }
+}
+
+#[tokio::test]
+async fn test_stream_raw_chunks() {
+    let test_server = TestServer::new().await;
+    test_server
+        .mock_streaming_response(
+            "/v1/responses",
+            load_chunks_fixture("openai-local-shell-tool-1"),
+        )
+        .await;
+
+    let model = create_model(&test_server, "codex-mini-latest");
+    let parts = collect_stream(
+        &model,
+        CallOptions {
+            prompt: user_prompt("Hello"),
+            include_raw_chunks: Some(true),
+            ..Default::default()
+        },
+    )
+    .await;
+
+    let raw_count = parts
+        .iter()
+        .filter(|p| matches!(p, StreamPart::Raw { .. }))
+        .count();
+    assert_eq!(raw_count, 7);
+
+    let request = test_server.last_request_body().await.unwrap();
+    assert_eq!(request["stream"], true);
+}
@@ -33,6 +33,10 @@ where
     /// Generates a list of embeddings for the given input values.
     ///
     /// Naming: "do" prefix to prevent accidental direct usage of the method by the user.
+    ///
+    /// # Errors
+    ///
+    /// Failed API calls are reported as [`ApiCallError`](crate::ApiCallError).
     async fn do_embed(
         &self,
         options: EmbedOptions<VALUE>,
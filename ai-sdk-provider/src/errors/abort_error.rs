@@ -0,0 +1,49 @@
+/// Error returned when an operation is cancelled through an
+/// [`AbortSignal`](crate::shared::AbortSignal).
+///
+/// Use [`AbortError::is_abort`] to tell a cancelled call apart from a failed
+/// one when the error is boxed or wrapped.
+///
+/// # Example
+///
+/// ```
+/// use ai_sdk_provider::AbortError;
+///
+/// let error: Box<dyn std::error::Error + Send + Sync> = Box::new(AbortError);
+/// assert!(AbortError::is_abort(error.as_ref()));
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, thiserror::Error)]
+#[error("The operation was aborted")]
+pub struct AbortError;
+
+impl AbortError {
+    /// Returns whether an `AbortError` is in an error or its chain of sources
+    pub fn is_abort(error: &(dyn std::error::Error + 'static)) -> bool {
+        let mut current = Some(error);
+        while let Some(error) = current {
+            if error.is::<AbortError>() {
+                return true;
+            }
+            current = error.source();
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, thiserror::Error)]
+    #[error("wrapped")]
+    struct Wrapper(#[source] AbortError);
+
+    #[test]
+    fn test_is_abort_in_source_chain() {
+        assert!(AbortError::is_abort(&AbortError));
+        assert!(AbortError::is_abort(&Wrapper(AbortError)));
+
+        let other: Box<dyn std::error::Error + Send + Sync> = "plain error".into();
+        assert!(!AbortError::is_abort(other.as_ref()));
+    }
+}
@@ -0,0 +1,229 @@
+use crate::SharedHeaders;
+use std::time::Duration;
+
+/// Error returned by a provider when an API call fails.
+///
+/// Providers return this error (boxed, or as the [`source`] of a
+/// provider-specific error) for failed HTTP requests, so callers can inspect
+/// the status code, headers and body without knowing the provider's own
+/// error type. Use [`ApiCallError::find`] to locate it in an error chain.
+///
+/// [`source`]: std::error::Error::source
+///
+/// # Example
+///
+/// ```
+/// use ai_sdk_provider::ApiCallError;
+///
+/// let error = ApiCallError::new("Rate limit exceeded", "https://api.example.com/v1/chat")
+///     .with_status_code(429)
+///     .with_response_body(r#"{"error":{"message":"Rate limit exceeded"}}"#);
+///
+/// assert_eq!(error.status_code, Some(429));
+/// assert!(error.is_retryable);
+/// ```
+#[derive(Debug, Clone, thiserror::Error)]
+#[error("{message}")]
+pub struct ApiCallError {
+    /// Human-readable error message
+    pub message: String,
+    /// URL of the failed request
+    pub url: String,
+    /// HTTP status code, if a response was received
+    pub status_code: Option<u16>,
+    /// Response headers, if a response was received
+    pub response_headers: Option<SharedHeaders>,
+    /// Raw response body, if a response was received
+    pub response_body: Option<String>,
+    /// Whether repeating the same request may succeed
+    pub is_retryable: bool,
+}
+
+impl ApiCallError {
+    /// Creates a new non-retryable error for a request to `url`
+    pub fn new(message: impl Into<String>, url: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            url: url.into(),
+            status_code: None,
+            response_headers: None,
+            response_body: None,
+            is_retryable: false,
+        }
+    }
+
+    /// Sets the HTTP status code and derives `is_retryable` from it
+    ///
+    /// See [`ApiCallError::is_retryable_status`] for the classification.
+    pub fn with_status_code(mut self, status_code: u16) -> Self {
+        self.status_code = Some(status_code);
+        self.is_retryable = Self::is_retryable_status(status_code);
+        self
+    }
+
+    /// Sets the response headers
+    pub fn with_response_headers(mut self, headers: SharedHeaders) -> Self {
+        self.response_headers = Some(headers);
+        self
+    }
+
+    /// Sets the raw response body
+    pub fn with_response_body(mut self, body: impl Into<String>) -> Self {
+        self.response_body = Some(body.into());
+        self
+    }
+
+    /// Overrides whether the error is retryable
+    pub fn with_retryable(mut self, is_retryable: bool) -> Self {
+        self.is_retryable = is_retryable;
+        self
+    }
+
+    /// Returns whether a response with this status code is worth retrying
+    ///
+    /// Request timeouts (408), conflicts (409), rate limits (429) and server
+    /// errors (5xx) are retryable; all other statuses are not.
+    pub fn is_retryable_status(status_code: u16) -> bool {
+        matches!(status_code, 408 | 409 | 429) || status_code >= 500
+    }
+
+    /// Returns a response header by name (case-insensitive)
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.response_headers.as_ref().and_then(|headers| {
+            headers
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(name))
+                .map(|(_, value)| value.as_str())
+        })
+    }
+
+    /// Returns the server's requested back-off, if any
+    ///
+    /// Reads the `retry-after-ms` header (milliseconds) and falls back to
+    /// `retry-after` (seconds). HTTP-date values are not supported.
+    pub fn retry_after(&self) -> Option<Duration> {
+        // Negative, non-finite and out-of-range values are ignored
+        let seconds = |name: &str, scale: f64| {
+            self.header(name)
+                .and_then(|value| value.trim().parse::<f64>().ok())
+                .and_then(|value| Duration::try_from_secs_f64(value / scale).ok())
+        };
+
+        seconds("retry-after-ms", 1000.0).or_else(|| seconds("retry-after", 1.0))
+    }
+
+    /// Finds an `ApiCallError` in an error or its chain of sources
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ai_sdk_provider::ApiCallError;
+    ///
+    /// let error: Box<dyn std::error::Error + Send + Sync> =
+    ///     Box::new(ApiCallError::new("Bad request", "https://api.example.com").with_status_code(400));
+    ///
+    /// let api_error = ApiCallError::find(error.as_ref()).unwrap();
+    /// assert!(!api_error.is_retryable);
+    /// ```
+    pub fn find<'a>(error: &'a (dyn std::error::Error + 'static)) -> Option<&'a ApiCallError> {
+        let mut current = Some(error);
+        while let Some(error) = current {
+            if let Some(api_error) = error.downcast_ref::<ApiCallError>() {
+                return Some(api_error);
+            }
+            current = error.source();
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[derive(Debug, thiserror::Error)]
+    #[error("wrapped")]
+    struct Wrapper(#[source] ApiCallError);
+
+    #[test]
+    fn test_retryable_status_codes() {
+        for status in [408, 409, 429, 500, 502, 503, 504] {
+            assert!(ApiCallError::is_retryable_status(status), "{}", status);
+        }
+        for status in [400, 401, 403, 404, 422] {
+            assert!(!ApiCallError::is_retryable_status(status), "{}", status);
+        }
+    }
+
+    #[test]
+    fn test_builder() {
+        let error = ApiCallError::new("Server error", "https://api.example.com")
+            .with_status_code(503)
+            .with_response_headers(HashMap::from([(
+                "Retry-After".to_string(),
+                "2".to_string(),
+            )]))
+            .with_response_body("overloaded");
+
+        assert_eq!(error.to_string(), "Server error");
+        assert_eq!(error.url, "https://api.example.com");
+        assert!(error.is_retryable);
+        assert_eq!(error.header("retry-after"), Some("2"));
+        assert_eq!(error.response_body.as_deref(), Some("overloaded"));
+
+        let error = error.with_retryable(false);
+        assert!(!error.is_retryable);
+    }
+
+    #[test]
+    fn test_retry_after() {
+        let error = |headers: &[(&str, &str)]| {
+            ApiCallError::new("Rate limited", "url")
+                .with_status_code(429)
+                .with_response_headers(
+                    headers
+                        .iter()
+                        .map(|(k, v)| (k.to_string(), v.to_string()))
+                        .collect(),
+                )
+        };
+
+        assert_eq!(
+            error(&[("retry-after", "2")]).retry_after(),
+            Some(Duration::from_secs(2))
+        );
+        assert_eq!(
+            error(&[("retry-after", "2"), ("retry-after-ms", "250")]).retry_after(),
+            Some(Duration::from_millis(250))
+        );
+        assert_eq!(
+            error(&[("retry-after", "Wed, 21 Oct 2015 07:28:00 GMT")]).retry_after(),
+            None
+        );
+        assert_eq!(error(&[]).retry_after(), None);
+    }
+
+    #[test]
+    fn test_retry_after_out_of_range() {
+        let error = |name: &str, value: &str| {
+            ApiCallError::new("Rate limited", "url")
+                .with_status_code(429)
+                .with_response_headers([(name.to_string(), value.to_string())].into())
+        };
+
+        assert_eq!(error("retry-after", "1e30").retry_after(), None);
+        assert_eq!(error("retry-after-ms", "1e30").retry_after(), None);
+        assert_eq!(error("retry-after", "-1").retry_after(), None);
+        assert_eq!(error("retry-after", "inf").retry_after(), None);
+    }
+
+    #[test]
+    fn test_find_in_source_chain() {
+        let error = Wrapper(ApiCallError::new("Unauthorized", "url").with_status_code(401));
+        assert_eq!(ApiCallError::find(&error).unwrap().status_code, Some(401));
+
+        let other: Box<dyn std::error::Error + Send + Sync> = "plain error".into();
+        assert!(ApiCallError::find(other.as_ref()).is_none());
+    }
+}
@@ -0,0 +1,5 @@
+mod abort_error;
+mod api_call_error;
+
+pub use abort_error::AbortError;
+pub use api_call_error::ApiCallError;
@@ -27,6 +27,10 @@ pub trait ImageModel: Send + Sync {
     /// Generates an array of images.
     ///
     /// Naming: "do" prefix to prevent accidental direct usage of the method by the user.
+    ///
+    /// # Errors
+    ///
+    /// Failed API calls are reported as [`ApiCallError`](crate::ApiCallError).
     async fn do_generate(
         &self,
         options: ImageGenerateOptions,
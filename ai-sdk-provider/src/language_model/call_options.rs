@@ -1,6 +1,6 @@
 use super::prompt::Prompt;
 use super::tools::{Tool, ToolChoice};
-use crate::SharedProviderOptions;
+use crate::{AbortSignal, SharedProviderOptions};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
@@ -66,6 +66,10 @@ pub struct CallOptions {
     /// Provider-specific options
     #[serde(skip_serializing_if = "Option::is_none")]
     pub provider_options: Option<SharedProviderOptions>,
+
+    /// Signal for cancelling the call, including an in-progress stream
+    #[serde(skip)]
+    pub abort_signal: Option<AbortSignal>,
 }
 
 /// Format for the model's response
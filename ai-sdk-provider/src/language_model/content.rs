@@ -124,6 +124,10 @@ pub struct ToolResultPart {
     /// Structured output from the tool execution
     pub output: ToolResultOutput,
 
+    /// Whether the tool was executed by the provider
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider_executed: Option<bool>,
+
     /// Whether the result is preliminary
     #[serde(skip_serializing_if = "Option::is_none")]
     pub preliminary: Option<bool>,
@@ -197,6 +201,7 @@ mod tests {
                 value: "success".into(),
                 provider_metadata: None,
             },
+            provider_executed: None,
             preliminary: None,
             provider_metadata: None,
         };
@@ -215,6 +220,7 @@ mod tests {
                 value: "error occurred".into(),
                 provider_metadata: None,
             },
+            provider_executed: None,
             preliminary: None,
             provider_metadata: None,
         };
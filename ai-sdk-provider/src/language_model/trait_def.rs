@@ -28,12 +28,21 @@ pub trait LanguageModel: Send + Sync {
     }
 
     /// Generate a language model output (non-streaming)
+    ///
+    /// # Errors
+    ///
+    /// Failed API calls are reported as [`ApiCallError`](crate::ApiCallError).
     async fn do_generate(
         &self,
         options: CallOptions,
     ) -> Result<GenerateResponse, Box<dyn std::error::Error + Send + Sync>>;
 
     /// Generate a language model output (streaming)
+    ///
+    /// # Errors
+    ///
+    /// Errors that occur before the stream starts, such as a rejected
+    /// request, are reported as [`ApiCallError`](crate::ApiCallError).
     async fn do_stream(
         &self,
         options: CallOptions,
@@ -94,6 +103,12 @@ pub struct ResponseInfo {
 /// Error during streaming
 #[derive(Debug, thiserror::Error)]
 pub enum StreamError {
+    /// The connection to the provider failed while streaming
+    #[error("Stream error: {0}")]
+    ApiCall(#[from] crate::ApiCallError),
+    /// The stream was cancelled through the call's abort signal
+    #[error("Stream error: {0}")]
+    Aborted(#[from] crate::AbortError),
     /// Other streaming error
     #[error("Stream error: {0}")]
     Other(String),
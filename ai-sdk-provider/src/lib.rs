@@ -13,6 +13,16 @@
 //! - **Transcription Models** - Speech-to-text transcription
 //! - **Reranking Models** - Document reranking by relevance
 //!
+//! ## Errors
+//!
+//! Model methods return boxed errors. When an API call fails, providers
+//! return an [`ApiCallError`] (directly or as the source of their own error
+//! type) carrying the status code, response headers and body, and whether
+//! the request may be retried. Use [`ApiCallError::find`] to get at it.
+//!
+//! Calls can be cancelled by passing an [`AbortSignal`] in their options;
+//! providers then return an [`AbortError`].
+//!
 //! ## Example
 //!
 //! ```rust,ignore
@@ -47,6 +57,8 @@
 
 /// Embedding model interfaces and types for text embedding generation.
 pub mod embedding_model;
+/// Error types shared by all providers.
+pub mod errors;
 /// Image model interfaces and types for image generation.
 pub mod image_model;
 /// JSON value types for provider metadata and structured data.
@@ -66,6 +78,7 @@ pub mod transcription_model;
 
 // Re-export commonly used types
 pub use embedding_model::{EmbedOptions, EmbedResponse, Embedding, EmbeddingModel, EmbeddingUsage};
+pub use errors::{AbortError, ApiCallError};
 pub use image_model::{
     CallWarning as ImageCallWarning, ImageData, ImageGenerateOptions, ImageGenerateResponse,
     ImageModel, ImageProviderMetadata,
@@ -80,7 +93,10 @@ pub use reranking_model::{
     Documents, RankingItem, RerankOptions, RerankResponse, RerankingModel,
     ResponseInfo as RerankingResponseInfo,
 };
-pub use shared::{SharedHeaders, SharedProviderMetadata, SharedProviderOptions, SharedWarning};
+pub use shared::{
+    abortable, AbortController, AbortSignal, SharedHeaders, SharedProviderMetadata,
+    SharedProviderOptions, SharedWarning,
+};
 pub use speech_model::{
     AudioData, CallWarning as SpeechCallWarning, SpeechGenerateOptions, SpeechGenerateResponse,
     SpeechModel,
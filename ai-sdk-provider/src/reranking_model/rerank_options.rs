@@ -29,7 +29,7 @@ pub struct RerankOptions {
     pub top_n: Option<usize>,
 
     /// Abort signal for cancelling the operation
-    pub abort_signal: Option<tokio::sync::watch::Receiver<bool>>,
+    pub abort_signal: Option<crate::AbortSignal>,
 
     /// Additional provider-specific options
     pub provider_options: Option<SharedProviderOptions>,
@@ -25,6 +25,10 @@ pub trait RerankingModel: Send + Sync {
     /// # Returns
     ///
     /// A `RerankResponse` containing the ranked list of documents sorted by relevance score
+    ///
+    /// # Errors
+    ///
+    /// Failed API calls are reported as [`ApiCallError`](crate::ApiCallError).
     async fn do_rerank(
         &self,
         options: RerankOptions,
@@ -0,0 +1,171 @@
+use crate::AbortError;
+use std::future::Future;
+use std::sync::Arc;
+use tokio::sync::watch;
+
+/// Cancels operations holding one of its [`AbortSignal`]s.
+///
+/// Clones share the same signal, so a controller can be handed to another
+/// task (e.g. a request handler that notices the client disconnected).
+///
+/// # Example
+///
+/// ```
+/// use ai_sdk_provider::AbortController;
+///
+/// let controller = AbortController::new();
+/// let signal = controller.signal();
+/// assert!(!signal.is_aborted());
+///
+/// controller.abort();
+/// assert!(signal.is_aborted());
+/// ```
+#[derive(Debug, Clone)]
+pub struct AbortController {
+    sender: Arc<watch::Sender<bool>>,
+}
+
+impl AbortController {
+    /// Creates a controller that has not been aborted
+    pub fn new() -> Self {
+        let (sender, _) = watch::channel(false);
+        Self {
+            sender: Arc::new(sender),
+        }
+    }
+
+    /// Returns a signal that is aborted when this controller is
+    pub fn signal(&self) -> AbortSignal {
+        AbortSignal(self.sender.subscribe())
+    }
+
+    /// Aborts every operation holding a signal from this controller
+    pub fn abort(&self) {
+        self.sender.send_replace(true);
+    }
+
+    /// Returns whether [`abort`](Self::abort) has been called
+    pub fn is_aborted(&self) -> bool {
+        *self.sender.borrow()
+    }
+}
+
+impl Default for AbortController {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Signal used to cancel an in-flight operation.
+///
+/// Obtained from an [`AbortController`], or from the receiving half of a
+/// `tokio::sync::watch::channel(false)` where sending `true` aborts. A signal
+/// whose sender is dropped without aborting never fires.
+#[derive(Debug, Clone)]
+pub struct AbortSignal(watch::Receiver<bool>);
+
+impl AbortSignal {
+    /// Returns whether the signal has been aborted
+    pub fn is_aborted(&self) -> bool {
+        *self.0.borrow()
+    }
+
+    /// Resolves once the signal is aborted
+    pub async fn aborted(&self) {
+        let mut receiver = self.0.clone();
+        if receiver.wait_for(|aborted| *aborted).await.is_err() {
+            std::future::pending::<()>().await;
+        }
+    }
+}
+
+impl From<watch::Receiver<bool>> for AbortSignal {
+    fn from(receiver: watch::Receiver<bool>) -> Self {
+        Self(receiver)
+    }
+}
+
+/// Signals are equal when they observe the same controller
+impl PartialEq for AbortSignal {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.same_channel(&other.0)
+    }
+}
+
+/// Runs `future` until it completes or `signal` is aborted
+///
+/// The future is dropped on abort, which cancels any HTTP request it has in
+/// flight. Without a signal the future simply runs to completion.
+///
+/// # Example
+///
+/// ```
+/// use ai_sdk_provider::{abortable, AbortController, AbortError};
+///
+/// # tokio::runtime::Runtime::new().unwrap().block_on(async {
+/// let controller = AbortController::new();
+/// controller.abort();
+///
+/// let result = abortable(Some(&controller.signal()), std::future::pending::<()>()).await;
+/// assert_eq!(result, Err(AbortError));
+/// # });
+/// ```
+pub async fn abortable<F: Future>(
+    signal: Option<&AbortSignal>,
+    future: F,
+) -> Result<F::Output, AbortError> {
+    match signal {
+        Some(signal) => tokio::select! {
+            biased;
+            _ = signal.aborted() => Err(AbortError),
+            output = future => Ok(output),
+        },
+        None => Ok(future.await),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_abort_cancels_pending_future() {
+        let controller = AbortController::new();
+        let signal = controller.signal();
+
+        let task = tokio::spawn(async move {
+            abortable(Some(&signal), tokio::time::sleep(Duration::from_secs(60))).await
+        });
+        controller.abort();
+
+        assert_eq!(task.await.unwrap(), Err(AbortError));
+    }
+
+    #[tokio::test]
+    async fn test_completes_without_abort() {
+        let controller = AbortController::new();
+        assert_eq!(
+            abortable(Some(&controller.signal()), async { 42 }).await,
+            Ok(42)
+        );
+        assert_eq!(abortable(None, async { 42 }).await, Ok(42));
+    }
+
+    #[tokio::test]
+    async fn test_dropped_sender_never_aborts() {
+        let (sender, receiver) = watch::channel(false);
+        let signal = AbortSignal::from(receiver);
+        drop(sender);
+
+        assert!(!signal.is_aborted());
+        assert_eq!(abortable(Some(&signal), async { "done" }).await, Ok("done"));
+    }
+
+    #[test]
+    fn test_signal_equality() {
+        let controller = AbortController::new();
+        assert_eq!(controller.signal(), controller.signal());
+        assert_ne!(controller.signal(), AbortController::new().signal());
+    }
+}
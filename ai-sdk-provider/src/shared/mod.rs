@@ -1,3 +1,7 @@
+mod abort;
+
+pub use abort::{abortable, AbortController, AbortSignal};
+
 use crate::json_value::JsonObject;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -29,7 +29,7 @@ pub struct SpeechGenerateOptions {
     pub provider_options: Option<SharedProviderOptions>,
 
     /// Abort signal for cancelling the operation.
-    pub abort_signal: Option<tokio::sync::watch::Receiver<bool>>,
+    pub abort_signal: Option<crate::AbortSignal>,
 
     /// Additional HTTP headers to be sent with the request.
     /// Only applicable for HTTP-based providers.
@@ -19,6 +19,10 @@ pub trait SpeechModel: Send + Sync {
     fn model_id(&self) -> &str;
 
     /// Generates speech audio from text.
+    ///
+    /// # Errors
+    ///
+    /// Failed API calls are reported as [`ApiCallError`](crate::ApiCallError).
     async fn do_generate(
         &self,
         options: SpeechGenerateOptions,
@@ -20,6 +20,10 @@ pub trait TranscriptionModel: Send + Sync {
     fn model_id(&self) -> &str;
 
     /// Generates a transcript from audio.
+    ///
+    /// # Errors
+    ///
+    /// Failed API calls are reported as [`ApiCallError`](crate::ApiCallError).
     async fn do_generate(
         &self,
         options: TranscriptionOptions,
@@ -39,8 +39,7 @@ pub struct TranscriptionOptions {
     pub provider_options: Option<SharedProviderOptions>,
 
     /// Abort signal for cancelling the operation.
-    /// Currently not implemented - reserved for future use.
-    pub abort_signal: Option<()>,
+    pub abort_signal: Option<crate::AbortSignal>,
 
     /// Additional HTTP headers to be sent with the request.
     /// Only applicable for HTTP-based providers.